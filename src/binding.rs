@@ -4,30 +4,76 @@
 //! (via plugs) or what they could depend on (via sockets). It bundles one or more WIT
 //! [`Interface`]s under a single identifier.
 
-use std::sync::Arc ;
-use std::collections::HashMap ;
-use futures::lock::Mutex ;
+use std::sync::{ Arc, PoisonError, RwLock };
+use std::sync::atomic::{ AtomicBool, AtomicUsize, Ordering };
+use std::collections::{ HashMap, HashSet };
 use wasmtime::component::{ Linker, Val };
 
-use crate::{ Interface, PluginContext };
+use crate::{ CallContext, ComponentInfo, DispatchId, Function, Interface, InterfaceBuilder, PluginContext };
 use crate::cardinality::{ Any, AtLeastOne, AtMostOne, Cardinality, ExactlyOne };
+use crate::plugin_lock::{ BindableInstance, LockStats, PluginLock };
+use crate::snapshot::{ CardinalityKind, GraphSnapshot, SnapshotError };
+use crate::socket_shape::{ self, Imports, SocketShapeError };
 use crate::plugin_instance::{ PluginInstanceAsync, PluginInstanceSync };
 
 
 
 type PluginSockets<PluginId, Plugins, Instance> =
-	<Plugins as Cardinality<PluginId, Instance>>::Rebind<Arc<Mutex<Instance>>> ;
+	<Plugins as Cardinality<PluginId, Instance>>::Rebind<Arc<PluginLock<Instance>>> ;
 
 type DispatchResults<PluginId, Plugins, Instance> =
-	<PluginSockets<PluginId, Plugins, Instance> as Cardinality<PluginId, Arc<Mutex<Instance>>>>::Rebind<
+	<PluginSockets<PluginId, Plugins, Instance> as Cardinality<PluginId, Arc<PluginLock<Instance>>>>::Rebind<
 		Result<wasmtime::component::Val, crate::DispatchError>
 	>;
 
+type TracedResults<PluginId, Plugins, Instance> =
+	( DispatchId, Result<DispatchResults<PluginId, Plugins, Instance>, crate::DispatchError> );
+
+#[cfg( feature = "wave" )]
+type WaveResults<PluginId, Plugins, Instance> =
+	<PluginSockets<PluginId, Plugins, Instance> as Cardinality<PluginId, Arc<PluginLock<Instance>>>>::Rebind<
+		Result<String, crate::wave::WaveError>
+	>;
+
+#[cfg( feature = "wave" )]
+type ConformanceReports<PluginId, Plugins, Instance> =
+	<PluginSockets<PluginId, Plugins, Instance> as Cardinality<PluginId, Arc<PluginLock<Instance>>>>::Rebind<
+		crate::conformance::ConformanceReport
+	>;
+
+type LockStatsOf<PluginId, Plugins, Instance> =
+	<PluginSockets<PluginId, Plugins, Instance> as Cardinality<PluginId, Arc<PluginLock<Instance>>>>::Rebind<LockStats> ;
+
 type DispatchVals<PluginId, Plugins, Instance> =
-	<PluginSockets<PluginId, Plugins, Instance> as Cardinality<PluginId, Arc<Mutex<Instance>>>>::Rebind<
+	<PluginSockets<PluginId, Plugins, Instance> as Cardinality<PluginId, Arc<PluginLock<Instance>>>>::Rebind<
 		wasmtime::component::Val
 	>;
 
+/// Called with the interface path and function name of a dispatch every plugin failed.
+type AllFailedHook = Arc<dyn Fn( &str, &str ) + Send + Sync>;
+
+/// Counts the calls of a dispatch that failed, to tell whether all of them did.
+/// Plugins disabled with [`Binding::set_enabled`] are not counted.
+#[derive( Default )]
+pub(crate) struct Outcomes {
+	total: AtomicUsize,
+	failed: AtomicUsize,
+}
+
+impl Outcomes {
+	pub(crate) fn record<T>( &self, result: Result<T, crate::DispatchError> ) -> Result<T, crate::DispatchError> {
+		if matches!( result, Err( crate::DispatchError::PluginDisabled )) { return result }
+		self.total.fetch_add( 1, Ordering::Relaxed );
+		if result.is_err() { self.failed.fetch_add( 1, Ordering::Relaxed ); }
+		result
+	}
+
+	fn all_failed( &self ) -> bool {
+		let total = self.total.load( Ordering::Relaxed );
+		total > 0 && self.failed.load( Ordering::Relaxed ) == total
+	}
+}
+
 struct BindingData<PluginId, Plugins, Instance>
 where
 	PluginId: std::hash::Hash + Eq + Clone + Send + Sync + 'static,
@@ -38,6 +84,52 @@ where
 	package_name: String,
 	interfaces: HashMap<String, Interface>,
 	plugins: PluginSockets<PluginId, Plugins, Instance>,
+	all_failed: RwLock<Option<AllFailedHook>>,
+	direct_results: AtomicBool,
+}
+
+/// Declares a [`Binding`] interface by interface, created with [`Binding::builder`].
+pub struct BindingBuilder<PluginId, Ctx, Plugins = ExactlyOne<PluginId, PluginInstanceSync<Ctx>>, Instance = PluginInstanceSync<Ctx>> {
+	package_name: String,
+	interfaces: HashMap<String, Interface>,
+	context: std::marker::PhantomData<fn() -> ( PluginId, Ctx )>,
+	plugins: std::marker::PhantomData<fn() -> ( Plugins, Instance )>,
+}
+
+impl<PluginId, Ctx, Plugins, Instance> std::fmt::Debug for BindingBuilder<PluginId, Ctx, Plugins, Instance> {
+	fn fmt( &self, f: &mut std::fmt::Formatter<'_> ) -> std::fmt::Result {
+		f.debug_struct( "BindingBuilder" )
+			.field( "package_name", &self.package_name )
+			.field( "interfaces", &self.interfaces )
+			.finish()
+	}
+}
+
+impl<PluginId, Ctx, Plugins, Instance> BindingBuilder<PluginId, Ctx, Plugins, Instance> {
+
+	/// Declares the interface `name` with the items added by `declare`, replacing any
+	/// earlier declaration of the same name.
+	pub fn interface( mut self, name: impl Into<String>, declare: impl FnOnce( InterfaceBuilder ) -> InterfaceBuilder ) -> Self {
+		self.interfaces.insert( name.into(), declare( Interface::builder() ).build() );
+		self
+	}
+
+}
+
+impl<PluginId, Ctx, Plugins, Instance> BindingBuilder<PluginId, Ctx, Plugins, Instance>
+where
+	PluginId: std::hash::Hash + Eq + Clone + Send + Sync + 'static,
+	Ctx: PluginContext + 'static,
+	Instance: BindableInstance + Send + 'static,
+	Plugins: Cardinality<PluginId, Instance> + 'static,
+	PluginSockets<PluginId, Plugins, Instance>: Cardinality<PluginId, Arc<PluginLock<Instance>>> + Send + Sync,
+{
+
+	/// Creates the binding, implemented by `plugins`.
+	pub fn build( self, plugins: Plugins ) -> Binding<PluginId, Ctx, Plugins, Instance> {
+		Binding::new( self.package_name, self.interfaces, plugins )
+	}
+
 }
 
 /// An abstract contract specifying what plugins must implement (via plugs) or what
@@ -50,7 +142,7 @@ where
 ///
 /// ```
 /// # use std::collections::{ HashMap, HashSet };
-/// # use wasm_link::{ Binding, Interface, Function, FunctionKind, ReturnKind, Plugin, Engine, Component, Linker, ResourceTable };
+/// # use wasm_link::{ Binding, Interface, ReturnKind, Plugin, Engine, Component, Linker, ResourceTable };
 /// # use wasm_link::cardinality::ExactlyOne ;
 /// # struct Ctx { resource_table: ResourceTable }
 /// # impl wasm_link::PluginContext for Ctx {
@@ -63,13 +155,10 @@ where
 /// let binding: Binding<String, Ctx> = Binding::new(
 /// 	"my:package",
 /// 	HashMap::from([
-/// 		( "api".to_string(), Interface::new(
-/// 			HashMap::from([( "get-value".into(), Function::new(
-/// 				FunctionKind::Freestanding,
-/// 				ReturnKind::MayContainResources,
-/// 			))]),
-/// 			HashSet::from([ "my-resource".to_string() ]),
-/// 		)),
+/// 		( "api".to_string(), Interface::builder()
+/// 			.function( "get-value", ReturnKind::MayContainResources )
+/// 			.resource( "my-resource" )
+/// 			.build()),
 /// 	]),
 /// 	ExactlyOne( "my-plugin".to_string(), plugin ),
 /// );
@@ -135,25 +224,302 @@ where
 	Ctx: PluginContext + 'static,
 	Instance: Send + 'static,
 	Plugins: Cardinality<PluginId, Instance> + 'static,
-	PluginSockets<PluginId, Plugins, Instance>: Cardinality<PluginId, Arc<Mutex<Instance>>> + Send + Sync,
+	PluginSockets<PluginId, Plugins, Instance>: Cardinality<PluginId, Arc<PluginLock<Instance>>> + Send + Sync,
 {
 
+	/// Starts declaring a binding of the package `package_name` interface by interface.
+	///
+	/// ```
+	/// # use wasm_link::{ Binding, Component, Engine, Linker, Plugin, ReturnKind, ResourceTable };
+	/// # use wasm_link::cardinality::ExactlyOne ;
+	/// # struct Ctx { resource_table: ResourceTable }
+	/// # impl wasm_link::PluginContext for Ctx {
+	/// # 	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.resource_table }
+	/// # }
+	/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+	/// # let engine = Engine::default();
+	/// # let linker = Linker::new( &engine );
+	/// # let plugin = Plugin::new( Component::new( &engine, "(component)" )?, Ctx { resource_table: ResourceTable::new() }).instantiate( &engine, &linker )?;
+	/// let binding: Binding<String, Ctx> = Binding::builder( "my:package" )
+	/// 	.interface( "api", | api | api
+	/// 		.function( "get-value", ReturnKind::MayContainResources )
+	/// 		.resource( "my-resource" ))
+	/// 	.build( ExactlyOne( "my-plugin".to_string(), plugin ));
+	/// assert!( binding.interface( "api" ).is_some() );
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn builder( package_name: impl Into<String> ) -> BindingBuilder<PluginId, Ctx, Plugins, Instance> {
+		BindingBuilder {
+			package_name: package_name.into(),
+			interfaces: HashMap::new(),
+			context: std::marker::PhantomData,
+			plugins: std::marker::PhantomData,
+		}
+	}
+
 	/// Creates a new binding specification.
 	pub fn new(
 		package_name: impl Into<String>,
 		interfaces: HashMap<String, Interface>,
 		plugins: Plugins
-	) -> Self {
+	) -> Self
+	where
+		Instance: BindableInstance,
+	{
 		Self( Arc::new( BindingData {
 			package_name: package_name.into(),
 			interfaces,
-			plugins: plugins.map_mut(| plugin | Arc::new( Mutex::new( plugin ))),
+			plugins: plugins.map_mut( PluginLock::shared ),
+			all_failed: RwLock::new( None ),
+			direct_results: AtomicBool::new( false ),
 		}), std::marker::PhantomData )
 	}
 
+	/// Returns a handle dispatching to the interface `name` of this binding, or `None`
+	/// if the binding has no such interface.
+	///
+	/// The handle looks the interface up once, so its calls can only fail to resolve
+	/// the function, and lists the functions that can be called.
+	///
+	/// ```
+	/// # use std::collections::{ HashMap, HashSet };
+	/// # use wasm_link::{ Binding, Component, Engine, Interface, Linker, Plugin, PluginContext, ResourceTable, ReturnKind };
+	/// # use wasm_link::cardinality::ExactlyOne ;
+	/// # struct Context { table: ResourceTable }
+	/// # impl PluginContext for Context { fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.table } }
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// # let engine = Engine::default();
+	/// # let linker = Linker::new( &engine );
+	/// # let plugin = Plugin::new( Component::new( &engine, "(component)" )?, Context { table: ResourceTable::new() })
+	/// # 	.instantiate( &engine, &linker )?;
+	/// let binding: Binding<String, Context> = Binding::new(
+	/// 	"my:package",
+	/// 	HashMap::from([( "api".to_string(), Interface::builder().function( "get-value", ReturnKind::AssumeNoResources ).build())]),
+	/// 	ExactlyOne( "plugin".to_string(), plugin ),
+	/// );
+	/// let api = binding.interface( "api" ).expect( "declared above" );
+	/// assert_eq!( api.path(), "my:package/api" );
+	/// assert_eq!( api.functions().map(|( name, _ )| name ).collect::<Vec<_>>(), [ "get-value" ]);
+	/// # Ok(()) }
+	/// ```
+	pub fn interface( &self, name: &str ) -> Option<InterfaceHandle<'_, PluginId, Ctx, Plugins, Instance>> {
+		let ( name, interface ) = self.0.interfaces.get_key_value( name )?;
+		Some( InterfaceHandle { binding: self, name, interface, path: format!( "{}/{}", self.0.package_name, name ) })
+	}
+
+	/// Returns lock contention statistics of each plugin in this binding.
+	///
+	/// Dispatches to a plugin are serialized by a lock. The statistics cover every
+	/// dispatch to the plugin, whether made by the host or by another plugin, and help
+	/// find plugins that limit the throughput of the binding.
+	///
+	/// ```
+	/// # use std::collections::{ HashMap, HashSet };
+	/// # use wasm_link::{ Binding, Component, Engine, Linker, Plugin, PluginContext, ResourceTable };
+	/// # use wasm_link::cardinality::ExactlyOne ;
+	/// # struct Context { table: ResourceTable }
+	/// # impl PluginContext for Context { fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.table } }
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// # let engine = Engine::default();
+	/// # let linker = Linker::new( &engine );
+	/// # let plugin = Plugin::new( Component::new( &engine, "(component)" )?, Context { table: ResourceTable::new() })
+	/// # 	.instantiate( &engine, &linker )?;
+	/// let binding: Binding<String, Context> = Binding::new( "my:package", HashMap::new(), ExactlyOne( "plugin".to_string(), plugin ));
+	/// let ExactlyOne( _, stats ) = binding.lock_stats();
+	/// assert_eq!( stats.acquisitions, 0 );
+	/// # Ok(()) }
+	/// ```
+	pub fn lock_stats( &self ) -> LockStatsOf<PluginId, Plugins, Instance> {
+		self.0.plugins.map(| _, plugin | plugin.stats())
+	}
+
+	/// Enables or disables the plugin with the given id, returning `false` if the
+	/// binding has no such plugin.
+	///
+	/// Disabled plugins stay loaded but are not invoked: broadcast dispatches skip
+	/// them, and calls to methods of resources they own fail with
+	/// [`DispatchError::PluginDisabled`]( crate::DispatchError::PluginDisabled ). Skipped
+	/// plugins do not count towards [`on_all_failed`](Self::on_all_failed), so disabling
+	/// plugins never makes a dispatch fail with
+	/// [`DispatchError::AllImplementationsFailed`]( crate::DispatchError::AllImplementationsFailed ).
+	/// As the shape of broadcast results is fixed by the cardinality, a skipped plugin
+	/// still has an entry in them, holding `PluginDisabled`. This takes a misbehaving
+	/// plugin out of rotation without unloading it and losing its state. Calls already
+	/// in progress are not interrupted.
+	///
+	/// ```
+	/// # use std::collections::{ HashMap, HashSet };
+	/// # use wasm_link::{ Binding, Component, DispatchError, Engine, Linker, Plugin, PluginContext, ResourceTable };
+	/// # use wasm_link::cardinality::Any ;
+	/// # struct Context { table: ResourceTable }
+	/// # impl PluginContext for Context { fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.table } }
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// # let engine = Engine::default();
+	/// # let linker = Linker::new( &engine );
+	/// # let plugin = || Plugin::new( Component::new( &engine, "(component)" ).unwrap(), Context { table: ResourceTable::new() })
+	/// # 	.instantiate( &engine, &linker );
+	/// let binding: Binding<String, Context, Any<String, _>> = Binding::new(
+	/// 	"my:package",
+	/// 	HashMap::new(),
+	/// 	Any( HashMap::from([( "a".to_string(), plugin()? ), ( "b".to_string(), plugin()? )])),
+	/// );
+	/// assert!( binding.set_enabled( &"a".to_string(), false ));
+	/// assert_eq!( binding.is_enabled( &"a".to_string() ), Some( false ));
+	/// assert!( !binding.set_enabled( &"c".to_string(), false ));
+	/// # Ok(()) }
+	/// ```
+	pub fn set_enabled( &self, id: &PluginId, enabled: bool ) -> bool {
+		self.0.plugins.get( id ).map(| plugin | plugin.set_enabled( enabled )).is_some()
+	}
+
+	/// Returns whether the plugin with the given id is enabled, or `None` if the
+	/// binding has no such plugin.
+	///
+	/// See [`set_enabled`](Self::set_enabled).
+	pub fn is_enabled( &self, id: &PluginId ) -> Option<bool> {
+		self.0.plugins.get( id ).map(| plugin | plugin.is_enabled() )
+	}
+
+	/// Returns facts about the compiled component of the plugin with the given id, or
+	/// `None` if the binding has no such plugin.
+	///
+	/// ```
+	/// # use std::collections::{ HashMap, HashSet };
+	/// # use wasm_link::{ Binding, Component, Engine, Linker, Plugin, PluginContext, ResourceTable };
+	/// # use wasm_link::cardinality::ExactlyOne ;
+	/// # struct Context { table: ResourceTable }
+	/// # impl PluginContext for Context { fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.table } }
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// # let engine = Engine::default();
+	/// # let linker = Linker::new( &engine );
+	/// # let plugin = Plugin::new( Component::new( &engine, "(component)" )?, Context { table: ResourceTable::new() })
+	/// # 	.instantiate( &engine, &linker )?;
+	/// let binding: Binding<String, Context> = Binding::new( "my:package", HashMap::new(), ExactlyOne( "plugin".to_string(), plugin ));
+	/// let info = binding.component_info( &"plugin".to_string() ).expect( "bound above" );
+	/// assert!( info.imports().is_empty() );
+	/// # Ok(()) }
+	/// ```
+	pub fn component_info( &self, id: &PluginId ) -> Option<&ComponentInfo> {
+		self.0.plugins.get( id ).map(| plugin | plugin.component_info() )
+	}
+
+	/// Sets a hook called with the interface path and function name of every
+	/// freestanding dispatch in which all plugins of the binding failed.
+	///
+	/// Once set, such dispatches made by the host fail with
+	/// [`DispatchError::AllImplementationsFailed`]( crate::DispatchError::AllImplementationsFailed )
+	/// instead of returning one error per plugin, so hosts can fall back or alert
+	/// without inspecting each result. Consuming plugins still receive each plugin's
+	/// error, as their import's shape is fixed, but their dispatches call the hook too.
+	/// Dispatches to a binding without plugins never count as failed, and neither do
+	/// plugins disabled with [`set_enabled`](Self::set_enabled).
+	///
+	/// ```
+	/// # use std::collections::{ HashMap, HashSet };
+	/// # use wasm_link::{ Binding, Component, Engine, Linker, Plugin, PluginContext, ResourceTable };
+	/// # use wasm_link::cardinality::ExactlyOne ;
+	/// # struct Context { table: ResourceTable }
+	/// # impl PluginContext for Context { fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.table } }
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// # let engine = Engine::default();
+	/// # let linker = Linker::new( &engine );
+	/// # let plugin = Plugin::new( Component::new( &engine, "(component)" )?, Context { table: ResourceTable::new() })
+	/// # 	.instantiate( &engine, &linker )?;
+	/// let binding: Binding<String, Context> = Binding::new( "my:package", HashMap::new(), ExactlyOne( "plugin".to_string(), plugin ));
+	/// binding.on_all_failed(| interface, function | eprintln!( "every implementation of {interface}#{function} failed" ));
+	/// # Ok(()) }
+	/// ```
+	pub fn on_all_failed( &self, hook: impl Fn( &str, &str ) + Send + Sync + 'static ) {
+		*self.0.all_failed.write().unwrap_or_else( PoisonError::into_inner ) = Some( Arc::new( hook ));
+	}
+
+	/// Calls the hook set with [`on_all_failed`](Self::on_all_failed) if all calls
+	/// counted by `outcomes` failed, returning whether it was called.
+	pub(crate) fn notify_all_failed( &self, interface_name: &str, function_name: &str, outcomes: &Outcomes ) -> bool {
+		if !outcomes.all_failed() { return false }
+		let hook = self.0.all_failed.read().unwrap_or_else( PoisonError::into_inner ).clone();
+		let Some( hook ) = hook else { return false };
+		hook( &format!( "{}/{}", self.0.package_name, interface_name ), function_name );
+		true
+	}
+
 	pub(crate) fn plugins( &self ) -> &PluginSockets<PluginId, Plugins, Instance> {
 		&self.0.plugins
 	}
+
+	/// Shapes the wrapped results of a freestanding function for consuming plugins,
+	/// unwrapping the single result if [`with_direct_results`](Self::with_direct_results) is set.
+	pub(crate) fn socket_results( &self, results: Val ) -> Val {
+		if !self.0.direct_results.load( Ordering::Relaxed ) { return results }
+		match results {
+			Val::Tuple( mut fields ) if fields.len() == 2 => fields.swap_remove( 1 ),
+			results => results,
+		}
+	}
+
+	fn snapshot_as( &self, cardinality: CardinalityKind ) -> Result<GraphSnapshot, SnapshotError>
+	where
+		PluginId: Into<Val>,
+	{
+		let mut plugins = Vec::new();
+		self.0.plugins.map(| plugin_id, plugin | plugins.push(( plugin_id.clone().into(), plugin.wiring() )));
+		GraphSnapshot::capture( &self.0.package_name, cardinality, &self.0.interfaces, plugins )
+	}
+
+	fn check_imports_as(
+		&self,
+		component: &wasmtime::component::types::Component,
+		engine: &wasmtime::Engine,
+		cardinality: CardinalityKind,
+		host_provided: &[String],
+	) -> Result<(), SocketShapeError> {
+		let direct = self.0.direct_results.load( Ordering::Relaxed );
+		socket_shape::check( component, engine, &self.0.package_name, &self.0.interfaces, cardinality, direct, host_provided )
+	}
+
+	fn interface_idents( &self ) -> Vec<String> {
+		self.0.interfaces.keys().map(| name | format!( "{}/{}", self.0.package_name, name )).collect()
+	}
+}
+
+impl<PluginId, Ctx, Instance> Binding<PluginId, Ctx, ExactlyOne<PluginId, Instance>, Instance>
+where
+	PluginId: std::hash::Hash + Eq + Clone + Send + Sync + 'static,
+	Ctx: PluginContext + 'static,
+	Instance: Send + 'static,
+	PluginSockets<PluginId, ExactlyOne<PluginId, Instance>, Instance>: Send + Sync,
+{
+
+	/// Hands consuming plugins the result of freestanding functions as a plain
+	/// `result<T, dispatch-error>`, rather than in a `tuple` with the plugin's id.
+	///
+	/// The id of the only plugin is rarely of use, so this spares guests unwrapping it
+	/// on every call. Plugins linked against the binding must import its functions in
+	/// the plain shape; linking checks this as usual. Set it before linking any plugin
+	/// against the binding. Dispatches made by the host are not affected.
+	///
+	/// ```
+	/// # use std::collections::{ HashMap, HashSet };
+	/// # use wasm_link::{ Binding, Component, Engine, Linker, Plugin, PluginContext, ResourceTable };
+	/// # use wasm_link::cardinality::ExactlyOne ;
+	/// # struct Context { table: ResourceTable }
+	/// # impl PluginContext for Context { fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.table } }
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// # let engine = Engine::default();
+	/// # let linker = Linker::new( &engine );
+	/// # let plugin = Plugin::new( Component::new( &engine, "(component)" )?, Context { table: ResourceTable::new() })
+	/// # 	.instantiate( &engine, &linker )?;
+	/// let binding: Binding<String, Context> = Binding::new( "my:package", HashMap::new(), ExactlyOne( "plugin".to_string(), plugin ))
+	/// 	.with_direct_results();
+	/// # let _ = binding ;
+	/// # Ok(()) }
+	/// ```
+	pub fn with_direct_results( self ) -> Self {
+		self.0.direct_results.store( true, Ordering::Relaxed );
+		self
+	}
+
 }
 
 impl<PluginId, Ctx, Plugins> Binding<PluginId, Ctx, Plugins, PluginInstanceSync<Ctx>>
@@ -161,18 +527,21 @@ where
 	PluginId: std::hash::Hash + Eq + Clone + Send + Sync + 'static,
 	Ctx: PluginContext + 'static,
 	Plugins: Cardinality<PluginId, PluginInstanceSync<Ctx>> + 'static,
-	PluginSockets<PluginId, Plugins, PluginInstanceSync<Ctx>>: Cardinality<PluginId, Arc<Mutex<PluginInstanceSync<Ctx>>>> + Send + Sync,
+	PluginSockets<PluginId, Plugins, PluginInstanceSync<Ctx>>: Cardinality<PluginId, Arc<PluginLock<PluginInstanceSync<Ctx>>>> + Send + Sync,
 {
 
-	pub(crate) fn add_to_linker( binding: &Binding<PluginId, Ctx, Plugins>, linker: &mut Linker<Ctx> ) -> Result<(), wasmtime::Error>
+	pub(crate) fn add_to_linker( binding: &Binding<PluginId, Ctx, Plugins>, linker: &mut Linker<Ctx>, imports: &Imports, host_defined: &HashSet<String> ) -> Result<Vec<String>, wasmtime::Error>
 	where
 		PluginId: Into<Val>,
 		DispatchVals<PluginId, Plugins, PluginInstanceSync<Ctx>>: Into<Val>,
 	{
-		binding.0.interfaces.iter().try_for_each(|( name, interface )| {
+		binding.0.interfaces.iter().filter_map(|( name, interface )| {
 			let interface_ident = format!( "{}/{}", binding.0.package_name, name );
-			interface.add_to_linker( linker, &binding.0.package_name, &interface_ident, name, binding )
-		})
+			let imported = imports.get( &interface_ident )?;
+			// Interfaces the host already implements natively are left to the host
+			if host_defined.contains( &interface_ident ) { return Some( Ok( interface_ident )) }
+			interface.add_to_linker( linker, &binding.0.package_name, &interface_ident, name, imported, binding ).err().map( Err )
+		}).collect()
 	}
 
 	/// Dispatches a function call to all plugins implementing this binding.
@@ -198,6 +567,47 @@ where
 		function_name: &str,
 		args: &[wasmtime::component::Val],
 	) -> Result<DispatchResults<PluginId, Plugins, PluginInstanceSync<Ctx>>, crate::DispatchError> {
+		self.dispatch_with( &CallContext::new(), interface_name, function_name, args )
+	}
+
+	/// Same as [`dispatch`](Self::dispatch), but runs under the given [`CallContext`]
+	/// instead of a fresh one.
+	///
+	/// The context is recorded in each plugin's store through
+	/// [`PluginContext::set_call_context`] and carried over to all nested dispatches,
+	/// so its [`DispatchId`] correlates the whole call chain.
+	///
+	/// ```
+	/// # use std::collections::{ HashMap, HashSet };
+	/// # use wasm_link::{ Binding, CallContext, Component, Engine, Linker, Plugin, PluginContext, ResourceTable };
+	/// # use wasm_link::cardinality::ExactlyOne ;
+	/// # struct Ctx { resource_table: ResourceTable }
+	/// # impl PluginContext for Ctx {
+	/// # 	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.resource_table }
+	/// # }
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// # let engine = Engine::default();
+	/// # let linker = Linker::new( &engine );
+	/// # let plugin = Plugin::new( Component::new( &engine, "(component)" )?, Ctx { resource_table: ResourceTable::new() }).instantiate( &engine, &linker )?;
+	/// # let binding: Binding<String, Ctx> = Binding::new( "my:package", HashMap::new(), ExactlyOne( "my-plugin".to_string(), plugin ));
+	/// let context = CallContext::new();
+	/// let result = binding.dispatch_with( &context, "api", "get-value", &[] );
+	/// if let Err( err ) = result {
+	/// 	eprintln!( "{}: {err}", context.dispatch_id() );
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// Returns an error if the interface or function is not found in this binding.
+	pub fn dispatch_with(
+		&self,
+		context: &CallContext,
+		interface_name: &str,
+		function_name: &str,
+		args: &[wasmtime::component::Val],
+	) -> Result<DispatchResults<PluginId, Plugins, PluginInstanceSync<Ctx>>, crate::DispatchError> {
 
 		let interface = self.0.interfaces.get( interface_name )
 			.ok_or_else(|| crate::DispatchError::InvalidInterfacePath( format!( "{}/{}", self.0.package_name, interface_name )))?;
@@ -205,20 +615,212 @@ where
 		let function = interface.function( function_name )
 			.ok_or_else(|| crate::DispatchError::InvalidFunction( function_name.to_string() ))?;
 
-		Ok( self.0.plugins.map(| _, plugin | plugin
-			.try_lock().ok_or( crate::DispatchError::LockRejected )
+		let outcomes = Outcomes::default();
+		let results = self.0.plugins.map(| plugin_id, plugin | outcomes.record( plugin
+			.try_lock( &function, context.dispatch_id() )
 			.and_then(| mut lock | lock.dispatch(
 				&self.0.package_name,
 				interface_name,
 				function_name,
-				function,
+				&function,
 				args,
+				plugin_id,
+				context,
 			))
-		))
+		));
+		match self.notify_all_failed( interface_name, function_name, &outcomes ) {
+			true => Err( crate::DispatchError::AllImplementationsFailed ),
+			false => Ok( results ),
+		}
 
 	}
 
+	/// Same as [`dispatch`](Self::dispatch), but also returns the [`DispatchId`] the
+	/// dispatch ran under, including when it fails.
+	///
+	/// Use it to match a failed dispatch against what observers, limiters and host
+	/// functions reported for the same call chain.
+	pub fn dispatch_traced(
+		&self,
+		interface_name: &str,
+		function_name: &str,
+		args: &[wasmtime::component::Val],
+	) -> TracedResults<PluginId, Plugins, PluginInstanceSync<Ctx>> {
+		let context = CallContext::new();
+		( context.dispatch_id(), self.dispatch_with( &context, interface_name, function_name, args ))
+	}
 
+	/// Same as [`dispatch`](Self::dispatch), but takes the arguments and returns the
+	/// results as [WAVE]( crate::wave ) text.
+	///
+	/// `args` are comma-separated WAVE values, parsed against the parameter types of
+	/// each plugin's export. Void functions yield `none`.
+	///
+	/// ```
+	/// # use std::collections::{ HashMap, HashSet };
+	/// # use wasm_link::{ Binding, Component, Engine, Function, FunctionKind, Interface, Linker, Plugin, PluginContext, ResourceTable, ReturnKind };
+	/// # use wasm_link::cardinality::ExactlyOne ;
+	/// # struct Context { table: ResourceTable }
+	/// # impl PluginContext for Context { fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.table } }
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// # let engine = Engine::default();
+	/// # let linker = Linker::new( &engine );
+	/// # let component = Component::new( &engine, r#"(component
+	/// # 	(core module $m (func (export "add") (param i32 i32) (result i32) local.get 0 local.get 1 i32.add))
+	/// # 	(core instance $i (instantiate $m))
+	/// # 	(func $add (param "a" u32) (param "b" u32) (result u32) (canon lift (core func $i "add")))
+	/// # 	(instance $root (export "add" (func $add)))
+	/// # 	(export "example:plugin/root" (instance $root))
+	/// # )"# )?;
+	/// # let plugin = Plugin::new( component, Context { table: ResourceTable::new() }).instantiate( &engine, &linker )?;
+	/// # let binding: Binding<String, Context> = Binding::new(
+	/// # 	"example:plugin",
+	/// # 	HashMap::from([( "root".to_string(), Interface::new(
+	/// # 		HashMap::from([( "add".to_string(), Function::new( FunctionKind::Freestanding, ReturnKind::AssumeNoResources ))]),
+	/// # 		HashSet::new(),
+	/// # 	))]),
+	/// # 	ExactlyOne( "plugin".to_string(), plugin ),
+	/// # );
+	/// let ExactlyOne( _, result ) = binding.dispatch_wave( "root", "add", "40, 2" )?;
+	/// assert_eq!( result?, "42" );
+	/// # Ok(()) }
+	/// ```
+	///
+	/// # Errors
+	/// Returns an error if the interface or function is not found in this binding.
+	#[cfg( feature = "wave" )]
+	pub fn dispatch_wave(
+		&self,
+		interface_name: &str,
+		function_name: &str,
+		args: &str,
+	) -> Result<WaveResults<PluginId, Plugins, PluginInstanceSync<Ctx>>, crate::DispatchError> {
+
+		let interface = self.0.interfaces.get( interface_name )
+			.ok_or_else(|| crate::DispatchError::InvalidInterfacePath( format!( "{}/{}", self.0.package_name, interface_name )))?;
+
+		let function = interface.function( function_name )
+			.ok_or_else(|| crate::DispatchError::InvalidFunction( function_name.to_string() ))?;
+
+		let context = CallContext::new();
+		Ok( self.0.plugins.map(| plugin_id, plugin | {
+			let mut lock = plugin.try_lock( &function, context.dispatch_id() )?;
+			let ( result, _ ) = self.dispatch_wave_to( &mut lock, interface_name, function_name, &function, args, plugin_id, &context )?;
+			crate::wave::format( &result )
+		}))
+	}
+
+	/// Runs the [test vectors]( crate::conformance::TestVector ) declared by this binding's
+	/// interfaces against each plugin and reports which of them pass.
+	///
+	/// Use to reject plugins that claim to implement a binding but violate its contract.
+	/// Plugins busy with another dispatch fail every vector with
+	/// [`DispatchError::LockRejected`]( crate::DispatchError::LockRejected ).
+	///
+	/// ```
+	/// # use std::collections::{ HashMap, HashSet };
+	/// # use wasm_link::{ Binding, Component, Engine, Function, FunctionKind, Interface, Linker, Plugin, PluginContext, ResourceTable, ReturnKind };
+	/// # use wasm_link::cardinality::ExactlyOne ;
+	/// # use wasm_link::conformance::TestVector ;
+	/// # struct Context { table: ResourceTable }
+	/// # impl PluginContext for Context { fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.table } }
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// # let engine = Engine::default();
+	/// # let linker = Linker::new( &engine );
+	/// # let component = Component::new( &engine, r#"(component
+	/// # 	(core module $m (func (export "add") (param i32 i32) (result i32) local.get 0 local.get 1 i32.add))
+	/// # 	(core instance $i (instantiate $m))
+	/// # 	(func $add (param "a" u32) (param "b" u32) (result u32) (canon lift (core func $i "add")))
+	/// # 	(instance $root (export "add" (func $add)))
+	/// # 	(export "example:plugin/root" (instance $root))
+	/// # )"# )?;
+	/// # let plugin = Plugin::new( component, Context { table: ResourceTable::new() }).instantiate( &engine, &linker )?;
+	/// let interface = Interface::new(
+	/// 	HashMap::from([( "add".to_string(), Function::new( FunctionKind::Freestanding, ReturnKind::AssumeNoResources ))]),
+	/// 	HashSet::new(),
+	/// ).with_test_vectors([
+	/// 	TestVector::new( "add", "40, 2" ).expecting( "42" ),
+	/// 	TestVector::new( "add", "0, 0" ).expecting( "0" ),
+	/// ]);
+	/// let binding: Binding<String, Context> = Binding::new(
+	/// 	"example:plugin",
+	/// 	HashMap::from([( "root".to_string(), interface )]),
+	/// 	ExactlyOne( "plugin".to_string(), plugin ),
+	/// );
+	/// let ExactlyOne( _, report ) = binding.run_conformance();
+	/// assert!( report.passed() );
+	/// # Ok(()) }
+	/// ```
+	#[cfg( feature = "wave" )]
+	pub fn run_conformance( &self ) -> ConformanceReports<PluginId, Plugins, PluginInstanceSync<Ctx>> {
+		let mut interfaces = self.0.interfaces.iter().collect::<Vec<_>>();
+		interfaces.sort_by_key(|( name, _ )| *name );
+		let context = CallContext::new();
+		self.0.plugins.map(| plugin_id, plugin | interfaces.iter()
+			.flat_map(|( interface_name, interface )| interface.test_vectors().iter().map( move | vector | ( *interface_name, *interface, vector )))
+			.map(|( interface_name, interface, vector )| {
+				let result = interface.function( vector.function() )
+					.ok_or_else(|| crate::DispatchError::InvalidFunction( vector.function().to_string() ).into() )
+					.and_then(| function | {
+						let mut lock = plugin.try_lock( &function, context.dispatch_id() )?;
+						let ( actual, ty ) = self.dispatch_wave_to( &mut lock, interface_name, vector.function(), &function, vector.args(), plugin_id, &context )?;
+						let result_type = ty.results().next();
+						vector.check( &actual, result_type.as_ref() )
+					});
+				crate::conformance::VectorOutcome::new( interface_name, vector, result )
+			})
+			.collect()
+		)
+	}
+
+	#[cfg( feature = "wave" )]
+	#[allow( clippy::too_many_arguments )]
+	fn dispatch_wave_to(
+		&self,
+		instance: &mut PluginInstanceSync<Ctx>,
+		interface_name: &str,
+		function_name: &str,
+		function: &crate::Function,
+		args: &str,
+		plugin_id: &PluginId,
+		context: &CallContext,
+	) -> Result<( Val, wasmtime::component::types::ComponentFunc ), crate::wave::WaveError> {
+		let ty = instance.function_type( &self.0.package_name, interface_name, function_name )?;
+		let args = crate::wave::parse_args( &ty.params().map(|( _, ty )| ty ).collect::<Vec<_>>(), args )?;
+		let result = instance.dispatch(
+			&self.0.package_name,
+			interface_name,
+			function_name,
+			function,
+			&args,
+			plugin_id,
+			context,
+		)?;
+		Ok(( result, ty ))
+	}
+
+
+	/// Returns the fuel left in the store of the plugin with the given id, or `None` if
+	/// the binding has no such plugin or the engine does not consume fuel.
+	///
+	/// Blocks until a call in progress on the plugin's own instance finishes, so it
+	/// must not be called from within one of the plugin's calls. Replicas and canaries
+	/// are not included. See [`PluginInstanceSync::remaining_fuel`].
+	pub fn remaining_fuel( &self, id: &PluginId ) -> Option<u64> {
+		let plugin = self.0.plugins.get( id )?;
+		futures::executor::block_on( plugin.inspect() ).remaining_fuel()
+	}
+
+	/// Returns the epoch deadline given to the latest call of the plugin with the
+	/// given id, or `None` if the binding has no such plugin or no call has been given
+	/// one yet.
+	///
+	/// Blocks like [`remaining_fuel`](Self::remaining_fuel). See
+	/// [`PluginInstanceSync::current_epoch_deadline`].
+	pub fn current_epoch_deadline( &self, id: &PluginId ) -> Option<u64> {
+		let plugin = self.0.plugins.get( id )?;
+		futures::executor::block_on( plugin.inspect() ).current_epoch_deadline()
+	}
 }
 
 impl<PluginId, Ctx, Plugins> Binding<PluginId, Ctx, Plugins, PluginInstanceAsync<Ctx>>
@@ -226,17 +828,20 @@ where
 	PluginId: std::hash::Hash + Eq + Clone + Send + Sync + 'static,
 	Ctx: PluginContext + 'static,
 	Plugins: Cardinality<PluginId, PluginInstanceAsync<Ctx>> + 'static,
-	PluginSockets<PluginId, Plugins, PluginInstanceAsync<Ctx>>: Cardinality<PluginId, Arc<Mutex<PluginInstanceAsync<Ctx>>>> + Send + Sync,
+	PluginSockets<PluginId, Plugins, PluginInstanceAsync<Ctx>>: Cardinality<PluginId, Arc<PluginLock<PluginInstanceAsync<Ctx>>>> + Send + Sync,
 {
-	pub(crate) fn add_to_linker_async( binding: &Self, linker: &mut Linker<Ctx> ) -> Result<(), wasmtime::Error>
+	pub(crate) fn add_to_linker_async( binding: &Self, linker: &mut Linker<Ctx>, imports: &Imports, host_defined: &HashSet<String> ) -> Result<Vec<String>, wasmtime::Error>
 	where
 		PluginId: Into<Val>,
 		DispatchVals<PluginId, Plugins, PluginInstanceAsync<Ctx>>: Into<Val> + Send,
 	{
-		binding.0.interfaces.iter().try_for_each(|( name, interface )| {
+		binding.0.interfaces.iter().filter_map(|( name, interface )| {
 			let interface_ident = format!( "{}/{}", binding.0.package_name, name );
-			interface.add_to_linker_async( linker, &binding.0.package_name, &interface_ident, name, binding )
-		})
+			let imported = imports.get( &interface_ident )?;
+			// Interfaces the host already implements natively are left to the host
+			if host_defined.contains( &interface_ident ) { return Some( Ok( interface_ident )) }
+			interface.add_to_linker_async( linker, &binding.0.package_name, &interface_ident, name, imported, binding ).err().map( Err )
+		}).collect()
 	}
 
 	/// Asynchronously dispatches a function call to all plugins implementing this binding.
@@ -287,6 +892,80 @@ where
 		function_name: &str,
 		args: &[wasmtime::component::Val],
 	) -> Result<DispatchResults<PluginId, Plugins, PluginInstanceAsync<Ctx>>, crate::DispatchError>
+	where
+		PluginId: Into<Val>,
+		DispatchResults<PluginId, Plugins, PluginInstanceAsync<Ctx>>: Send,
+	{
+		self.dispatch_async_with( &CallContext::new(), interface_name, function_name, args ).await
+	}
+
+	/// Same as [`dispatch_async`](Self::dispatch_async), but runs under the given
+	/// [`CallContext`] instead of a fresh one.
+	///
+	/// See [`Binding::dispatch_with`] for how the context propagates.
+	///
+	/// # Errors
+	/// Returns an error if the interface or function is not found in this binding.
+	pub async fn dispatch_async_with(
+		&self,
+		context: &CallContext,
+		interface_name: &str,
+		function_name: &str,
+		args: &[wasmtime::component::Val],
+	) -> Result<DispatchResults<PluginId, Plugins, PluginInstanceAsync<Ctx>>, crate::DispatchError>
+	where
+		PluginId: Into<Val>,
+		DispatchResults<PluginId, Plugins, PluginInstanceAsync<Ctx>>: Send,
+	{
+		self.dispatch_async_inner( context, interface_name, function_name, args, true ).await
+	}
+
+	/// The async counterpart of [`dispatch_traced`](Binding::dispatch_traced).
+	pub async fn dispatch_async_traced(
+		&self,
+		interface_name: &str,
+		function_name: &str,
+		args: &[wasmtime::component::Val],
+	) -> TracedResults<PluginId, Plugins, PluginInstanceAsync<Ctx>>
+	where
+		PluginId: Into<Val>,
+		DispatchResults<PluginId, Plugins, PluginInstanceAsync<Ctx>>: Send,
+	{
+		let context = CallContext::new();
+		( context.dispatch_id(), self.dispatch_async_with( &context, interface_name, function_name, args ).await )
+	}
+
+	/// Same as [`dispatch_async`](Self::dispatch_async), but fails fast instead of
+	/// waiting for a busy plugin.
+	///
+	/// Plugins that are in the middle of another dispatch return
+	/// [`DispatchError::LockRejected`](crate::DispatchError::LockRejected), the same as
+	/// with the synchronous [`dispatch`](Binding::dispatch). Useful for latency-sensitive
+	/// callers that would rather skip a busy plugin than queue behind it.
+	///
+	/// # Errors
+	/// Returns an error if the interface or function is not found in this binding.
+	pub async fn try_dispatch_async(
+		&self,
+		interface_name: &str,
+		function_name: &str,
+		args: &[wasmtime::component::Val],
+	) -> Result<DispatchResults<PluginId, Plugins, PluginInstanceAsync<Ctx>>, crate::DispatchError>
+	where
+		PluginId: Into<Val>,
+		DispatchResults<PluginId, Plugins, PluginInstanceAsync<Ctx>>: Send,
+	{
+		self.dispatch_async_inner( &CallContext::new(), interface_name, function_name, args, false ).await
+	}
+
+	async fn dispatch_async_inner(
+		&self,
+		context: &CallContext,
+		interface_name: &str,
+		function_name: &str,
+		args: &[wasmtime::component::Val],
+		wait: bool,
+	) -> Result<DispatchResults<PluginId, Plugins, PluginInstanceAsync<Ctx>>, crate::DispatchError>
 	where
 		PluginId: Into<Val>,
 		DispatchResults<PluginId, Plugins, PluginInstanceAsync<Ctx>>: Send,
@@ -298,25 +977,143 @@ where
 		let package_name = self.0.package_name.clone();
 		let interface_name = interface_name.to_string();
 		let function_name = function_name.to_string();
-		let function = function.clone();
 		let args = args.to_vec();
+		let outcomes = Outcomes::default();
+		let outcomes_ref = &outcomes ;
 
-		Ok( self.0.plugins.map_async(| _, plugin | {
+		let results = self.0.plugins.map_async(| plugin_id, plugin | {
 			let package_name = package_name.clone();
 			let interface_name = interface_name.clone();
 			let function_name = function_name.clone();
 			let function = function.clone();
 			let args = args.clone();
+			let context = context.clone();
 			async move {
-				plugin.lock().await.dispatch_async(
-					&package_name,
-					&interface_name,
-					&function_name,
-					&function,
-					&args,
-				).await
+				let lock = match wait {
+					true => plugin.lock( &function, context.dispatch_id() ).await,
+					false => plugin.try_lock( &function, context.dispatch_id() ),
+				};
+				let result = match lock {
+					Ok( lock ) => lock.dispatch_async(
+						&package_name,
+						&interface_name,
+						&function_name,
+						&function,
+						&args,
+						&plugin_id,
+						&context,
+					).await,
+					Err( err ) => Err( err ),
+				};
+				outcomes_ref.record( result )
 			}
-		}).await )
+		}).await;
+		match self.notify_all_failed( &interface_name, &function_name, &outcomes ) {
+			true => Err( crate::DispatchError::AllImplementationsFailed ),
+			false => Ok( results ),
+		}
+	}
+
+
+	/// Returns the fuel left in the store of the plugin with the given id, or `None` if
+	/// the binding has no such plugin or the engine does not consume fuel.
+	///
+	/// Waits for a call in progress on the plugin's own instance to finish. See
+	/// [`PluginInstanceSync::remaining_fuel`].
+	pub async fn remaining_fuel( &self, id: &PluginId ) -> Option<u64> {
+		let plugin = self.0.plugins.get( id )?;
+		plugin.inspect().await.remaining_fuel().await
+	}
+
+	/// Returns the epoch deadline given to the latest call of the plugin with the
+	/// given id, or `None` if the binding has no such plugin or no call has been given
+	/// one yet.
+	///
+	/// Waits like [`remaining_fuel`](Self::remaining_fuel). See
+	/// [`PluginInstanceSync::current_epoch_deadline`].
+	pub async fn current_epoch_deadline( &self, id: &PluginId ) -> Option<u64> {
+		let plugin = self.0.plugins.get( id )?;
+		plugin.inspect().await.current_epoch_deadline().await
+	}
+}
+
+/// One interface of a [`Binding`], returned by [`Binding::interface`].
+///
+/// Dispatches made through the handle behave exactly like those made through the
+/// binding with the handle's interface name.
+pub struct InterfaceHandle<'a, PluginId, Ctx, Plugins, Instance>
+where
+	PluginId: std::hash::Hash + Eq + Clone + Send + Sync + 'static,
+	Ctx: PluginContext + 'static,
+	Instance: Send + 'static,
+	Plugins: Cardinality<PluginId, Instance> + 'static,
+	PluginSockets<PluginId, Plugins, Instance>: Send + Sync,
+{
+	binding: &'a Binding<PluginId, Ctx, Plugins, Instance>,
+	name: &'a str,
+	interface: &'a Interface,
+	path: String,
+}
+
+impl<PluginId, Ctx, Plugins, Instance> InterfaceHandle<'_, PluginId, Ctx, Plugins, Instance>
+where
+	PluginId: std::hash::Hash + Eq + Clone + Send + Sync + 'static,
+	Ctx: PluginContext + 'static,
+	Instance: Send + 'static,
+	Plugins: Cardinality<PluginId, Instance> + 'static,
+	PluginSockets<PluginId, Plugins, Instance>: Send + Sync,
+{
+
+	/// Returns the name of the interface within its binding, e.g. `api`.
+	pub fn name( &self ) -> &str { self.name }
+
+	/// Returns the WIT path of the interface, e.g. `my:package/api`.
+	pub fn path( &self ) -> &str { &self.path }
+
+	/// Returns the declaration of the interface.
+	pub fn interface( &self ) -> &Interface { self.interface }
+
+	/// Returns the functions of the interface with their metadata, in no particular order.
+	pub fn functions( &self ) -> impl Iterator<Item = ( &str, Function )> { self.interface.functions() }
+
+}
+
+impl<PluginId, Ctx, Plugins> InterfaceHandle<'_, PluginId, Ctx, Plugins, PluginInstanceSync<Ctx>>
+where
+	PluginId: std::hash::Hash + Eq + Clone + Send + Sync + 'static,
+	Ctx: PluginContext + 'static,
+	Plugins: Cardinality<PluginId, PluginInstanceSync<Ctx>> + 'static,
+	PluginSockets<PluginId, Plugins, PluginInstanceSync<Ctx>>: Cardinality<PluginId, Arc<PluginLock<PluginInstanceSync<Ctx>>>> + Send + Sync,
+{
+
+	/// Calls `function` of this interface, see [`Binding::dispatch`].
+	///
+	/// # Errors
+	/// Returns an error if the function is not found in this interface.
+	pub fn call( &self, function: &str, args: &[Val] ) -> Result<DispatchResults<PluginId, Plugins, PluginInstanceSync<Ctx>>, crate::DispatchError> {
+		self.binding.dispatch( self.name, function, args )
+	}
+
+}
+
+impl<PluginId, Ctx, Plugins> InterfaceHandle<'_, PluginId, Ctx, Plugins, PluginInstanceAsync<Ctx>>
+where
+	PluginId: std::hash::Hash + Eq + Clone + Send + Sync + 'static,
+	Ctx: PluginContext + 'static,
+	Plugins: Cardinality<PluginId, PluginInstanceAsync<Ctx>> + 'static,
+	PluginSockets<PluginId, Plugins, PluginInstanceAsync<Ctx>>: Cardinality<PluginId, Arc<PluginLock<PluginInstanceAsync<Ctx>>>> + Send + Sync,
+{
+
+	/// Calls `function` of this interface, see [`Binding::dispatch_async`].
+	///
+	/// # Errors
+	/// Returns an error if the function is not found in this interface.
+	pub async fn call_async( &self, function: &str, args: &[Val] ) -> Result<DispatchResults<PluginId, Plugins, PluginInstanceAsync<Ctx>>, crate::DispatchError>
+	where
+		PluginId: Into<Val>,
+		DispatchResults<PluginId, Plugins, PluginInstanceAsync<Ctx>>: Send,
+	{
+		self.binding.dispatch_async( self.name, function, args ).await
 	}
 
 }
@@ -346,12 +1143,15 @@ where
 	PluginId: std::hash::Hash + Eq + Clone + Send + Sync + Into<Val> + 'static,
 	Ctx: PluginContext + 'static,
 {
-	pub(crate) fn add_to_linker( &self, linker: &mut Linker<Ctx> ) -> Result<(), wasmtime::Error> {
+	/// Adds dispatch shims for the items of this binding's interfaces the component
+	/// `imports` to `linker`, returning the interfaces skipped because they are among
+	/// those the host already defines.
+	pub(crate) fn add_to_linker( &self, linker: &mut Linker<Ctx>, imports: &Imports, host_defined: &HashSet<String> ) -> Result<Vec<String>, wasmtime::Error> {
 		match self {
-			Self::ExactlyOne( binding ) => Binding::add_to_linker( binding, linker ),
-			Self::AtMostOne( binding ) => Binding::add_to_linker( binding, linker ),
-			Self::AtLeastOne( binding ) => Binding::add_to_linker( binding, linker ),
-			Self::Any( binding ) => Binding::add_to_linker( binding, linker ),
+			Self::ExactlyOne( binding ) => Binding::add_to_linker( binding, linker, imports, host_defined ),
+			Self::AtMostOne( binding ) => Binding::add_to_linker( binding, linker, imports, host_defined ),
+			Self::AtLeastOne( binding ) => Binding::add_to_linker( binding, linker, imports, host_defined ),
+			Self::Any( binding ) => Binding::add_to_linker( binding, linker, imports, host_defined ),
 		}
 	}
 
@@ -362,12 +1162,13 @@ where
 	PluginId: std::hash::Hash + Eq + Clone + Send + Sync + Into<Val> + 'static,
 	Ctx: PluginContext + 'static,
 {
-	pub(crate) fn add_to_linker_async( &self, linker: &mut Linker<Ctx> ) -> Result<(), wasmtime::Error> {
+	/// See [`BindingAny::add_to_linker`].
+	pub(crate) fn add_to_linker_async( &self, linker: &mut Linker<Ctx>, imports: &Imports, host_defined: &HashSet<String> ) -> Result<Vec<String>, wasmtime::Error> {
 		match self {
-			Self::ExactlyOne( binding ) => Binding::add_to_linker_async( binding, linker ),
-			Self::AtMostOne( binding ) => Binding::add_to_linker_async( binding, linker ),
-			Self::AtLeastOne( binding ) => Binding::add_to_linker_async( binding, linker ),
-			Self::Any( binding ) => Binding::add_to_linker_async( binding, linker ),
+			Self::ExactlyOne( binding ) => Binding::add_to_linker_async( binding, linker, imports, host_defined ),
+			Self::AtMostOne( binding ) => Binding::add_to_linker_async( binding, linker, imports, host_defined ),
+			Self::AtLeastOne( binding ) => Binding::add_to_linker_async( binding, linker, imports, host_defined ),
+			Self::Any( binding ) => Binding::add_to_linker_async( binding, linker, imports, host_defined ),
 		}
 	}
 }
@@ -429,6 +1230,72 @@ where
 	pub fn into_any( self ) -> BindingAny<PluginId, Ctx, Instance> {
 		self.into()
 	}
+
+	/// Captures the wiring of the graph rooted at this binding.
+	///
+	/// See [`GraphSnapshot`] for what is recorded.
+	///
+	/// # Errors
+	/// Returns an error if a plugin id cannot be recorded or two different bindings
+	/// in the graph share a package name.
+	pub fn snapshot( &self ) -> Result<GraphSnapshot, SnapshotError>
+	where
+		PluginId: Into<Val>,
+	{
+		self.clone().into_any().snapshot()
+	}
+}
+
+impl<PluginId, Ctx, Instance> BindingAny<PluginId, Ctx, Instance>
+where
+	PluginId: std::hash::Hash + Eq + Clone + Send + Sync + 'static,
+	Ctx: PluginContext + 'static,
+	Instance: Send + 'static,
+{
+	/// Captures the wiring of the graph rooted at this binding.
+	///
+	/// See [`Binding::snapshot`].
+	///
+	/// # Errors
+	/// Returns an error if a plugin id cannot be recorded or two different bindings
+	/// in the graph share a package name.
+	pub fn snapshot( &self ) -> Result<GraphSnapshot, SnapshotError>
+	where
+		PluginId: Into<Val>,
+	{
+		match self {
+			Self::ExactlyOne( binding ) => binding.snapshot_as( CardinalityKind::ExactlyOne ),
+			Self::AtMostOne( binding ) => binding.snapshot_as( CardinalityKind::AtMostOne ),
+			Self::AtLeastOne( binding ) => binding.snapshot_as( CardinalityKind::AtLeastOne ),
+			Self::Any( binding ) => binding.snapshot_as( CardinalityKind::Any ),
+		}
+	}
+
+	/// Checks that `component` imports this binding's interfaces in the shape the
+	/// socket provides, except for the `host_provided` ones.
+	pub(crate) fn check_imports(
+		&self,
+		component: &wasmtime::component::types::Component,
+		engine: &wasmtime::Engine,
+		host_provided: &[String],
+	) -> Result<(), SocketShapeError> {
+		match self {
+			Self::ExactlyOne( binding ) => binding.check_imports_as( component, engine, CardinalityKind::ExactlyOne, host_provided ),
+			Self::AtMostOne( binding ) => binding.check_imports_as( component, engine, CardinalityKind::AtMostOne, host_provided ),
+			Self::AtLeastOne( binding ) => binding.check_imports_as( component, engine, CardinalityKind::AtLeastOne, host_provided ),
+			Self::Any( binding ) => binding.check_imports_as( component, engine, CardinalityKind::Any, host_provided ),
+		}
+	}
+
+	/// Returns the WIT paths of this binding's interfaces.
+	pub(crate) fn interface_idents( &self ) -> Vec<String> {
+		match self {
+			Self::ExactlyOne( binding ) => binding.interface_idents(),
+			Self::AtMostOne( binding ) => binding.interface_idents(),
+			Self::AtLeastOne( binding ) => binding.interface_idents(),
+			Self::Any( binding ) => binding.interface_idents(),
+		}
+	}
 }
 
 impl<PluginId, Ctx, Instance> Clone for BindingAny<PluginId, Ctx, Instance>
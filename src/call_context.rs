@@ -0,0 +1,274 @@
+//! Per-dispatch context shared along a chain of plugin-to-plugin calls.
+//!
+//! Every dispatch initiated by the host gets a [`CallContext`] with a unique
+//! [`DispatchId`]. When the plugin context opts in through
+//! [`PluginContext::set_call_context`]( crate::PluginContext::set_call_context ),
+//! the call context is recorded in the plugin's store for the duration of each
+//! call and propagated to every nested dispatch the plugin makes, so logs across a
+//! multi-hop call chain can be correlated.
+
+use std::any::Any ;
+use std::collections::HashMap ;
+use std::sync::Arc ;
+use std::time::{ Duration, Instant };
+use std::sync::atomic::{ AtomicU64, Ordering };
+use wasmtime::component::Val ;
+
+use crate::Function ;
+
+static NEXT_DISPATCH_ID: AtomicU64 = AtomicU64::new( 1 );
+
+
+
+/// Identifies a host-initiated dispatch and every nested call it causes.
+///
+/// Ids are unique within the process.
+#[derive( Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash )]
+pub struct DispatchId( u64 );
+
+impl DispatchId {
+
+	fn next() -> Self {
+		Self( NEXT_DISPATCH_ID.fetch_add( 1, Ordering::Relaxed ))
+	}
+
+	/// Returns the numeric value of this id.
+	pub fn get( self ) -> u64 { self.0 }
+
+}
+
+impl std::fmt::Display for DispatchId {
+	fn fmt( &self, f: &mut std::fmt::Formatter<'_> ) -> std::fmt::Result {
+		write!( f, "dispatch#{}", self.0 )
+	}
+}
+
+/// Ambient data of a dispatch, available to the plugin context for its duration.
+///
+/// Pass one to [`Binding::dispatch_with`]( crate::Binding::dispatch_with ) to know the
+/// [`DispatchId`] of a call up front or to attach per-call metadata such as a tenant id
+/// or locale; [`Binding::dispatch`]( crate::Binding::dispatch ) creates a fresh one for
+/// every call. Unlike mutating the plugin context between calls, metadata travels with
+/// the dispatch, so concurrent dispatches never observe each other's values.
+///
+/// The same goes for the per-dispatch limits set with [`with_fuel`](Self::with_fuel),
+/// [`with_epoch_deadline`](Self::with_epoch_deadline) and [`with_deadline`](Self::with_deadline),
+/// which tighten or relax the plugins' own limits for a single dispatch without
+/// touching their limiters. Like the metadata, they reach nested calls only through
+/// plugin contexts that record the call context.
+///
+/// ```
+/// use std::collections::HashMap ;
+/// use wasm_link::{ CallContext, Val };
+///
+/// let context = CallContext::new().with_metadata( HashMap::from([
+/// 	( "tenant".to_string(), Val::String( "acme".to_string() )),
+/// ]));
+/// assert_eq!( context.metadata().get( "tenant" ), Some( &Val::String( "acme".to_string() )));
+/// assert_ne!( context.dispatch_id(), CallContext::new().dispatch_id() );
+/// ```
+#[derive( Debug, Clone )]
+pub struct CallContext {
+	dispatch_id: DispatchId,
+	metadata: Arc<HashMap<String, Val>>,
+	fuel: Option<u64>,
+	epoch_deadline: Option<u64>,
+	deadline: Option<Instant>,
+	observed: bool,
+}
+
+impl Default for CallContext {
+	fn default() -> Self { Self::new() }
+}
+
+impl CallContext {
+
+	/// Creates a call context with a new, unique [`DispatchId`].
+	pub fn new() -> Self {
+		Self {
+			dispatch_id: DispatchId::next(),
+			metadata: Arc::new( HashMap::new() ),
+			fuel: None,
+			epoch_deadline: None,
+			deadline: None,
+			observed: true,
+		}
+	}
+
+	/// Attaches opaque metadata readable from the plugin context of every plugin
+	/// taking part in the dispatch.
+	pub fn with_metadata( mut self, metadata: HashMap<String, Val> ) -> Self {
+		self.metadata = Arc::new( metadata );
+		self
+	}
+
+	/// Returns the id shared by the dispatch and all its nested calls.
+	pub fn dispatch_id( &self ) -> DispatchId { self.dispatch_id }
+
+	/// Gives every call of the dispatch `fuel` units of fuel in place of what the called
+	/// plugin's fuel limiter or the function's default would.
+	///
+	/// Nested calls are covered only where the calling plugin's context records the
+	/// call context through [`PluginContext::set_call_context`]( crate::PluginContext::set_call_context )
+	/// and [`call_context`]( crate::PluginContext::call_context ); elsewhere they start
+	/// a fresh dispatch with the plugins' own limits. Has no effect unless fuel
+	/// consumption is enabled in the engine.
+	pub fn with_fuel( mut self, fuel: u64 ) -> Self {
+		self.fuel = Some( fuel );
+		self
+	}
+
+	/// Gives every call of the dispatch an epoch deadline of `ticks` in place of what
+	/// the called plugin's epoch limiter or the function's default would.
+	///
+	/// Nested calls are covered only where the calling plugin's context records the
+	/// call context, as for [`with_fuel`](Self::with_fuel). Has no effect unless epoch
+	/// interruption is enabled in the engine.
+	pub fn with_epoch_deadline( mut self, ticks: u64 ) -> Self {
+		self.epoch_deadline = Some( ticks );
+		self
+	}
+
+	/// Fails every call of the dispatch that starts at or after `deadline` with an
+	/// interrupt, as an exceeded epoch deadline would.
+	///
+	/// Calls into plugins registered with a [`Watchdog`]( crate::limits::Watchdog ) also
+	/// get an epoch deadline no later than `deadline`, so they are interrupted once it
+	/// passes rather than only refused afterwards. Nested calls are covered only where
+	/// the calling plugin's context records the call context, as for
+	/// [`with_fuel`](Self::with_fuel).
+	///
+	/// ```
+	/// use std::time::{ Duration, Instant };
+	/// use wasm_link::CallContext ;
+	///
+	/// let deadline = Instant::now() + Duration::from_millis( 100 );
+	/// assert_eq!( CallContext::new().with_deadline( deadline ).deadline(), Some( deadline ));
+	/// ```
+	pub fn with_deadline( mut self, deadline: Instant ) -> Self {
+		self.deadline = Some( deadline );
+		self
+	}
+
+	/// Keeps the calls of the dispatch from the plugins' dispatch and slow call
+	/// observers, e.g. for health checks that would skew their statistics.
+	///
+	/// Nested calls are covered only where the calling plugin's context records the
+	/// call context, as for [`with_fuel`](Self::with_fuel).
+	pub fn unobserved( mut self ) -> Self {
+		self.observed = false ;
+		self
+	}
+
+	/// Returns the metadata attached with [`with_metadata`](Self::with_metadata).
+	pub fn metadata( &self ) -> &HashMap<String, Val> { &self.metadata }
+
+	/// Returns the fuel set with [`with_fuel`](Self::with_fuel).
+	pub fn fuel( &self ) -> Option<u64> { self.fuel }
+
+	/// Returns the epoch deadline set with [`with_epoch_deadline`](Self::with_epoch_deadline).
+	pub fn epoch_deadline( &self ) -> Option<u64> { self.epoch_deadline }
+
+	/// Returns the deadline set with [`with_deadline`](Self::with_deadline).
+	pub fn deadline( &self ) -> Option<Instant> { self.deadline }
+
+	/// Returns `false` if the dispatch was made [`unobserved`](Self::unobserved).
+	pub fn is_observed( &self ) -> bool { self.observed }
+
+}
+
+/// Describes the call a fuel or epoch limiter is about to limit.
+///
+/// Received by limiters set with [`Plugin::with_call_fuel_limiter`]( crate::Plugin::with_call_fuel_limiter )
+/// and [`Plugin::with_call_epoch_limiter`]( crate::Plugin::with_call_epoch_limiter ), so budgets
+/// can be keyed off the plugin being called or the dispatch it belongs to.
+pub struct CallInfo<'a> {
+	pub(crate) interface_path: &'a str,
+	pub(crate) function_name: &'a str,
+	pub(crate) function: &'a Function,
+	pub(crate) plugin_id: &'a ( dyn Any + Send + Sync ),
+	pub(crate) context: &'a CallContext,
+}
+
+impl CallInfo<'_> {
+
+	/// Returns the WIT interface path of the called function (e.g., `"my:package/api"`).
+	pub fn interface_path( &self ) -> &str { self.interface_path }
+
+	/// Returns the name of the called function.
+	pub fn function_name( &self ) -> &str { self.function_name }
+
+	/// Returns the metadata of the called function.
+	pub fn function( &self ) -> &Function { self.function }
+
+	/// Returns the id of the called plugin, as given to the binding it is plugged into.
+	///
+	/// Returns `None` if `PluginId` is not the binding's plugin id type.
+	pub fn plugin_id<PluginId: 'static>( &self ) -> Option<&PluginId> {
+		self.plugin_id.downcast_ref()
+	}
+
+	/// Returns the id of the dispatch this call belongs to.
+	pub fn dispatch_id( &self ) -> DispatchId { self.context.dispatch_id }
+
+	/// Returns the context of the dispatch this call belongs to.
+	pub fn context( &self ) -> &CallContext { self.context }
+
+}
+
+/// Outcome of a single call into a plugin, handed to the observer set with
+/// [`Plugin::with_dispatch_observer`]( crate::Plugin::with_dispatch_observer ).
+///
+/// Fuel figures are available only when fuel consumption is enabled in the engine.
+/// Comparing [`fuel_consumed`](Self::fuel_consumed) against the budget across many
+/// calls lets hosts calibrate limits empirically instead of guessing.
+pub struct DispatchReport<'a> {
+	pub(crate) call: &'a CallInfo<'a>,
+	pub(crate) fuel: Option<u64>,
+	pub(crate) fuel_consumed: Option<u64>,
+	pub(crate) elapsed: Duration,
+	pub(crate) succeeded: bool,
+}
+
+impl DispatchReport<'_> {
+
+	/// Returns the call this report describes.
+	pub fn call( &self ) -> &CallInfo<'_> { self.call }
+
+	/// Returns the fuel available to the plugin when the call started.
+	pub fn fuel( &self ) -> Option<u64> { self.fuel }
+
+	/// Returns the fuel the call consumed.
+	///
+	/// Equal to [`fuel`](Self::fuel) when the call ran out of fuel.
+	pub fn fuel_consumed( &self ) -> Option<u64> { self.fuel_consumed }
+
+	/// Returns the wall-clock time spent inside the plugin.
+	pub fn elapsed( &self ) -> Duration { self.elapsed }
+
+	/// Returns `false` if the plugin trapped.
+	pub fn succeeded( &self ) -> bool { self.succeeded }
+
+}
+
+/// A dispatch has held a plugin for too long, handed to the observer set with
+/// [`Plugin::with_long_hold_observer`]( crate::Plugin::with_long_hold_observer ).
+///
+/// Reported at most once per hold, by the first dispatch contending for the plugin
+/// or the first tick of the plugin's [`Watchdog`]( crate::limits::Watchdog ) after the
+/// threshold has passed, whichever comes first.
+#[derive( Debug, Clone, Copy, PartialEq, Eq )]
+pub struct LongHold {
+	pub(crate) dispatch_id: DispatchId,
+	pub(crate) held_for: Duration,
+}
+
+impl LongHold {
+
+	/// Returns the id of the dispatch holding the plugin.
+	pub fn dispatch_id( &self ) -> DispatchId { self.dispatch_id }
+
+	/// Returns how long the plugin had been held when the hold was reported.
+	pub fn held_for( &self ) -> Duration { self.held_for }
+
+}
@@ -0,0 +1,166 @@
+//! Staged rollout of a new plugin version next to the stable one.
+//!
+//! A plugin instance given a canary with [`PluginInstanceSync::with_canary`]( crate::PluginInstanceSync::with_canary )
+//! or [`PluginInstanceAsync::with_canary`]( crate::PluginInstanceAsync::with_canary ) sends
+//! part of its traffic to the canary as decided by a [`CanaryRollout`]. A comparison hook
+//! may additionally invoke both versions for every call and receive their results as a
+//! [`CanaryComparison`], so regressions show up before the canary takes over.
+
+use std::sync::Arc ;
+use wasmtime::component::Val ;
+
+use crate::{ CallContext, DispatchError, DispatchId };
+
+type CanaryRoute = Arc<dyn Fn( &CallContext ) -> bool + Send + Sync>;
+type ComparisonHook = Arc<dyn Fn( &CanaryComparison<'_> ) + Send + Sync>;
+
+
+
+/// Decides which calls a canary serves and whether its results are compared against
+/// the stable version's.
+///
+/// Only freestanding functions whose [`ReturnKind`]( crate::ReturnKind ) cannot contain
+/// resources are ever routed to the canary, since resources live in the instance that
+/// created them. All other calls go to the stable version.
+///
+/// ```
+/// use wasm_link::{ CanaryRollout, Val };
+///
+/// // Tenant "acme" uses the canary; every call is also run on both versions and compared.
+/// let rollout = CanaryRollout::keyed(| context | {
+/// 	context.metadata().get( "tenant" ) == Some( &Val::String( "acme".to_string() ))
+/// }).with_comparison(| comparison | if !comparison.matches() {
+/// 	eprintln!( "canary diverged on {}: {:?}", comparison.function_name(), comparison.canary() );
+/// });
+/// # let _ = rollout ;
+/// ```
+#[derive( Clone )]
+pub struct CanaryRollout {
+	route: CanaryRoute,
+	comparison: Option<ComparisonHook>,
+}
+
+impl std::fmt::Debug for CanaryRollout {
+	fn fmt( &self, f: &mut std::fmt::Formatter<'_> ) -> std::fmt::Result {
+		f.debug_struct( "CanaryRollout" )
+			.field( "route", &"<closure>" )
+			.field( "comparison", &self.comparison.as_ref().map(| _ | "<closure>" ))
+			.finish()
+	}
+}
+
+impl CanaryRollout {
+
+	/// Routes `percent` of dispatches to the canary.
+	///
+	/// The split is keyed off the [`DispatchId`], so all nested calls of a dispatch
+	/// reach the same version. Values above `100` are treated as `100`.
+	pub fn percentage( percent: u8 ) -> Self {
+		let percent = u64::from( percent.min( 100 ));
+		Self::keyed( move | context | context.dispatch_id().get() % 100 < percent )
+	}
+
+	/// Routes the dispatches for which `route` returns `true` to the canary, e.g.
+	/// based on a tenant id attached to the [`CallContext`].
+	pub fn keyed( route: impl Fn( &CallContext ) -> bool + Send + Sync + 'static ) -> Self {
+		Self { route: Arc::new( route ), comparison: None }
+	}
+
+	/// Invokes both versions for every call eligible for the canary and hands their
+	/// results to `hook`. The call still returns only the result of the version the
+	/// rollout routes it to.
+	///
+	/// Both versions observe every call, so only use comparison for functions
+	/// without side effects outside the plugin.
+	pub fn with_comparison( mut self, hook: impl Fn( &CanaryComparison<'_> ) + Send + Sync + 'static ) -> Self {
+		self.comparison = Some( Arc::new( hook ));
+		self
+	}
+
+	pub(crate) fn routes_to_canary( &self, context: &CallContext ) -> bool {
+		( self.route )( context )
+	}
+
+	pub(crate) fn compares( &self ) -> bool { self.comparison.is_some() }
+
+}
+
+/// A canary attached to a plugin instance.
+pub(crate) struct Canary<Instance> {
+	pub(crate) instance: Instance,
+	pub(crate) rollout: CanaryRollout,
+}
+
+impl<Instance> Canary<Instance> {
+
+	/// Hands the results of a call invoked on both versions to the comparison hook.
+	#[allow( clippy::too_many_arguments )]
+	pub(crate) fn compare(
+		&self,
+		package_name: &str,
+		interface_name: &str,
+		function_name: &str,
+		context: &CallContext,
+		served_by_canary: bool,
+		stable: &Result<Val, DispatchError>,
+		canary: &Result<Val, DispatchError>,
+	) {
+		let Some( hook ) = self.rollout.comparison.as_ref() else { return };
+		hook( &CanaryComparison {
+			interface_path: &format!( "{package_name}/{interface_name}" ),
+			function_name,
+			dispatch_id: context.dispatch_id(),
+			served_by_canary,
+			stable,
+			canary,
+		});
+	}
+
+}
+
+/// The results of a call invoked on both the stable version of a plugin and its canary.
+///
+/// Received by the hook set with [`CanaryRollout::with_comparison`].
+#[derive( Debug )]
+pub struct CanaryComparison<'a> {
+	pub(crate) interface_path: &'a str,
+	pub(crate) function_name: &'a str,
+	pub(crate) dispatch_id: DispatchId,
+	pub(crate) served_by_canary: bool,
+	pub(crate) stable: &'a Result<Val, DispatchError>,
+	pub(crate) canary: &'a Result<Val, DispatchError>,
+}
+
+impl CanaryComparison<'_> {
+
+	/// Returns the WIT interface path of the called function (e.g., `"my:package/api"`).
+	pub fn interface_path( &self ) -> &str { self.interface_path }
+
+	/// Returns the name of the called function.
+	pub fn function_name( &self ) -> &str { self.function_name }
+
+	/// Returns the id of the dispatch the call belongs to.
+	pub fn dispatch_id( &self ) -> DispatchId { self.dispatch_id }
+
+	/// Whether the caller received the canary's result.
+	pub fn served_by_canary( &self ) -> bool { self.served_by_canary }
+
+	/// Returns the result of the stable version.
+	pub fn stable( &self ) -> &Result<Val, DispatchError> { self.stable }
+
+	/// Returns the result of the canary.
+	pub fn canary( &self ) -> &Result<Val, DispatchError> { self.canary }
+
+	/// Whether both versions returned equal values, or both failed with the same error.
+	pub fn matches( &self ) -> bool {
+		match ( self.stable, self.canary ) {
+			( Ok( stable ), Ok( canary )) => stable == canary,
+			( Err( stable ), Err( canary )) => stable.to_string() == canary.to_string(),
+			_ => false,
+		}
+	}
+
+}
+
+#[cfg(test)]
+mod tests { include!( "canary_tests.rs" ); }
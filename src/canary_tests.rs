@@ -0,0 +1,34 @@
+use wasmtime::component::Val ;
+use crate::{ CallContext, DispatchError };
+use super::{ CanaryComparison, CanaryRollout };
+
+
+
+fn comparison<'a>( stable: &'a Result<Val, DispatchError>, canary: &'a Result<Val, DispatchError> ) -> CanaryComparison<'a> {
+	CanaryComparison {
+		interface_path: "test:pkg/root",
+		function_name: "f",
+		dispatch_id: CallContext::new().dispatch_id(),
+		served_by_canary: false,
+		stable,
+		canary,
+	}
+}
+
+#[test]
+fn percentage_routes_that_share_of_dispatches() {
+	let routed = | rollout: CanaryRollout | ( 0..100 )
+		.filter(| _ | rollout.routes_to_canary( &CallContext::new() ))
+		.count();
+	assert_eq!( routed( CanaryRollout::percentage( 0 )), 0 );
+	assert_eq!( routed( CanaryRollout::percentage( 25 )), 25 );
+	assert_eq!( routed( CanaryRollout::percentage( 250 )), 100 );
+}
+
+#[test]
+fn comparison_matches_equal_values_and_errors() {
+	assert!( comparison( &Ok( Val::U32( 1 )), &Ok( Val::U32( 1 ))).matches() );
+	assert!( !comparison( &Ok( Val::U32( 1 )), &Ok( Val::U32( 2 ))).matches() );
+	assert!( comparison( &Err( DispatchError::LockRejected ), &Err( DispatchError::LockRejected )).matches() );
+	assert!( !comparison( &Ok( Val::U32( 1 )), &Err( DispatchError::MissingResponse )).matches() );
+}
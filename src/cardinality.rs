@@ -1,5 +1,6 @@
 //! Cardinality wrappers for plugin collections.
 
+use std::cmp::Ordering ;
 use std::collections::HashMap ;
 use std::future::Future ;
 use std::hash::Hash ;
@@ -53,6 +54,10 @@ pub trait Cardinality<Id, T>: Sized {
 	fn get( &self, id: &Id ) -> Option<&T>
 	where
 		Id: Hash + Eq ;
+
+	/// Returns the first entry matching `predicate`, in no particular order, without
+	/// visiting the entries after it.
+	fn find( &self, predicate: impl FnMut( &Id, &T ) -> bool ) -> Option<( &Id, &T )> ;
 }
 
 /// Exactly one value with ID, guaranteed present.
@@ -124,6 +129,10 @@ impl<Id, T> Cardinality<Id, T> for ExactlyOne<Id, T> {
 		debug_assert!( &self.0 == id, "singleton cardinality id mismatch" );
 		Some( &self.1 )
 	}
+
+	fn find( &self, mut predicate: impl FnMut( &Id, &T ) -> bool ) -> Option<( &Id, &T )> {
+		predicate( &self.0, &self.1 ).then_some(( &self.0, &self.1 ))
+	}
 }
 
 impl<Id, T> Cardinality<Id, T> for AtMostOne<Id, T> {
@@ -178,6 +187,10 @@ impl<Id, T> Cardinality<Id, T> for AtMostOne<Id, T> {
 			}
 		}
 	}
+
+	fn find( &self, mut predicate: impl FnMut( &Id, &T ) -> bool ) -> Option<( &Id, &T )> {
+		self.0.as_ref().map(|( id, value )| ( id, value )).filter(|( id, value )| predicate( id, value ))
+	}
 }
 
 impl<Id: Hash + Eq, T> Cardinality<Id, T> for AtLeastOne<Id, T> {
@@ -231,6 +244,10 @@ impl<Id: Hash + Eq, T> Cardinality<Id, T> for AtLeastOne<Id, T> {
 	{
 		self.0.get( id )
 	}
+
+	fn find( &self, mut predicate: impl FnMut( &Id, &T ) -> bool ) -> Option<( &Id, &T )> {
+		self.0.iter().find(|( id, value )| predicate( id, value ))
+	}
 }
 
 impl<Id: Hash + Eq, T> Cardinality<Id, T> for Any<Id, T> {
@@ -270,6 +287,10 @@ impl<Id: Hash + Eq, T> Cardinality<Id, T> for Any<Id, T> {
 	{
 		self.0.get( id )
 	}
+
+	fn find( &self, mut predicate: impl FnMut( &Id, &T ) -> bool ) -> Option<( &Id, &T )> {
+		self.0.iter().find(|( id, value )| predicate( id, value ))
+	}
 }
 
 impl<Id: Hash + Eq + Into<Val>> From<ExactlyOne<Id, Val>> for Val {
@@ -287,22 +308,47 @@ impl<Id: Hash + Eq + Into<Val>> From<AtMostOne<Id, Val>> for Val {
 	}
 }
 
+/// Entries are sorted by plugin id, so consumers see the same order on every run.
 impl<Id: Hash + Eq + Into<Val>> From<AtLeastOne<Id, Val>> for Val {
 	fn from( socket: AtLeastOne<Id, Val> ) -> Self {
-		Val::Map(
-			socket.0.into_iter()
-				.map(|( id, val )| ( id.into(), val ))
-				.collect()
-		)
+		sorted_map( socket.0.into_iter().map(|( id, val )| ( id.into(), val )))
 	}
 }
 
+/// Entries are sorted by plugin id, so consumers see the same order on every run.
 impl<Id: Hash + Eq + Into<Val>> From<Any<Id, Val>> for Val {
 	fn from( socket: Any<Id, Val> ) -> Self {
-		Val::Map(
-			socket.0.into_iter()
-				.map(|( id, val )| ( id.into(), val ))
-				.collect()
-		)
+		sorted_map( socket.0.into_iter().map(|( id, val )| ( id.into(), val )))
+	}
+}
+
+/// Collects `entries` into a map ordered by key: numerically for integer ids,
+/// lexically for textual ids and by their rendering otherwise.
+fn sorted_map( entries: impl Iterator<Item = ( Val, Val )> ) -> Val {
+	let mut entries = entries.collect::<Vec<_>>();
+	entries.sort_by(|( a, _ ), ( b, _ )| compare_ids( a, b ));
+	Val::Map( entries )
+}
+
+fn compare_ids( a: &Val, b: &Val ) -> Ordering {
+	fn integer( id: &Val ) -> Option<i128> {
+		match id {
+			Val::S8( id ) => Some( i128::from( *id )),
+			Val::U8( id ) => Some( i128::from( *id )),
+			Val::S16( id ) => Some( i128::from( *id )),
+			Val::U16( id ) => Some( i128::from( *id )),
+			Val::S32( id ) => Some( i128::from( *id )),
+			Val::U32( id ) => Some( i128::from( *id )),
+			Val::S64( id ) => Some( i128::from( *id )),
+			Val::U64( id ) => Some( i128::from( *id )),
+			_ => None,
+		}
+	}
+	match ( a, b ) {
+		( Val::String( a ), Val::String( b )) | ( Val::Enum( a ), Val::Enum( b )) => a.cmp( b ),
+		_ => match ( integer( a ), integer( b )) {
+			( Some( a ), Some( b )) => a.cmp( &b ),
+			_ => crate::val::pretty( a ).cmp( &crate::val::pretty( b )),
+		},
 	}
 }
@@ -106,6 +106,32 @@ fn any_maps_and_gets() {
 	assert_eq!( mapped.get( &"b".to_string() ), Some( &12 ));
 }
 
+#[test]
+fn find_stops_at_the_first_match() {
+	let mut visited = 0 ;
+	let values = Any( HashMap::from([
+		( "a".to_string(), 1_u32 ),
+		( "b".to_string(), 2_u32 ),
+		( "c".to_string(), 3_u32 ),
+	]));
+	assert!( values.find(| _, _ | { visited += 1 ; true }).is_some() );
+	assert_eq!( visited, 1 );
+	assert_eq!( values.find(| id, _ | id == "b" ), Some(( &"b".to_string(), &2 )));
+	assert_eq!( values.find(| id, _ | id == "d" ), None );
+
+	let values = AtLeastOne( nem! { "a".to_string() => 1_u32, "b".to_string() => 2_u32 } );
+	assert_eq!( values.find(| _, value | *value == 2 ), Some(( &"b".to_string(), &2 )));
+
+	let values = ExactlyOne( "plugin".to_string(), 10_u32 );
+	assert_eq!( values.find(| _, value | *value == 10 ), Some(( &"plugin".to_string(), &10 )));
+	assert_eq!( values.find(| _, value | *value == 11 ), None );
+
+	let values = AtMostOne( Some(( "plugin".to_string(), 10_u32 )));
+	assert_eq!( values.find(| id, _ | id == "plugin" ), Some(( &"plugin".to_string(), &10 )));
+	let values: AtMostOne<String, u32> = AtMostOne( None );
+	assert_eq!( values.find(| _, _ | true ), None );
+}
+
 #[test]
 fn exactly_one_into_val() {
 	let val = Val::from( ExactlyOne( "id".to_string(), Val::U32( 7 )));
@@ -156,3 +182,17 @@ fn any_into_val() {
 		)
 	));
 }
+
+#[test]
+fn collection_into_val_orders_entries_by_id() {
+	let ids = [ "delta", "alpha", "charlie", "bravo", "echo" ];
+	let val = Val::from( Any( ids.iter().map(| id | ( id.to_string(), Val::Bool( true ))).collect::<HashMap<_, _>>() ));
+	let Val::Map( entries ) = val else { panic!( "Expected a map, found: {val:?}" ) };
+	let order = entries.iter().map(|( id, _ )| id.clone() ).collect::<Vec<_>>();
+	assert_eq!( order, [ "alpha", "bravo", "charlie", "delta", "echo" ].map(| id | Val::String( id.to_string() )));
+
+	let val = Val::from( AtLeastOne( nem! { 10_u32 => Val::Bool( true ), 9 => Val::Bool( true ), 100 => Val::Bool( true ) }));
+	let Val::Map( entries ) = val else { panic!( "Expected a map, found: {val:?}" ) };
+	let order = entries.iter().map(|( id, _ )| id.clone() ).collect::<Vec<_>>();
+	assert_eq!( order, [ Val::U32( 9 ), Val::U32( 10 ), Val::U32( 100 ) ]);
+}
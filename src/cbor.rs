@@ -0,0 +1,336 @@
+//! [CBOR]( https://www.rfc-editor.org/rfc/rfc8949 ) encoding of [`Val`] trees.
+//!
+//! Unlike [WAVE]( crate::wave ), the encoding is self-describing: every value is written
+//! as a two-element array of its kind and its payload, e.g. `["u32", 42]`, so it can be
+//! decoded without the types of the function it belongs to. This allows shipping
+//! dispatch arguments and results to another process or persisting them. Requires the
+//! `cbor` feature.
+//!
+//! ```
+//! use wasm_link::Val ;
+//!
+//! let value = Val::Option( Some( Box::new( Val::String( "text".to_string() ))));
+//! let bytes = wasm_link::cbor::encode( &value ).unwrap();
+//! assert_eq!( wasm_link::cbor::decode( &bytes ).unwrap(), value );
+//! ```
+//!
+//! Resources, futures, streams and error contexts only have meaning within the store
+//! that created them and cannot be encoded.
+
+use thiserror::Error ;
+use wasmtime::component::Val ;
+
+/// Deepest nesting [`decode`] accepts, protecting the stack from malicious input.
+const MAX_DEPTH: usize = 256 ;
+
+
+
+/// Errors that occur when encoding or decoding values.
+#[derive( Error, Debug, Clone, PartialEq, Eq )]
+pub enum CborError {
+	/// The value contains something only meaningful within its store, such as a resource.
+	#[error( "Unencodable Value: {0}" )] Unencodable( String ),
+	/// The input is not valid CBOR or not an encoded value.
+	#[error( "Malformed Input: {0}" )] Malformed( String ),
+}
+
+/// Encodes a value as CBOR.
+///
+/// # Errors
+/// Returns [`CborError::Unencodable`] if the value contains a resource, future, stream
+/// or error context.
+pub fn encode( value: &Val ) -> Result<Vec<u8>, CborError> {
+	let mut out = Vec::new();
+	encode_into( value, &mut out )?;
+	Ok( out )
+}
+
+/// Decodes a value encoded with [`encode`].
+///
+/// # Errors
+/// Returns [`CborError::Malformed`] if `bytes` is not exactly one encoded value.
+pub fn decode( bytes: &[u8] ) -> Result<Val, CborError> {
+	let mut reader = Reader { bytes, position: 0 };
+	let value = to_val( reader.item( 0 )? )?;
+	match reader.position == bytes.len() {
+		true => Ok( value ),
+		false => Err( malformed( "trailing bytes" )),
+	}
+}
+
+fn malformed( reason: impl Into<String> ) -> CborError { CborError::Malformed( reason.into() ) }
+
+fn head( major: u8, argument: u64, out: &mut Vec<u8> ) {
+	let major = major << 5 ;
+	let bytes = argument.to_be_bytes();
+	match argument {
+		0..=23 => out.push( major | bytes[7] ),
+		24..=0xff => out.extend([ major | 0x18, bytes[7] ]),
+		0x100..=0xffff => { out.push( major | 0x19 ); out.extend( &bytes[6..] ); },
+		0x1_0000..=0xffff_ffff => { out.push( major | 0x1a ); out.extend( &bytes[4..] ); },
+		_ => { out.push( major | 0x1b ); out.extend( bytes ); },
+	}
+}
+
+fn text( value: &str, out: &mut Vec<u8> ) {
+	head( 3, value.len() as u64, out );
+	out.extend( value.as_bytes() );
+}
+
+fn signed( value: i64, out: &mut Vec<u8> ) {
+	match u64::try_from( value ) {
+		Ok( value ) => head( 0, value, out ),
+		Err( _ ) => head( 1, ( -1 - value ).unsigned_abs(), out ),
+	}
+}
+
+fn optional( value: Option<&Val>, out: &mut Vec<u8> ) -> Result<(), CborError> {
+	if let Some( value ) = value { return encode_into( value, out ) }
+	out.push( 0xf6 );
+	Ok(())
+}
+
+fn encode_into( value: &Val, out: &mut Vec<u8> ) -> Result<(), CborError> {
+	let kind = | kind: &str, out: &mut Vec<u8> | { head( 4, 2, out ); text( kind, out ); };
+	match value {
+		Val::Bool( value ) => { kind( "bool", out ); out.push( if *value { 0xf5 } else { 0xf4 }); },
+		Val::S8( value ) => { kind( "s8", out ); signed( i64::from( *value ), out ); },
+		Val::U8( value ) => { kind( "u8", out ); head( 0, u64::from( *value ), out ); },
+		Val::S16( value ) => { kind( "s16", out ); signed( i64::from( *value ), out ); },
+		Val::U16( value ) => { kind( "u16", out ); head( 0, u64::from( *value ), out ); },
+		Val::S32( value ) => { kind( "s32", out ); signed( i64::from( *value ), out ); },
+		Val::U32( value ) => { kind( "u32", out ); head( 0, u64::from( *value ), out ); },
+		Val::S64( value ) => { kind( "s64", out ); signed( *value, out ); },
+		Val::U64( value ) => { kind( "u64", out ); head( 0, *value, out ); },
+		Val::Float32( value ) => { kind( "f32", out ); out.push( 0xfa ); out.extend( value.to_be_bytes() ); },
+		Val::Float64( value ) => { kind( "f64", out ); out.push( 0xfb ); out.extend( value.to_be_bytes() ); },
+		Val::Char( value ) => { kind( "char", out ); text( value.encode_utf8( &mut [0; 4] ), out ); },
+		Val::String( value ) => { kind( "string", out ); text( value, out ); },
+		Val::Enum( case ) => { kind( "enum", out ); text( case, out ); },
+		Val::Flags( flags ) => {
+			kind( "flags", out );
+			head( 4, flags.len() as u64, out );
+			for flag in flags { text( flag, out ); }
+		},
+		Val::List( items ) | Val::Tuple( items ) => {
+			kind( if matches!( value, Val::List( _ )) { "list" } else { "tuple" }, out );
+			head( 4, items.len() as u64, out );
+			items.iter().try_for_each(| item | encode_into( item, out ))?;
+		},
+		Val::Record( fields ) => {
+			kind( "record", out );
+			head( 5, fields.len() as u64, out );
+			fields.iter().try_for_each(|( name, value )| { text( name, out ); encode_into( value, out ) })?;
+		},
+		Val::Map( entries ) => {
+			kind( "map", out );
+			head( 4, entries.len() as u64, out );
+			entries.iter().try_for_each(|( key, value )| {
+				head( 4, 2, out );
+				encode_into( key, out )?;
+				encode_into( value, out )
+			})?;
+		},
+		Val::Option( value ) => { kind( "option", out ); optional( value.as_deref(), out )?; },
+		Val::Result( result ) => {
+			kind( "result", out );
+			head( 4, 2, out );
+			let ( case, value ) = match result {
+				Ok( value ) => ( "ok", value ),
+				Err( value ) => ( "err", value ),
+			};
+			text( case, out );
+			optional( value.as_deref(), out )?;
+		},
+		Val::Variant( case, value ) => {
+			kind( "variant", out );
+			head( 4, 2, out );
+			text( case, out );
+			optional( value.as_deref(), out )?;
+		},
+		Val::Resource( _ ) => return Err( CborError::Unencodable( "resource".to_string() )),
+		Val::Future( _ ) => return Err( CborError::Unencodable( "future".to_string() )),
+		Val::Stream( _ ) => return Err( CborError::Unencodable( "stream".to_string() )),
+		Val::ErrorContext( _ ) => return Err( CborError::Unencodable( "error-context".to_string() )),
+	}
+	Ok(())
+}
+
+/// A generic CBOR data item.
+#[derive( Debug )]
+enum Item {
+	Unsigned( u64 ),
+	/// Negative integer `-1 - n`
+	Negative( u64 ),
+	Text( String ),
+	Array( Vec<Item> ),
+	Map( Vec<( Item, Item )> ),
+	Bool( bool ),
+	Null,
+	Float32( f32 ),
+	Float64( f64 ),
+}
+
+struct Reader<'a> {
+	bytes: &'a [u8],
+	position: usize,
+}
+
+impl Reader<'_> {
+
+	fn take( &mut self, count: usize ) -> Result<&[u8], CborError> {
+		let end = self.position.checked_add( count ).filter(| end | *end <= self.bytes.len() )
+			.ok_or_else(|| malformed( "unexpected end of input" ))?;
+		let bytes = &self.bytes[self.position..end];
+		self.position = end ;
+		Ok( bytes )
+	}
+
+	fn take_array<const N: usize>( &mut self ) -> Result<[u8; N], CborError> {
+		let Ok( bytes ) = <[u8; N]>::try_from( self.take( N )? ) else { return Err( malformed( "unexpected end of input" )) };
+		Ok( bytes )
+	}
+
+	fn argument( &mut self, info: u8 ) -> Result<u64, CborError> {
+		let mut be = | count: usize | self.take( count ).map(| bytes | bytes.iter().fold( 0, | acc, byte | acc << 8 | u64::from( *byte )));
+		match info {
+			0..=23 => Ok( u64::from( info )),
+			24 => be( 1 ),
+			25 => be( 2 ),
+			26 => be( 4 ),
+			27 => be( 8 ),
+			_ => Err( malformed( "indefinite lengths are not supported" )),
+		}
+	}
+
+	fn length( &mut self, info: u8 ) -> Result<usize, CborError> {
+		let length = usize::try_from( self.argument( info )? ).map_err(| _ | malformed( "length out of range" ))?;
+		// Every element takes at least one byte, so longer lengths are certainly truncated
+		match length <= self.bytes.len() - self.position {
+			true => Ok( length ),
+			false => Err( malformed( "unexpected end of input" )),
+		}
+	}
+
+	fn item( &mut self, depth: usize ) -> Result<Item, CborError> {
+		if depth > MAX_DEPTH { return Err( malformed( "nesting too deep" )) }
+		let initial = self.take( 1 )?[0];
+		let ( major, info ) = ( initial >> 5, initial & 0x1f );
+		match major {
+			0 => Ok( Item::Unsigned( self.argument( info )? )),
+			1 => Ok( Item::Negative( self.argument( info )? )),
+			3 => {
+				let length = self.length( info )?;
+				let bytes = self.take( length )?.to_vec();
+				String::from_utf8( bytes ).map( Item::Text ).map_err(| _ | malformed( "invalid UTF-8" ))
+			},
+			4 => {
+				let length = self.length( info )?;
+				( 0..length ).map(| _ | self.item( depth + 1 )).collect::<Result<_, _>>().map( Item::Array )
+			},
+			5 => {
+				let length = self.length( info )?;
+				( 0..length ).map(| _ | Ok(( self.item( depth + 1 )?, self.item( depth + 1 )? )))
+					.collect::<Result<_, _>>().map( Item::Map )
+			},
+			7 => match info {
+				20 => Ok( Item::Bool( false )),
+				21 => Ok( Item::Bool( true )),
+				22 => Ok( Item::Null ),
+				26 => Ok( Item::Float32( f32::from_be_bytes( self.take_array()?))),
+				27 => Ok( Item::Float64( f64::from_be_bytes( self.take_array()?))),
+				_ => Err( malformed( format!( "unsupported simple value {info}" ))),
+			},
+			_ => Err( malformed( format!( "unsupported major type {major}" ))),
+		}
+	}
+
+}
+
+/// Converts an item to a value; items are already limited to [`MAX_DEPTH`].
+fn to_val( item: Item ) -> Result<Val, CborError> {
+	let Item::Array( parts ) = item else { return Err( malformed( "expected a [kind, payload] array" )) };
+	let [ Item::Text( kind ), payload ]: [Item; 2] = parts.try_into()
+		.map_err(| _ | malformed( "expected a [kind, payload] array" ))?
+	else { return Err( malformed( "expected a [kind, payload] array" )) };
+
+	let unsigned = | payload: &Item | match payload {
+		Item::Unsigned( value ) => Ok( *value ),
+		_ => Err( malformed( format!( "expected an unsigned integer for {kind}" ))),
+	};
+	let signed = | payload: &Item | match payload {
+		Item::Unsigned( value ) => i64::try_from( *value ).map_err(| _ | ()),
+		Item::Negative( value ) => i64::try_from( *value ).map(| value | -1 - value ).map_err(| _ | ()),
+		_ => Err(()),
+	}.map_err(| () | malformed( format!( "expected a signed integer for {kind}" )));
+	let range = || malformed( format!( "{kind} out of range" ));
+	let text = | payload: Item | match payload {
+		Item::Text( value ) => Ok( value ),
+		_ => Err( malformed( format!( "expected text for {kind}" ))),
+	};
+	let array = | payload: Item | match payload {
+		Item::Array( items ) => Ok( items ),
+		_ => Err( malformed( format!( "expected an array for {kind}" ))),
+	};
+	let optional = | payload: Item | match payload {
+		Item::Null => Ok( None ),
+		item => to_val( item ).map(| value | Some( Box::new( value ))),
+	};
+	let case = | payload: Item | match <[Item; 2]>::try_from( array( payload )? ) {
+		Ok([ Item::Text( case ), value ]) => Ok(( case, optional( value )? )),
+		_ => Err( malformed( format!( "expected a [case, payload] array for {kind}" ))),
+	};
+
+	Ok( match kind.as_str() {
+		"bool" => match payload { Item::Bool( value ) => Val::Bool( value ), _ => return Err( malformed( "expected a bool" )) },
+		"s8" => Val::S8( i8::try_from( signed( &payload )? ).map_err(| _ | range() )? ),
+		"u8" => Val::U8( u8::try_from( unsigned( &payload )? ).map_err(| _ | range() )? ),
+		"s16" => Val::S16( i16::try_from( signed( &payload )? ).map_err(| _ | range() )? ),
+		"u16" => Val::U16( u16::try_from( unsigned( &payload )? ).map_err(| _ | range() )? ),
+		"s32" => Val::S32( i32::try_from( signed( &payload )? ).map_err(| _ | range() )? ),
+		"u32" => Val::U32( u32::try_from( unsigned( &payload )? ).map_err(| _ | range() )? ),
+		"s64" => Val::S64( signed( &payload )? ),
+		"u64" => Val::U64( unsigned( &payload )? ),
+		"f32" => match payload { Item::Float32( value ) => Val::Float32( value ), _ => return Err( malformed( "expected a single-precision float" )) },
+		"f64" => match payload { Item::Float64( value ) => Val::Float64( value ), _ => return Err( malformed( "expected a double-precision float" )) },
+		"char" => {
+			let value = text( payload )?;
+			let mut chars = value.chars();
+			match ( chars.next(), chars.next() ) {
+				( Some( value ), None ) => Val::Char( value ),
+				_ => return Err( malformed( "expected a single char" )),
+			}
+		},
+		"string" => Val::String( text( payload )? ),
+		"enum" => Val::Enum( text( payload )? ),
+		"flags" => Val::Flags( array( payload )?.into_iter().map( text ).collect::<Result<_, _>>()? ),
+		"list" => Val::List( array( payload )?.into_iter().map( to_val ).collect::<Result<_, _>>()? ),
+		"tuple" => Val::Tuple( array( payload )?.into_iter().map( to_val ).collect::<Result<_, _>>()? ),
+		"record" => match payload {
+			Item::Map( fields ) => Val::Record( fields.into_iter()
+				.map(|( name, value )| Ok(( text( name )?, to_val( value )? )))
+				.collect::<Result<_, CborError>>()? ),
+			_ => return Err( malformed( "expected a map for record" )),
+		},
+		"map" => Val::Map( array( payload )?.into_iter()
+			.map(| entry | match <[Item; 2]>::try_from( array( entry )? ) {
+				Ok([ key, value ]) => Ok(( to_val( key )?, to_val( value )? )),
+				Err( _ ) => Err( malformed( "expected a [key, value] array for map entry" )),
+			})
+			.collect::<Result<_, _>>()? ),
+		"option" => Val::Option( optional( payload )? ),
+		"result" => match case( payload )? {
+			( case, value ) if case == "ok" => Val::Result( Ok( value )),
+			( case, value ) if case == "err" => Val::Result( Err( value )),
+			( case, _ ) => return Err( malformed( format!( "unknown result case {case}" ))),
+		},
+		"variant" => {
+			let ( case, value ) = case( payload )?;
+			Val::Variant( case, value )
+		},
+		_ => return Err( malformed( format!( "unknown kind {kind}" ))),
+	})
+}
+
+#[cfg(test)]
+mod tests { include!( "cbor_tests.rs" ); }
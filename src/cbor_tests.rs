@@ -0,0 +1,48 @@
+use wasmtime::component::Val ;
+use super::{ decode, encode, CborError };
+
+
+
+fn round_trip( value: Val ) {
+	let bytes = encode( &value ).expect( "Failed to encode" );
+	assert_eq!( decode( &bytes ), Ok( value ));
+}
+
+#[test]
+fn scalars_round_trip() {
+	round_trip( Val::Bool( true ));
+	round_trip( Val::S8( i8::MIN ));
+	round_trip( Val::U16( u16::MAX ));
+	round_trip( Val::S32( -1 ));
+	round_trip( Val::S64( i64::MIN ));
+	round_trip( Val::U64( u64::MAX ));
+	round_trip( Val::Float32( 1.5 ));
+	round_trip( Val::Float64( -0.25 ));
+	round_trip( Val::Char( 'ř' ));
+	round_trip( Val::String( "text".repeat( 100 )));
+}
+
+#[test]
+fn composites_round_trip() {
+	round_trip( Val::Record( vec![
+		( "items".to_string(), Val::List( vec![ Val::U32( 1 ), Val::U32( 300 )])),
+		( "pair".to_string(), Val::Tuple( vec![ Val::Enum( "a".to_string() ), Val::Flags( vec![ "x".to_string() ])])),
+		( "lookup".to_string(), Val::Map( vec![( Val::String( "k".to_string() ), Val::Option( None ))])),
+	]));
+	round_trip( Val::Result( Err( Some( Box::new( Val::Variant( "boom".to_string(), None ))))));
+	round_trip( Val::Result( Ok( None )));
+}
+
+#[test]
+fn encoding_is_canonical_cbor() {
+	assert_eq!( encode( &Val::U32( 42 )), Ok( vec![ 0x82, 0x63, b'u', b'3', b'2', 0x18, 42 ]));
+}
+
+#[test]
+fn malformed_input_is_rejected() {
+	let bytes = encode( &Val::List( vec![ Val::U32( 1 )])).expect( "Failed to encode" );
+	assert!( matches!( decode( &bytes[..bytes.len() - 1] ), Err( CborError::Malformed( _ ))));
+	assert!( matches!( decode( &[ 0x82, 0x62, b'u', b'8', 0x19, 0x01, 0x00 ]), Err( CborError::Malformed( _ ))));
+	assert!( matches!( decode( &[ 0x9b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff ]), Err( CborError::Malformed( _ ))));
+	assert!( matches!( decode( &[ 0x81; 1000 ]), Err( CborError::Malformed( _ ))));
+}
@@ -0,0 +1,131 @@
+use std::collections::BTreeMap ;
+use wasmtime::Engine ;
+use wasmtime::component::Component ;
+use wasmtime::component::types::{ ComponentFunc, ComponentItem, Type };
+
+use crate::export_path ;
+use crate::socket_shape::outermost ;
+
+
+
+/// Facts about the compiled component behind a plugin instance, for dashboards and
+/// for finding bloated plugins.
+///
+/// Returned by [`PluginInstanceSync::component_info`]( crate::PluginInstanceSync::component_info )
+/// and [`PluginInstanceAsync::component_info`]( crate::PluginInstanceAsync::component_info ).
+/// Only what Wasmtime keeps after compilation is available; custom sections such as
+/// `producers` are dropped by then and must be read from the component's bytes instead.
+#[derive( Debug, Clone, Default, PartialEq, Eq )]
+pub struct ComponentInfo {
+	/// Size of the compiled code and data in bytes
+	compiled_size: usize,
+	/// Number of compiled core functions
+	functions: usize,
+	/// Number of linear memories, if known before instantiation
+	memories: Option<u32>,
+	/// Names of the component's imports
+	imports: Vec<String>,
+	/// Names of the component's exports
+	exports: Vec<String>,
+	/// WIT signatures of the exported functions, keyed by `interface#function`
+	signatures: BTreeMap<String, String>,
+}
+
+impl ComponentInfo {
+
+	pub(crate) fn new( component: &Component, engine: &Engine ) -> Self {
+		let image = component.image_range();
+		let ty = component.component_type();
+		Self {
+			compiled_size: image.end.addr() - image.start.addr(),
+			functions: component.functions().count(),
+			memories: component.resources_required().map(| resources | resources.num_memories ),
+			imports: ty.imports( engine ).map(|( name, _ )| name.to_string() ).collect(),
+			exports: ty.exports( engine ).map(|( name, _ )| name.to_string() ).collect(),
+			signatures: ty.exports( engine )
+				.filter_map(|( name, item )| match item.ty {
+					ComponentItem::ComponentInstance( instance ) => Some(( export_path::unversioned( name ), instance )),
+					_ => None,
+				})
+				.flat_map(|( interface, instance )| instance.exports( engine )
+					.filter_map(|( function, item )| match item.ty {
+						ComponentItem::ComponentFunc( func ) => Some(( format!( "{interface}#{function}" ), signature( &func ))),
+						_ => None,
+					})
+					.collect::<Vec<_>>()
+				)
+				.collect(),
+		}
+	}
+
+	/// Returns the size of the compiled code and data in bytes.
+	pub fn compiled_size( &self ) -> usize { self.compiled_size }
+
+	/// Returns the number of compiled core functions, including adapters.
+	pub fn functions( &self ) -> usize { self.functions }
+
+	/// Returns the number of linear memories the component's core instances allocate,
+	/// or `None` if it instantiates imported modules.
+	pub fn memories( &self ) -> Option<u32> { self.memories }
+
+	/// Returns the names of the component's imports, e.g. `my:package/api`.
+	pub fn imports( &self ) -> &[String] { &self.imports }
+
+	/// Returns the names of the component's exports.
+	pub fn exports( &self ) -> &[String] { &self.exports }
+
+	/// Returns the WIT signature of a function the component exports from an interface,
+	/// e.g. `func(a: u32, b: u32) -> u32`.
+	///
+	/// Interfaces are looked up by their unversioned path. Named types are written out
+	/// structurally, as their names do not survive compilation, and resources appear as
+	/// `own<resource>` or `borrow<resource>`.
+	pub fn signature( &self, interface_path: &str, function: &str ) -> Option<&str> {
+		self.signatures.get( &format!( "{interface_path}#{function}" )).map( String::as_str )
+	}
+
+	pub(crate) fn signatures( &self ) -> &BTreeMap<String, String> { &self.signatures }
+
+}
+
+fn signature( func: &ComponentFunc ) -> String {
+	let params = func.params().map(|( name, ty )| format!( "{name}: {}", render( &ty ))).collect::<Vec<_>>();
+	let results = func.results().map(| ty | render( &ty )).collect::<Vec<_>>();
+	match results.as_slice() {
+		[] => format!( "func({})", params.join( ", " )),
+		[ result ] => format!( "func({}) -> {result}", params.join( ", " )),
+		_ => format!( "func({}) -> ({})", params.join( ", " ), results.join( ", " )),
+	}
+}
+
+/// Writes `ty` in WIT syntax, spelling out named types.
+fn render( ty: &Type ) -> String {
+	let join = | items: Vec<String> | items.join( ", " );
+	match ty {
+		Type::List( list ) => format!( "list<{}>", render( &list.ty() )),
+		Type::Map( map ) => format!( "map<{}, {}>", render( &map.key() ), render( &map.value() )),
+		Type::Option( option ) => format!( "option<{}>", render( &option.ty() )),
+		Type::Tuple( tuple ) => format!( "tuple<{}>", join( tuple.types().map(| ty | render( &ty )).collect() )),
+		Type::Result( result ) => match ( result.ok(), result.err() ) {
+			( None, None ) => "result".to_string(),
+			( Some( ok ), None ) => format!( "result<{}>", render( &ok )),
+			( None, Some( err )) => format!( "result<_, {}>", render( &err )),
+			( Some( ok ), Some( err )) => format!( "result<{}, {}>", render( &ok ), render( &err )),
+		},
+		Type::Record( record ) => format!( "record {{ {} }}", join( record.fields()
+			.map(| field | format!( "{}: {}", field.name, render( &field.ty )))
+			.collect()
+		)),
+		Type::Variant( variant ) => format!( "variant {{ {} }}", join( variant.cases()
+			.map(| case | match case.ty {
+				Some( ty ) => format!( "{}({})", case.name, render( &ty )),
+				None => case.name.to_string(),
+			})
+			.collect()
+		)),
+		Type::Enum( enum_type ) => format!( "enum {{ {} }}", join( enum_type.names().map( str::to_string ).collect() )),
+		Type::Flags( flags ) => format!( "flags {{ {} }}", join( flags.names().map( str::to_string ).collect() )),
+		Type::Own( _ ) | Type::Borrow( _ ) => format!( "{}<resource>", outermost( ty )),
+		ty => outermost( ty ).to_string(),
+	}
+}
@@ -0,0 +1,169 @@
+//! Test vectors verifying that plugins honour the contract of a binding.
+//!
+//! An [`Interface`]( crate::Interface ) may declare [`TestVector`]s with
+//! [`Interface::with_test_vectors`]( crate::Interface::with_test_vectors ). Each vector
+//! names a function, its arguments and, optionally, the expected result, all encoded as
+//! [WAVE]( crate::wave ). [`Binding::run_conformance`]( crate::Binding::run_conformance )
+//! executes them against every plugged plugin, so hosts can reject plugins that claim
+//! a binding but violate its contract. Running vectors requires the `wave` feature.
+
+#[cfg( feature = "wave" )] use thiserror::Error ;
+#[cfg( feature = "wave" )] use wasmtime::component::{ Type, Val };
+
+#[cfg( feature = "wave" )] use crate::wave::WaveError ;
+
+
+
+/// A single call a plugin must handle, and the result it must produce.
+///
+/// ```
+/// use wasm_link::conformance::TestVector ;
+///
+/// let vector = TestVector::new( "add", "40, 2" ).expecting( "42" );
+/// assert_eq!( vector.function(), "add" );
+/// assert_eq!( vector.expected(), Some( "42" ));
+/// ```
+#[derive( Debug, Clone, PartialEq, Eq )]
+#[cfg_attr( feature = "serde", derive( serde::Serialize, serde::Deserialize ))]
+pub struct TestVector {
+	/// Name of the called function
+	function: String,
+	/// Comma-separated WAVE arguments
+	#[cfg_attr( feature = "serde", serde( default ))]
+	args: String,
+	/// WAVE-encoded expected result; any successful result passes if not set
+	#[cfg_attr( feature = "serde", serde( default, skip_serializing_if = "Option::is_none" ))]
+	expected: Option<String>,
+}
+
+impl TestVector {
+
+	/// Creates a vector calling `function` with the comma-separated WAVE `args`.
+	/// The call passes as long as it succeeds.
+	pub fn new( function: impl Into<String>, args: impl Into<String> ) -> Self {
+		Self { function: function.into(), args: args.into(), expected: None }
+	}
+
+	/// Requires the call to return the WAVE-encoded `result`.
+	///
+	/// Results are compared as values, so formatting differences such as whitespace
+	/// do not matter.
+	pub fn expecting( mut self, result: impl Into<String> ) -> Self {
+		self.expected = Some( result.into() );
+		self
+	}
+
+	/// Returns the name of the called function.
+	pub fn function( &self ) -> &str { &self.function }
+
+	/// Returns the WAVE-encoded arguments.
+	pub fn args( &self ) -> &str { &self.args }
+
+	/// Returns the WAVE-encoded expected result, if any.
+	pub fn expected( &self ) -> Option<&str> { self.expected.as_deref() }
+
+	#[cfg( feature = "wave" )]
+	pub(crate) fn check( &self, actual: &Val, ty: Option<&Type> ) -> Result<(), ConformanceFailure> {
+		let Some( expected ) = &self.expected else { return Ok(()) };
+		let mismatch = || ConformanceFailure::Mismatch {
+			expected: expected.clone(),
+			actual: crate::wave::format( actual ).unwrap_or_else(| _ | format!( "{actual:?}" )),
+		};
+		let ty = ty.ok_or_else( mismatch )?;
+		let expected_value = Val::from_wave( ty, expected )
+			.map_err(| err | ConformanceFailure::InvalidExpectation( err.to_string() ))?;
+		match expected_value == *actual {
+			true => Ok(()),
+			false => Err( mismatch() ),
+		}
+	}
+
+}
+
+/// Why a [`TestVector`] failed.
+#[cfg( feature = "wave" )]
+#[derive( Error, Debug )]
+pub enum ConformanceFailure {
+	/// The plugin returned a different result than expected.
+	#[error( "Result Mismatch: expected {expected}, got {actual}" )]
+	Mismatch {
+		/// The expected result
+		expected: String,
+		/// The actual result, WAVE-encoded where possible
+		actual: String,
+	},
+	/// The expected result does not match the function's result type.
+	#[error( "Invalid Expectation: {0}" )] InvalidExpectation( String ),
+	/// The arguments could not be parsed or the dispatch failed.
+	#[error( transparent )] Call( #[from] WaveError ),
+}
+
+#[cfg( feature = "wave" )]
+impl From<crate::DispatchError> for ConformanceFailure {
+	fn from( err: crate::DispatchError ) -> Self { Self::Call( err.into() ) }
+}
+
+/// The outcome of running one [`TestVector`] against one plugin.
+#[cfg( feature = "wave" )]
+#[derive( Debug )]
+pub struct VectorOutcome {
+	interface: String,
+	vector: TestVector,
+	result: Result<(), ConformanceFailure>,
+}
+
+#[cfg( feature = "wave" )]
+impl VectorOutcome {
+
+	pub(crate) fn new( interface: &str, vector: &TestVector, result: Result<(), ConformanceFailure> ) -> Self {
+		Self { interface: interface.to_string(), vector: vector.clone(), result }
+	}
+
+	/// Returns the name of the interface declaring the vector.
+	pub fn interface( &self ) -> &str { &self.interface }
+
+	/// Returns the vector that was run.
+	pub fn vector( &self ) -> &TestVector { &self.vector }
+
+	/// Returns `Ok` if the plugin passed the vector.
+	pub fn result( &self ) -> &Result<(), ConformanceFailure> { &self.result }
+
+	/// Whether the plugin passed the vector.
+	pub fn passed( &self ) -> bool { self.result.is_ok() }
+
+}
+
+/// The outcomes of all test vectors of a binding run against a single plugin.
+///
+/// Returned by [`Binding::run_conformance`]( crate::Binding::run_conformance ).
+#[cfg( feature = "wave" )]
+#[derive( Debug, Default )]
+pub struct ConformanceReport {
+	outcomes: Vec<VectorOutcome>,
+}
+
+#[cfg( feature = "wave" )]
+impl ConformanceReport {
+
+	/// Whether the plugin passed every vector.
+	pub fn passed( &self ) -> bool { self.outcomes.iter().all( VectorOutcome::passed ) }
+
+	/// Returns the outcomes of all vectors, in the order they were run.
+	pub fn outcomes( &self ) -> &[VectorOutcome] { &self.outcomes }
+
+	/// Returns the outcomes of the vectors the plugin failed.
+	pub fn failures( &self ) -> impl Iterator<Item = &VectorOutcome> {
+		self.outcomes.iter().filter(| outcome | !outcome.passed() )
+	}
+
+}
+
+#[cfg( feature = "wave" )]
+impl FromIterator<VectorOutcome> for ConformanceReport {
+	fn from_iter<I: IntoIterator<Item = VectorOutcome>>( iter: I ) -> Self {
+		Self { outcomes: iter.into_iter().collect() }
+	}
+}
+
+#[cfg( all( test, feature = "wave" ))]
+mod tests { include!( "conformance_tests.rs" ); }
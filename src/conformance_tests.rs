@@ -0,0 +1,24 @@
+use wasmtime::component::{ Type, Val };
+use super::{ ConformanceFailure, TestVector };
+
+
+
+#[test]
+fn vectors_without_expectation_accept_any_result() {
+	assert!( TestVector::new( "f", "" ).check( &Val::U32( 7 ), None ).is_ok() );
+}
+
+#[test]
+fn results_are_compared_as_values() {
+	let vector = TestVector::new( "f", "" ).expecting( "  7 " );
+	assert!( vector.check( &Val::U32( 7 ), Some( &Type::U32 )).is_ok() );
+	match vector.check( &Val::U32( 8 ), Some( &Type::U32 )) {
+		Err( ConformanceFailure::Mismatch { expected, actual }) if expected == "  7 " && actual == "8" => {}
+		value => panic!( "Expected Mismatch, found: {value:#?}" ),
+	}
+	assert!( matches!( vector.check( &Val::U32( 7 ), None ), Err( ConformanceFailure::Mismatch { .. })));
+	assert!( matches!(
+		TestVector::new( "f", "" ).expecting( "\"7\"" ).check( &Val::U32( 7 ), Some( &Type::U32 )),
+		Err( ConformanceFailure::InvalidExpectation( _ )),
+	));
+}
@@ -0,0 +1,64 @@
+//! Debugging plugins.
+//!
+//! [`config`] returns an engine configuration meant for development: guest code is
+//! compiled without optimizations and with DWARF debug info, and traps carry a core
+//! dump. Plugins created with [`Plugin::with_core_dumps`]( crate::Plugin::with_core_dumps )
+//! write those core dumps to a directory of their own, one file per trapping call, so
+//! a crash in one plugin of a large graph can be inspected after the fact.
+//!
+//! To step through a plugin live, run the host under `gdb` or `lldb` with an engine
+//! created from [`config`]. Wasmtime registers the compiled guest code with the
+//! debugger, so breakpoints can be set on the guest's own function names. Since every
+//! plugin of a graph shares the engine, narrow breakpoints down to the plugin to debug
+//! by its function names, or link that plugin alone in a test graph.
+//!
+//! ```
+//! use wasm_link::Engine ;
+//!
+//! let engine = Engine::new( &wasm_link::debug::config() ).expect( "Invalid configuration" );
+//! # let _ = engine ;
+//! ```
+
+use std::path::PathBuf ;
+use wasmtime::{ AsContextMut, Config, OptLevel, WasmCoreDump };
+
+
+
+/// Returns an engine configuration for debugging plugins.
+///
+/// Enables DWARF debug info for native debuggers and core dumps on traps, and turns off
+/// optimizations so that guest locals are not optimized away. Compiled code is slower
+/// and larger; do not use it in production.
+pub fn config() -> Config {
+	let mut config = Config::new();
+	config
+		.debug_info( true )
+		.coredump_on_trap( true )
+		.cranelift_opt_level( OptLevel::None );
+	config
+}
+
+/// Writes the core dumps of a plugin's trapping calls to its directory.
+#[derive( Debug )]
+pub(crate) struct CoreDumps {
+	dir: PathBuf,
+	written: usize,
+}
+
+impl CoreDumps {
+
+	pub(crate) fn new( dir: PathBuf ) -> Self { Self { dir, written: 0 } }
+
+	/// Writes the core dump attached to `error`, if any, as `<n>-<function>.coredump`.
+	///
+	/// Failing to write the file is ignored, so that the dispatch still reports the trap
+	/// rather than an I/O error.
+	pub(crate) fn write( &mut self, store: impl AsContextMut, interface_path: &str, function_name: &str, error: &wasmtime::Error ) {
+		let Some( dump ) = error.downcast_ref::<WasmCoreDump>() else { return };
+		let bytes = dump.serialize( store, &format!( "{interface_path}#{function_name}" ));
+		let path = self.dir.join( format!( "{}-{function_name}.coredump", self.written ));
+		self.written += 1 ;
+		let _ = std::fs::create_dir_all( &self.dir ).and_then(| () | std::fs::write( path, bytes ));
+	}
+
+}
@@ -0,0 +1,172 @@
+use std::collections::{ HashMap, HashSet };
+use thiserror::Error ;
+use wasmtime::{ Cache, Config, Engine, InstanceAllocationStrategy, PoolingAllocationConfig, Store };
+use wasmtime::component::{ Component, Linker, ResourceTable, Val };
+
+use crate::{ Binding, Function, FunctionKind, Interface, Plugin, PluginContext, ReturnKind };
+use crate::cardinality::ExactlyOne ;
+
+
+
+/// Builds an [`Engine`] exposing only the options `wasm_link` supports.
+///
+/// Wasmtime's [`Config`] offers many options that break dispatching, such as turning off
+/// the component model or the concurrency support asynchronous plugins rely on. The
+/// builder keeps those at their defaults and only exposes what is safe to change:
+/// fuel and epoch interruption for [resource limits]( crate#resource-limits ), the
+/// pooling allocator, the compilation cache and parallel compilation. Hosts needing
+/// other options can still create the engine from their own [`Config`].
+///
+/// ```
+/// use wasm_link::EngineBuilder ;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let engine = EngineBuilder::new()
+/// 	.with_fuel( true )
+/// 	.with_epoch_interruption( true )
+/// 	.build()?;
+/// # let _ = engine ;
+/// # Ok(()) }
+/// ```
+#[derive( Debug, Default )]
+pub struct EngineBuilder {
+	/// Whether calls consume fuel
+	fuel: bool,
+	/// Whether calls can be interrupted by epoch deadlines
+	epoch_interruption: bool,
+	/// Pooling allocator limits, if instances are allocated from a pool
+	pooling: Option<PoolingAllocationConfig>,
+	/// Cache of compiled components
+	cache: Option<Cache>,
+	/// Whether to compile functions in parallel, `None` keeping Wasmtime's default
+	parallel_compilation: Option<bool>,
+}
+
+impl EngineBuilder {
+
+	/// Creates a builder with Wasmtime's default options.
+	pub fn new() -> Self { Self::default() }
+
+	/// Enables fuel consumption, see [`Config::consume_fuel`].
+	pub fn with_fuel( mut self, enable: bool ) -> Self {
+		self.fuel = enable ;
+		self
+	}
+
+	/// Enables interrupting calls at epoch deadlines, see [`Config::epoch_interruption`].
+	pub fn with_epoch_interruption( mut self, enable: bool ) -> Self {
+		self.epoch_interruption = enable ;
+		self
+	}
+
+	/// Allocates instances from a pool with the given limits instead of on demand,
+	/// see [`InstanceAllocationStrategy::Pooling`].
+	pub fn with_pooling_allocator( mut self, limits: PoolingAllocationConfig ) -> Self {
+		self.pooling = Some( limits );
+		self
+	}
+
+	/// Caches compiled components, see [`Config::cache`].
+	pub fn with_cache( mut self, cache: Cache ) -> Self {
+		self.cache = Some( cache );
+		self
+	}
+
+	/// Sets whether functions are compiled in parallel, see [`Config::parallel_compilation`].
+	pub fn with_parallel_compilation( mut self, enable: bool ) -> Self {
+		self.parallel_compilation = Some( enable );
+		self
+	}
+
+	/// Creates the engine.
+	///
+	/// # Errors
+	/// Returns an error if Wasmtime rejects the configuration, e.g. because the pooling
+	/// allocator cannot reserve its memory.
+	pub fn build( self ) -> Result<Engine, wasmtime::Error> {
+		let mut config = Config::new();
+		config.consume_fuel( self.fuel ).epoch_interruption( self.epoch_interruption );
+		if let Some( limits ) = self.pooling { config.allocation_strategy( InstanceAllocationStrategy::Pooling( limits )); }
+		if let Some( cache ) = self.cache { config.cache( Some( cache )); }
+		if let Some( parallel ) = self.parallel_compilation { config.parallel_compilation( parallel ); }
+		Engine::new( &config )
+	}
+
+}
+
+/// A problem with an engine configuration found by [`verify_engine`].
+#[derive( Error, Debug, Clone, PartialEq, Eq )]
+pub enum CompatWarning {
+	/// Fuel consumption is disabled, so every call given fuel by a fuel limiter or an
+	/// interface default fails. Enable it with [`EngineBuilder::with_fuel`].
+	#[error( "Fuel Disabled: calls given fuel will fail" )] FuelDisabled,
+	/// Epoch interruption is disabled, so epoch deadlines and watchdogs never interrupt
+	/// calls. Enable it with [`EngineBuilder::with_epoch_interruption`].
+	#[error( "Epoch Interruption Disabled: epoch deadlines will be ignored" )] EpochInterruptionDisabled,
+	/// A probe plugin could not be compiled, instantiated or dispatched to.
+	#[error( "Dispatch Failed: {0}" )] DispatchFailed( String ),
+}
+
+/// Checks that `engine` can run `wasm_link` plugins and supports resource limits,
+/// returning a warning for each problem found.
+///
+/// Compiles a tiny probe component and dispatches to it, with and without an epoch
+/// deadline, so that misconfigured engines are reported at startup rather than by the
+/// first dispatch to a real plugin. An empty list means the engine is fully supported.
+///
+/// ```
+/// use wasm_link::{ CompatWarning, EngineBuilder, verify_engine };
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let engine = EngineBuilder::new().with_fuel( true ).build()?;
+/// assert_eq!( verify_engine( &engine ), vec![ CompatWarning::EpochInterruptionDisabled ]);
+/// # Ok(()) }
+/// ```
+pub fn verify_engine( engine: &Engine ) -> Vec<CompatWarning> {
+	let fuel = Store::new( engine, () ).set_fuel( 1 ).is_ok();
+	let mut warnings = Vec::new();
+	if !fuel { warnings.push( CompatWarning::FuelDisabled ); }
+	match probe( engine, fuel, 1_000_000 ) {
+		Ok( Val::U32( 42 )) => {},
+		Ok( value ) => return vec![ CompatWarning::DispatchFailed( format!( "unexpected result {value:?}" ))],
+		Err( err ) => return vec![ CompatWarning::DispatchFailed( err.to_string() )],
+	}
+	// A deadline of zero ticks interrupts the probe on entry, unless epochs are ignored
+	if probe( engine, fuel, 0 ).is_ok() { warnings.push( CompatWarning::EpochInterruptionDisabled ); }
+	warnings
+}
+
+const PROBE: &str = r#"(component
+	(core module $m (func (export "probe") (result i32) (i32.const 42)))
+	(core instance $i (instantiate $m))
+	(func $f (result u32) (canon lift (core func $i "probe")))
+	(instance $inst (export "probe" (func $f)))
+	(export "wasm-link:probe/probe" (instance $inst))
+)"#;
+
+struct ProbeContext { table: ResourceTable }
+
+impl PluginContext for ProbeContext {
+	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.table }
+}
+
+/// Dispatches to the probe component, giving the call `ticks` as its epoch deadline.
+fn probe( engine: &Engine, fuel: bool, ticks: u64 ) -> Result<Val, wasmtime::Error> {
+	let component = Component::new( engine, PROBE )?;
+	let mut plugin = Plugin::new( component, ProbeContext { table: ResourceTable::new() });
+	if fuel { plugin = plugin.with_initial_fuel( 1_000 ).with_fuel_limiter(| _, _, _, _ | 1_000 ); }
+	let instance = plugin.with_epoch_limiter( move | _, _, _, _ | ticks ).instantiate( engine, &Linker::new( engine ))?;
+	let binding: Binding<String, ProbeContext> = Binding::new(
+		"wasm-link:probe",
+		HashMap::from([( "probe".to_string(), Interface::new(
+			HashMap::from([( "probe".to_string(), Function::new( FunctionKind::Freestanding, ReturnKind::AssumeNoResources ))]),
+			HashSet::new(),
+		))]),
+		ExactlyOne( "probe".to_string(), instance ),
+	);
+	let ExactlyOne( _, result ) = binding.dispatch( "probe", "probe", &[] )?;
+	Ok( result? )
+}
+
+#[cfg(test)]
+mod tests { include!( "engine_tests.rs" ); }
@@ -0,0 +1,30 @@
+use wasmtime::{ PoolingAllocationConfig, Store };
+use super::{ CompatWarning, EngineBuilder, verify_engine };
+
+
+
+#[test]
+fn fuel_is_only_available_when_enabled() {
+	let engine = EngineBuilder::new().build().unwrap();
+	assert!( Store::new( &engine, () ).set_fuel( 1 ).is_err() );
+	let engine = EngineBuilder::new().with_fuel( true ).build().unwrap();
+	assert!( Store::new( &engine, () ).set_fuel( 1 ).is_ok() );
+}
+
+#[test]
+fn pooled_engines_instantiate_components() {
+	let mut limits = PoolingAllocationConfig::default();
+	limits.total_component_instances( 2 ).total_memories( 2 ).total_tables( 2 ).total_core_instances( 2 );
+	let engine = EngineBuilder::new().with_pooling_allocator( limits ).with_parallel_compilation( false ).build().unwrap();
+	let component = wasmtime::component::Component::new( &engine, "(component)" ).unwrap();
+	let linker = wasmtime::component::Linker::<()>::new( &engine );
+	assert!( linker.instantiate( &mut Store::new( &engine, () ), &component ).is_ok() );
+}
+
+#[test]
+fn verify_engine_reports_disabled_limits() {
+	let engine = EngineBuilder::new().build().unwrap();
+	assert_eq!( verify_engine( &engine ), vec![ CompatWarning::FuelDisabled, CompatWarning::EpochInterruptionDisabled ]);
+	let engine = EngineBuilder::new().with_fuel( true ).with_epoch_interruption( true ).build().unwrap();
+	assert_eq!( verify_engine( &engine ), Vec::new() );
+}
@@ -0,0 +1,93 @@
+//! Resolution of the interface paths a binding dispatches to onto the names a
+//! component actually exports them under.
+//!
+//! A plugin may export `my:pkg/api` as `my:pkg/api@1.2.0`, or nest it inside another
+//! exported instance. Exact top-level exports always win; otherwise the highest version
+//! exported at the top level is used, and only then an instance nested one level deep.
+
+use std::cmp::Ordering ;
+use std::collections::HashMap ;
+use wasmtime::Engine ;
+use wasmtime::component::types::{ self, ComponentItem };
+
+
+
+/// Export names to walk from the component root to reach an interface, for the
+/// interface paths not exported under their own name.
+pub(crate) type ExportPaths = HashMap<String, Vec<String>>;
+
+/// Finds where `component` exports the interfaces it does not export under their plain
+/// `package/interface` path.
+pub(crate) fn resolve( component: &types::Component, engine: &Engine ) -> ExportPaths {
+
+	let mut top_level = Vec::new();
+	let mut nested = Vec::new();
+	component.exports( engine ).for_each(|( name, item )| {
+		let ComponentItem::ComponentInstance( instance ) = item.ty else { return };
+		instance.exports( engine )
+			.filter(|( _, item )| matches!( item.ty, ComponentItem::ComponentInstance( _ )))
+			.for_each(|( inner, _ )| nested.push( vec![ name.to_string(), inner.to_string() ]));
+		top_level.push( name.to_string() );
+	});
+
+	let mut paths = ExportPaths::new();
+	// Plain nested names take precedence over versioned nested names
+	nested.sort_by_key(| path | path[1].contains( '@' ));
+	for path in nested {
+		paths.entry( unversioned( &path[1] ).to_string() ).or_insert( path );
+	}
+
+	let mut versioned = top_level.iter()
+		.filter_map(| name | name.split_once( '@' ).map(|( base, version )| ( base, version, name )))
+		.collect::<Vec<_>>();
+	// Ascending, so the highest version is inserted last
+	versioned.sort_by(|( _, a, _ ), ( _, b, _ )| compare_versions( a, b ));
+	for ( base, version, name ) in versioned {
+		paths.insert( base.to_string(), vec![ name.clone() ]);
+		// Packages may also be requested with the version attached to the package name
+		if let Some(( package, interface )) = base.split_once( '/' ) {
+			paths.insert( format!( "{package}@{version}/{interface}" ), vec![ name.clone() ]);
+		}
+	}
+
+	paths.retain(| requested, _ | !top_level.contains( requested ));
+	paths
+
+}
+
+/// Strips the version from an export name such as `my:pkg/api@1.0.0`.
+pub(crate) fn unversioned( name: &str ) -> &str {
+	name.split_once( '@' ).map_or( name, |( base, _ )| base )
+}
+
+/// Orders semantic versions by precedence, ignoring build metadata. Versions that do
+/// not parse are ordered lexically after the rest.
+fn compare_versions( a: &str, b: &str ) -> Ordering {
+	fn parse( version: &str ) -> Option<( [u64; 3], Option<&str> )> {
+		let version = version.split_once( '+' ).map_or( version, |( version, _ )| version );
+		let ( core, pre ) = match version.split_once( '-' ) {
+			Some(( core, pre )) => ( core, Some( pre )),
+			None => ( version, None ),
+		};
+		let mut parts = core.split( '.' ).map( str::parse::<u64> );
+		let numbers = [ parts.next()?.ok()?, parts.next()?.ok()?, parts.next()?.ok()? ];
+		match parts.next() {
+			None => Some(( numbers, pre )),
+			Some( _ ) => None,
+		}
+	}
+	match ( parse( a ), parse( b )) {
+		( Some(( a_core, a_pre )), Some(( b_core, b_pre ))) => a_core.cmp( &b_core ).then_with(|| match ( a_pre, b_pre ) {
+			( None, None ) => Ordering::Equal,
+			( None, Some( _ )) => Ordering::Greater,
+			( Some( _ ), None ) => Ordering::Less,
+			( Some( a_pre ), Some( b_pre )) => a_pre.cmp( b_pre ),
+		}),
+		( Some( _ ), None ) => Ordering::Less,
+		( None, Some( _ )) => Ordering::Greater,
+		( None, None ) => a.cmp( b ),
+	}
+}
+
+#[cfg(test)]
+mod tests { include!( "export_path_tests.rs" ); }
@@ -0,0 +1,32 @@
+use std::cmp::Ordering ;
+use super::{ compare_versions, resolve, unversioned };
+
+
+
+#[test]
+fn versions_are_ordered_by_precedence() {
+	assert_eq!( compare_versions( "1.10.0", "1.9.0" ), Ordering::Greater );
+	assert_eq!( compare_versions( "1.0.0-alpha", "1.0.0" ), Ordering::Less );
+	assert_eq!( compare_versions( "1.0.0+build", "1.0.0" ), Ordering::Equal );
+	assert_eq!( compare_versions( "latest", "2.0.0" ), Ordering::Greater );
+}
+
+#[test]
+fn unversioned_strips_the_version() {
+	assert_eq!( unversioned( "my:pkg/api@1.0.0" ), "my:pkg/api" );
+	assert_eq!( unversioned( "my:pkg/api" ), "my:pkg/api" );
+}
+
+#[test]
+fn exact_exports_win_over_versioned_and_nested_ones() {
+	let engine = wasmtime::Engine::default();
+	let component = wasmtime::component::Component::from_file(
+		&engine,
+		concat!( env!( "CARGO_MANIFEST_DIR" ), "/tests/export_path/exports.wat" ),
+	).expect( "Failed to compile component" );
+	let paths = resolve( &component.component_type(), &engine );
+	assert_eq!( paths.get( "my:pkg/api" ), None );
+	assert_eq!( paths.get( "my:pkg/versioned" ), Some( &vec![ "my:pkg/versioned@1.10.0".to_string() ]));
+	assert_eq!( paths.get( "my:pkg@1.2.0/versioned" ), Some( &vec![ "my:pkg/versioned@1.2.0".to_string() ]));
+	assert_eq!( paths.get( "my:pkg/nested" ), Some( &vec![ "outer".to_string(), "my:pkg/nested".to_string() ]));
+}
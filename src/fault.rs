@@ -0,0 +1,144 @@
+//! Fault injection for chaos testing.
+//!
+//! A [`FaultInjector`] installed with [`Plugin::with_fault_injector`]( crate::Plugin::with_fault_injector )
+//! makes the dispatch layer misbehave on purpose at a configurable rate, so hosts can
+//! verify their resilience logic against plugin failures without writing faulty plugins.
+//!
+//! Injectors are set per plugin. Every instance belongs to exactly one binding, so
+//! giving each plugin of a binding an injector makes the whole binding misbehave,
+//! while still allowing healthy and faulty plugins to be mixed.
+
+use std::sync::Arc ;
+use std::time::Duration ;
+use wasmtime::component::Val ;
+
+use crate::CallInfo ;
+
+
+
+/// Chooses the value replacing the result of a call, or `None` to leave it intact.
+type Corruption = Arc<dyn Fn( &CallInfo<'_> ) -> Option<Val> + Send + Sync> ;
+
+/// A failure injected into a single dispatch.
+#[derive( Clone )]
+pub enum Fault {
+	/// Fails the call with [`DispatchError::LockRejected`]( crate::DispatchError::LockRejected )
+	/// without invoking the plugin.
+	LockRejected,
+	/// Fails the call with a [`DispatchError::RuntimeException`]( crate::DispatchError::RuntimeException )
+	/// carrying the given message without invoking the plugin.
+	Trap( String ),
+	/// Delays the call by the given duration before invoking the plugin.
+	///
+	/// Instances created by [`Plugin::instantiate`]( crate::Plugin::instantiate ) block
+	/// the thread driving the plugin's store; async instances wait without blocking
+	/// their executor.
+	Latency( Duration ),
+	/// Invokes the plugin, then replaces its return value with the one chosen for the
+	/// call, if any. See [`Fault::corrupt`].
+	Corrupt( Corruption ),
+}
+
+impl Fault {
+
+	/// Creates a [`Fault::Corrupt`] choosing the replacement value from the call.
+	///
+	/// The value must be valid for the WIT return type of the called function, so
+	/// `corrupt` should return `None` for functions it has no well-typed value for.
+	/// Ignored for functions returning [`ReturnKind::Void`]( crate::ReturnKind::Void ).
+	///
+	/// ```
+	/// use wasm_link::{ Fault, Val };
+	///
+	/// let fault = Fault::corrupt(| call | match call.function_name() {
+	/// 	"get-count" => Some( Val::U32( u32::MAX )),
+	/// 	_ => None,
+	/// });
+	/// # let _ = fault ;
+	/// ```
+	pub fn corrupt( corrupt: impl Fn( &CallInfo<'_> ) -> Option<Val> + Send + Sync + 'static ) -> Self {
+		Self::Corrupt( Arc::new( corrupt ))
+	}
+
+}
+
+impl std::fmt::Debug for Fault {
+	fn fmt( &self, f: &mut std::fmt::Formatter<'_> ) -> std::fmt::Result {
+		match self {
+			Self::LockRejected => f.write_str( "LockRejected" ),
+			Self::Trap( message ) => f.debug_tuple( "Trap" ).field( message ).finish(),
+			Self::Latency( delay ) => f.debug_tuple( "Latency" ).field( delay ).finish(),
+			Self::Corrupt( _ ) => f.debug_tuple( "Corrupt" ).finish_non_exhaustive(),
+		}
+	}
+}
+
+/// Injects [`Fault`]s into a plugin's dispatches at a configurable rate.
+///
+/// Faults are drawn from a seeded pseudo-random sequence, so a failing chaos test
+/// can be reproduced by reusing its seed.
+///
+/// ```
+/// use std::time::Duration ;
+/// use wasm_link::{ Fault, FaultInjector };
+///
+/// // Roughly one in ten calls is rejected or delayed.
+/// let injector = FaultInjector::new( 0xC0FFEE, 0.1, vec![
+/// 	Fault::LockRejected,
+/// 	Fault::Latency( Duration::from_millis( 50 )),
+/// ]);
+/// # let _ = injector ;
+/// ```
+#[derive( Debug, Clone )]
+pub struct FaultInjector {
+	/// Probability in `0.0..=1.0` that a call receives a fault
+	rate: f64,
+	/// Faults to choose from, uniformly
+	faults: Vec<Fault>,
+	/// `SplitMix64` state
+	state: u64,
+}
+
+impl FaultInjector {
+
+	/// Creates an injector that faults each call with probability `rate`,
+	/// choosing uniformly among `faults`.
+	///
+	/// `rate` is clamped to `0.0..=1.0`. An empty `faults` list never injects anything.
+	pub fn new( seed: u64, rate: f64, faults: Vec<Fault> ) -> Self {
+		Self { rate: rate.clamp( 0.0, 1.0 ), faults, state: seed }
+	}
+
+	/// Decides the fault, if any, for the next call.
+	pub(crate) fn next_fault( &mut self ) -> Option<Fault> {
+		if self.faults.is_empty() { return None }
+		#[allow( clippy::cast_precision_loss )]
+		let roll = ( self.next_u64() >> 11 ) as f64 / ( 1_u64 << 53 ) as f64 ;
+		if roll >= self.rate { return None }
+		#[allow( clippy::cast_possible_truncation )]
+		let index = ( self.next_u64() % self.faults.len() as u64 ) as usize ;
+		Some( self.faults[index].clone() )
+	}
+
+	fn next_u64( &mut self ) -> u64 {
+		self.state = self.state.wrapping_add( 0x9E37_79B9_7F4A_7C15 );
+		let mut z = self.state ;
+		z = ( z ^ ( z >> 30 )).wrapping_mul( 0xBF58_476D_1CE4_E5B9 );
+		z = ( z ^ ( z >> 27 )).wrapping_mul( 0x94D0_49BB_1331_11EB );
+		z ^ ( z >> 31 )
+	}
+
+}
+
+/// Resolves after `duration` without blocking the executor, timed by a helper thread.
+pub(crate) async fn delay( duration: Duration ) {
+	let ( sender, receiver ) = futures::channel::oneshot::channel();
+	std::thread::spawn( move || {
+		std::thread::sleep( duration );
+		let _ = sender.send(());
+	});
+	let _ = receiver.await ;
+}
+
+#[cfg(test)]
+mod tests { include!( "fault_tests.rs" ); }
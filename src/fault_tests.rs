@@ -0,0 +1,35 @@
+use super::{ Fault, FaultInjector };
+
+
+
+#[test]
+fn zero_rate_never_injects() {
+	let mut injector = FaultInjector::new( 7, 0.0, vec![ Fault::LockRejected ]);
+	assert!(( 0..1000 ).all(| _ | injector.next_fault().is_none() ));
+}
+
+#[test]
+fn full_rate_always_injects() {
+	let mut injector = FaultInjector::new( 7, 1.0, vec![ Fault::LockRejected ]);
+	assert!(( 0..1000 ).all(| _ | matches!( injector.next_fault(), Some( Fault::LockRejected ))));
+}
+
+#[test]
+fn empty_fault_list_never_injects() {
+	let mut injector = FaultInjector::new( 7, 1.0, Vec::new() );
+	assert!( injector.next_fault().is_none() );
+}
+
+#[test]
+fn rate_is_approximated_and_reproducible() {
+	let faults = vec![ Fault::LockRejected, Fault::Trap( "boom".to_string() )];
+	let mut first = FaultInjector::new( 42, 0.25, faults.clone() );
+	let mut second = FaultInjector::new( 42, 0.25, faults );
+	let first_run = ( 0..10_000 ).map(| _ | first.next_fault().map(| fault | format!( "{fault:?}" ))).collect::<Vec<_>>();
+	let second_run = ( 0..10_000 ).map(| _ | second.next_fault().map(| fault | format!( "{fault:?}" ))).collect::<Vec<_>>();
+	assert_eq!( first_run, second_run );
+	let injected = first_run.iter().filter(| fault | fault.is_some() ).count();
+	assert!(( 2_000..3_000 ).contains( &injected ), "injected {injected} faults out of 10000" );
+	assert!( first_run.iter().any(| fault | fault.as_deref() == Some( "LockRejected" )));
+	assert!( first_run.iter().any(| fault | fault.as_deref() == Some( "Trap(\"boom\")" )));
+}
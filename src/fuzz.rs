@@ -0,0 +1,162 @@
+//! Well-typed [`Val`] generation for property-based testing and fuzzing.
+//!
+//! Available with the `arbitrary` feature. A [`ValGenerator`] turns raw fuzzer
+//! input ([`arbitrary::Unstructured`]) into values matching a component model
+//! [`Type`], or into a full argument list for a [`ComponentFunc`]. This makes it
+//! possible to drive [`Binding::dispatch`]( crate::Binding::dispatch ) with
+//! arbitrary but valid inputs derived from the WIT signature of the target function.
+//!
+//! ```
+//! use arbitrary::Unstructured ;
+//! use wasm_link::{ Component, Engine };
+//! use wasm_link::fuzz::ValGenerator ;
+//! use wasmtime::component::types::ComponentItem ;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let engine = Engine::default();
+//! let component = Component::new( &engine, r#"(component
+//! 	(core module $m (func (export "f") (param i32 i32)))
+//! 	(core instance $i (instantiate $m))
+//! 	(func (export "f") (param "a" u32) (param "b" bool) (canon lift (core func $i "f")))
+//! )"# )?;
+//! let Some( ComponentItem::ComponentFunc( function )) = component.component_type().get_export( &engine, "f" ).map(| export | export.ty )
+//! 	else { panic!( "missing export" ) };
+//!
+//! let mut input = Unstructured::new( &[ 1, 2, 3, 4, 5, 6, 7, 8 ]);
+//! let args = ValGenerator::new().args( &mut input, &function )?;
+//! assert_eq!( args.len(), 2 );
+//! # Ok(())
+//! # }
+//! ```
+
+use arbitrary::{ Unstructured, Error, Result };
+use wasmtime::component::{ ResourceAny, Val };
+use wasmtime::component::types::{ ComponentFunc, ResourceType, Type };
+
+
+
+/// Generates well-typed [`Val`]s from fuzzer input.
+///
+/// Resource handles cannot be conjured from raw bytes since they must belong to a
+/// store. Provide a resource source with [`with_resources`](Self::with_resources)
+/// to generate values containing `own` or `borrow` handles; without one, such types
+/// fail with [`arbitrary::Error::IncorrectFormat`]. Async types (`future`, `stream`,
+/// `error-context`) always fail the same way.
+pub struct ValGenerator<'a> {
+	/// Upper bound on the length of generated lists and maps
+	max_len: usize,
+	/// Source of resource handles for `own` and `borrow` types
+	#[allow( clippy::type_complexity )]
+	resources: Option<Box<dyn FnMut( &ResourceType ) -> ResourceAny + 'a>>,
+}
+
+impl Default for ValGenerator<'_> {
+	fn default() -> Self { Self::new() }
+}
+
+impl std::fmt::Debug for ValGenerator<'_> {
+	fn fmt( &self, f: &mut std::fmt::Formatter<'_> ) -> std::fmt::Result {
+		f.debug_struct( "ValGenerator" )
+			.field( "max_len", &self.max_len )
+			.field( "resources", &self.resources.as_ref().map(| _ | "<closure>" ))
+			.finish()
+	}
+}
+
+impl<'a> ValGenerator<'a> {
+
+	/// Default upper bound on the length of generated lists and maps.
+	pub const DEFAULT_MAX_LEN: usize = 8 ;
+
+	/// Creates a generator without a resource source.
+	pub fn new() -> Self {
+		Self { max_len: Self::DEFAULT_MAX_LEN, resources: None }
+	}
+
+	/// Sets the upper bound on the length of generated lists and maps.
+	pub fn with_max_len( mut self, max_len: usize ) -> Self {
+		self.max_len = max_len ;
+		self
+	}
+
+	/// Sets a closure that provides a resource handle whenever an `own` or `borrow`
+	/// type is generated.
+	pub fn with_resources( mut self, resources: impl FnMut( &ResourceType ) -> ResourceAny + 'a ) -> Self {
+		self.resources = Some( Box::new( resources ));
+		self
+	}
+
+	/// Generates an argument list matching the parameters of `function`.
+	///
+	/// # Errors
+	/// Returns an error if the input is exhausted or a parameter type cannot be generated.
+	pub fn args( &mut self, u: &mut Unstructured<'_>, function: &ComponentFunc ) -> Result<Vec<Val>> {
+		function.params().map(|( _, ty )| self.val( u, &ty )).collect()
+	}
+
+	/// Generates a value of type `ty`.
+	///
+	/// # Errors
+	/// Returns an error if the input is exhausted or the type cannot be generated.
+	pub fn val( &mut self, u: &mut Unstructured<'_>, ty: &Type ) -> Result<Val> {
+		Ok( match ty {
+			Type::Bool => Val::Bool( u.arbitrary()? ),
+			Type::S8 => Val::S8( u.arbitrary()? ),
+			Type::U8 => Val::U8( u.arbitrary()? ),
+			Type::S16 => Val::S16( u.arbitrary()? ),
+			Type::U16 => Val::U16( u.arbitrary()? ),
+			Type::S32 => Val::S32( u.arbitrary()? ),
+			Type::U32 => Val::U32( u.arbitrary()? ),
+			Type::S64 => Val::S64( u.arbitrary()? ),
+			Type::U64 => Val::U64( u.arbitrary()? ),
+			Type::Float32 => Val::Float32( u.arbitrary()? ),
+			Type::Float64 => Val::Float64( u.arbitrary()? ),
+			Type::Char => Val::Char( u.arbitrary()? ),
+			Type::String => Val::String( u.arbitrary()? ),
+			Type::List( list ) => {
+				let len = u.int_in_range( 0..=self.max_len )?;
+				Val::List(( 0..len ).map(| _ | self.val( u, &list.ty() )).collect::<Result<_>>()? )
+			}
+			Type::Map( map ) => {
+				let len = u.int_in_range( 0..=self.max_len )?;
+				Val::Map(( 0..len ).try_fold( Vec::with_capacity( len ), | mut entries: Vec<( Val, Val )>, _ | {
+					let key = self.val( u, &map.key() )?;
+					let value = self.val( u, &map.value() )?;
+					if !entries.iter().any(|( existing, _ )| existing == &key ) { entries.push(( key, value )); }
+					Ok( entries )
+				})? )
+			}
+			Type::Record( record ) => Val::Record( record.fields()
+				.map(| field | Ok(( field.name.to_string(), self.val( u, &field.ty )? )))
+				.collect::<Result<_>>()?
+			),
+			Type::Tuple( tuple ) => Val::Tuple( tuple.types().map(| ty | self.val( u, &ty )).collect::<Result<_>>()? ),
+			Type::Variant( variant ) => {
+				let index = u.choose_index( variant.cases().len() )?;
+				let case = variant.cases().nth( index ).ok_or( Error::EmptyChoose )?;
+				let payload = case.ty.map(| ty | self.val( u, &ty ).map( Box::new )).transpose()?;
+				Val::Variant( case.name.to_string(), payload )
+			}
+			Type::Enum( enum_type ) => Val::Enum(( *u.choose( &enum_type.names().collect::<Vec<_>>() )? ).to_string() ),
+			Type::Option( option ) => match u.arbitrary()? {
+				true => Val::Option( Some( Box::new( self.val( u, &option.ty() )? ))),
+				false => Val::Option( None ),
+			},
+			Type::Result( result ) => match u.arbitrary()? {
+				true => Val::Result( Ok( result.ok().map(| ty | self.val( u, &ty ).map( Box::new )).transpose()? )),
+				false => Val::Result( Err( result.err().map(| ty | self.val( u, &ty ).map( Box::new )).transpose()? )),
+			},
+			Type::Flags( flags ) => Val::Flags( flags.names()
+				.map(| name | Ok( u.arbitrary::<bool>()?.then(|| name.to_string() )))
+				.collect::<Result<Vec<_>>>()?
+				.into_iter().flatten().collect()
+			),
+			Type::Own( resource ) | Type::Borrow( resource ) => match self.resources.as_mut() {
+				Some( resources ) => Val::Resource( resources( resource )),
+				None => return Err( Error::IncorrectFormat ),
+			},
+			Type::Future( _ ) | Type::Stream( _ ) | Type::ErrorContext => return Err( Error::IncorrectFormat ),
+		})
+	}
+
+}
@@ -1,10 +1,11 @@
 use std::sync::Arc ;
 use std::collections::{ HashMap, HashSet };
-use futures::lock::Mutex ;
 use wasmtime::component::{ Linker, ResourceType, Val };
 
 use crate::{ Binding, PluginContext, PluginInstanceAsync, PluginInstanceSync };
 use crate::cardinality::Cardinality ;
+use crate::conformance::TestVector ;
+use crate::plugin_lock::PluginLock ;
 use crate::linker::{
 	dispatch_all,
 	dispatch_all_async,
@@ -12,6 +13,9 @@ use crate::linker::{
 	dispatch_method,
 	dispatch_method_async,
 	dispatch_method_async_blocking,
+	dispatch_targeted,
+	dispatch_targeted_async,
+	dispatch_targeted_async_blocking,
 };
 use crate::resource_wrapper::ResourceWrapper ;
 
@@ -40,26 +44,131 @@ use crate::resource_wrapper::ResourceWrapper ;
 /// );
 /// # let _ = binding;
 /// ```
-#[derive( Debug, Clone, Default )]
+///
+/// Interfaces can also be declared item by item with [`Interface::builder`].
+#[derive( Debug, Clone, Default, PartialEq, Eq )]
+#[cfg_attr( feature = "serde", derive( serde::Serialize, serde::Deserialize ))]
 pub struct Interface {
 	/// Functions exported by this interface
+	#[cfg_attr( feature = "serde", serde( default, serialize_with = "crate::snapshot::sorted_map" ))]
 	functions: HashMap<String, Function>,
 	/// Resource types defined by this interface
+	#[cfg_attr( feature = "serde", serde( default, serialize_with = "crate::snapshot::sorted_set" ))]
 	resources: HashSet<String>,
+	/// Test vectors plugins implementing this interface must pass
+	#[cfg_attr( feature = "serde", serde( default, skip_serializing_if = "Vec::is_empty" ))]
+	test_vectors: Vec<TestVector>,
+	/// Fuel given to calls of the functions when the plugin has no fuel limiter
+	#[cfg_attr( feature = "serde", serde( default, skip_serializing_if = "Option::is_none" ))]
+	default_fuel: Option<u64>,
+	/// Epoch deadline given to calls of the functions when the plugin has no epoch limiter
+	#[cfg_attr( feature = "serde", serde( default, skip_serializing_if = "Option::is_none" ))]
+	default_epoch_deadline: Option<u64>,
 }
 
 impl Interface {
+	/// Starts declaring an interface item by item.
+	///
+	/// ```
+	/// use wasm_link::{ Interface, ReturnKind };
+	///
+	/// let interface = Interface::builder()
+	/// 	.resource( "counter" )
+	/// 	.function( "make-counter", ReturnKind::MayContainResources )
+	/// 	.method( "[method]counter.get-value", ReturnKind::AssumeNoResources )
+	/// 	.build();
+	/// assert_eq!( interface.functions().count(), 2 );
+	/// ```
+	pub fn builder() -> InterfaceBuilder { InterfaceBuilder::default() }
+
 	/// Creates a new interface declaration.
 	pub fn new(
 		functions: HashMap<String, Function>,
 		resources: HashSet<String>,
 	) -> Self {
-		Self { functions, resources }
+		Self { functions, resources, test_vectors: Vec::new(), default_fuel: None, default_epoch_deadline: None }
+	}
+
+	/// Sets the fuel given to calls of this interface's functions when the called
+	/// plugin has no fuel limiter, including functions declared afterwards.
+	///
+	/// A limiter set with [`Plugin::with_fuel_limiter`]( crate::Plugin::with_fuel_limiter )
+	/// always takes precedence; it can read the default through
+	/// [`Function::default_fuel`]. Use this when a whole interface is expensive
+	/// and every plugin implementing it should get the same budget.
+	///
+	/// **Warning:** Fuel consumption must be enabled in the [`Engine`]( wasmtime::Engine )
+	/// via [`Config::consume_fuel`]( wasmtime::Config::consume_fuel ). If not enabled,
+	/// dispatch will fail with a [`RuntimeException`]( crate::DispatchError::RuntimeException ).
+	///
+	/// ```
+	/// # use wasm_link::{ Interface, InterfaceBuilder, ReturnKind };
+	/// let interface = Interface::builder()
+	/// 	.function( "render", ReturnKind::Void )
+	/// 	.build()
+	/// 	.with_default_fuel( 5_000_000 )
+	/// 	.with_default_epoch_deadline( 10 );
+	/// let interface = InterfaceBuilder::from( interface )
+	/// 	.function( "layout", ReturnKind::Void )
+	/// 	.build();
+	/// assert!( interface.functions().all(|( _, function )| function.default_fuel() == Some( 5_000_000 )));
+	/// ```
+	pub fn with_default_fuel( mut self, fuel: u64 ) -> Self {
+		self.default_fuel = Some( fuel );
+		self
+	}
+
+	/// Sets the epoch deadline, in ticks, given to calls of this interface's functions
+	/// when the called plugin has no epoch limiter, including functions declared
+	/// afterwards.
+	///
+	/// A limiter set with [`Plugin::with_epoch_limiter`]( crate::Plugin::with_epoch_limiter )
+	/// always takes precedence; it can read the default through
+	/// [`Function::default_epoch_deadline`].
+	///
+	/// **Warning:** Epoch interruption must be enabled in the [`Engine`]( wasmtime::Engine )
+	/// via [`Config::epoch_interruption`]( wasmtime::Config::epoch_interruption ). If not
+	/// enabled, the deadline is silently ignored.
+	pub fn with_default_epoch_deadline( mut self, ticks: u64 ) -> Self {
+		self.default_epoch_deadline = Some( ticks );
+		self
 	}
 
+	/// Declares test vectors that plugins implementing this interface must pass.
+	///
+	/// Vectors are run by [`Binding::run_conformance`]( crate::Binding::run_conformance ).
+	pub fn with_test_vectors( mut self, vectors: impl IntoIterator<Item = TestVector> ) -> Self {
+		self.test_vectors.extend( vectors );
+		self
+	}
+
+	/// Returns the test vectors declared with [`with_test_vectors`](Self::with_test_vectors).
+	pub fn test_vectors( &self ) -> &[TestVector] { &self.test_vectors }
+
+	/// Returns the functions of this interface with their metadata, in no particular order.
+	///
+	/// The metadata carries the interface's default limits.
+	pub fn functions( &self ) -> impl Iterator<Item = ( &str, Function )> {
+		self.functions.iter().map(|( name, function )| ( name.as_str(), self.with_defaults( function )))
+	}
+
+	/// Returns the metadata of the function `name`, carrying the interface's default limits.
 	#[inline]
-	pub(crate) fn function( &self, name: &str ) -> Option<&Function> {
-		self.functions.get( name )
+	pub(crate) fn function( &self, name: &str ) -> Option<Function> {
+		self.functions.get( name ).map(| function | self.with_defaults( function ))
+	}
+
+	fn with_defaults( &self, function: &Function ) -> Function {
+		Function {
+			default_fuel: self.default_fuel,
+			default_epoch_deadline: self.default_epoch_deadline,
+			..function.clone()
+		}
+	}
+
+	#[inline]
+	pub(crate) fn has_resource( &self, name: &str ) -> bool {
+		self.resources.contains( name )
 	}
 
 	#[inline]
@@ -69,27 +178,30 @@ impl Interface {
 		package_name: &str,
 		interface_ident: &str,
 		interface_name: &str,
+		imported: &HashSet<String>,
 		binding: &Binding<PluginId, Ctx, Plugins, PluginInstanceSync<Ctx>>,
 	) -> Result<(), wasmtime::Error>
 	where
 		PluginId: std::hash::Hash + Eq + Clone + Send + Sync + Into<Val> + 'static,
 		Ctx: PluginContext,
 		Plugins: Cardinality<PluginId, PluginInstanceSync<Ctx>> + 'static,
-		<Plugins as Cardinality<PluginId, PluginInstanceSync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceSync<Ctx>>>>: Send + Sync,
-		<Plugins as Cardinality<PluginId, PluginInstanceSync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceSync<Ctx>>>>: Cardinality<PluginId, Arc<Mutex<PluginInstanceSync<Ctx>>>>,
-		<<Plugins as Cardinality<PluginId, PluginInstanceSync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceSync<Ctx>>>> as Cardinality<PluginId, Arc<Mutex<PluginInstanceSync<Ctx>>>>>::Rebind<Val>: Into<Val>,
+		<Plugins as Cardinality<PluginId, PluginInstanceSync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceSync<Ctx>>>>: Send + Sync,
+		<Plugins as Cardinality<PluginId, PluginInstanceSync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceSync<Ctx>>>>: Cardinality<PluginId, Arc<PluginLock<PluginInstanceSync<Ctx>>>>,
+		<<Plugins as Cardinality<PluginId, PluginInstanceSync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceSync<Ctx>>>> as Cardinality<PluginId, Arc<PluginLock<PluginInstanceSync<Ctx>>>>>::Rebind<Val>: Into<Val>,
 	{
 		let mut linker_root = linker.root();
 		let mut linker_instance = linker_root.instance( interface_ident )?;
 
-		self.functions.iter().try_for_each(|( name, metadata )| {
+		self.functions.iter().filter(|( name, _ )| imported.contains( *name )).try_for_each(|( name, metadata )| {
 
 			let package_name_clone = package_name.to_string();
 			let interface_name_clone = interface_name.to_string();
 			let binding_clone = binding.clone();
 			let name_clone = name.clone();
-			let metadata_clone = metadata.clone();
+			let metadata_clone = self.with_defaults( metadata );
 
+			// `socket_shape::check` rejects imports without exactly one result before
+			// the plugin is instantiated, so `results[0]` always exists
 			macro_rules! link {( $dispatch: expr ) => {
 				linker_instance.func_new( name, move | ctx, _ty, args, results | Ok(
 					results[0] = $dispatch( &binding_clone, ctx, &package_name_clone, &interface_name_clone, &name_clone, &metadata_clone, args )
@@ -99,11 +211,12 @@ impl Interface {
 			match metadata.kind() {
 				FunctionKind::Freestanding => link!( dispatch_all ),
 				FunctionKind::Method => link!( dispatch_method ),
+				FunctionKind::Targeted => link!( dispatch_targeted ),
 			}
 
 		})?;
 
-		self.resources.iter().try_for_each(| resource | linker_instance
+		self.resources.intersection( imported ).try_for_each(| resource | linker_instance
 			.resource( resource.as_str(), ResourceType::host::<Arc<ResourceWrapper<PluginId>>>(), ResourceWrapper::<PluginId>::drop )
 		)?;
 
@@ -118,25 +231,26 @@ impl Interface {
 		package_name: &str,
 		interface_ident: &str,
 		interface_name: &str,
+		imported: &HashSet<String>,
 		binding: &Binding<PluginId, Ctx, Plugins, PluginInstanceAsync<Ctx>>,
 	) -> Result<(), wasmtime::Error>
 	where
 		PluginId: std::hash::Hash + Eq + Clone + Send + Sync + Into<Val> + 'static,
 		Ctx: PluginContext,
 		Plugins: Cardinality<PluginId, PluginInstanceAsync<Ctx>> + 'static,
-		<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceAsync<Ctx>>>>: Send + Sync,
-		<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceAsync<Ctx>>>>: Cardinality<PluginId, Arc<Mutex<PluginInstanceAsync<Ctx>>>>,
-		<<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceAsync<Ctx>>>> as Cardinality<PluginId, Arc<Mutex<PluginInstanceAsync<Ctx>>>>>::Rebind<Val>: Into<Val> + Send,
+		<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceAsync<Ctx>>>>: Send + Sync,
+		<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceAsync<Ctx>>>>: Cardinality<PluginId, Arc<PluginLock<PluginInstanceAsync<Ctx>>>>,
+		<<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceAsync<Ctx>>>> as Cardinality<PluginId, Arc<PluginLock<PluginInstanceAsync<Ctx>>>>>::Rebind<Val>: Into<Val> + Send,
 	{
 		let mut linker_root = linker.root();
 		let mut linker_instance = linker_root.instance( interface_ident )?;
 
-		self.functions.iter().try_for_each(|( name, metadata )| {
+		self.functions.iter().filter(|( name, _ )| imported.contains( *name )).try_for_each(|( name, metadata )| {
 			let package_name = package_name.to_string();
 			let interface_name = interface_name.to_string();
 			let binding = binding.clone();
 			let function_name = name.clone();
-			let function = metadata.clone();
+			let function = self.with_defaults( metadata );
 
 			macro_rules! link_concurrent {( $dispatch: expr ) => {
 				linker_instance.func_new_concurrent( name, move | ctx, _ty, args, results | {
@@ -173,43 +287,115 @@ impl Interface {
 			match ( metadata.is_async(), metadata.kind() ) {
 				( true, FunctionKind::Freestanding ) => link_concurrent!( dispatch_all_async ),
 				( true, FunctionKind::Method ) => link_concurrent!( dispatch_method_async ),
+				( true, FunctionKind::Targeted ) => link_concurrent!( dispatch_targeted_async ),
 				( false, FunctionKind::Freestanding ) => link_blocking!( dispatch_all_async_blocking ),
 				( false, FunctionKind::Method ) => link_blocking!( dispatch_method_async_blocking ),
+				( false, FunctionKind::Targeted ) => link_blocking!( dispatch_targeted_async_blocking ),
 			}
 		})?;
 
-		self.resources.iter().try_for_each(| resource | linker_instance.resource( resource.as_str(), ResourceType::host::<Arc<ResourceWrapper<PluginId>>>(), ResourceWrapper::<PluginId>::drop ))?;
+		self.resources.intersection( imported ).try_for_each(| resource | linker_instance.resource( resource.as_str(), ResourceType::host::<Arc<ResourceWrapper<PluginId>>>(), ResourceWrapper::<PluginId>::drop ))?;
 
 		Ok(())
 	}
 
 }
 
-/// Denotes whether a function is freestanding or a resource method.
-/// Constructors are treated as freestanding functions.
+/// Declares an [`Interface`] item by item, created with [`Interface::builder`].
+///
+/// Converting an existing interface into a builder declares further items on top of it.
+#[derive( Debug, Clone, Default )]
+pub struct InterfaceBuilder( Interface );
+
+impl InterfaceBuilder {
+
+	/// Declares a synchronous [`Freestanding`]( FunctionKind::Freestanding ) function.
+	pub fn function( self, name: impl Into<String>, return_kind: ReturnKind ) -> Self {
+		self.declare( name, Function::new( FunctionKind::Freestanding, return_kind ))
+	}
+
+	/// Declares a synchronous resource [`Method`]( FunctionKind::Method ).
+	///
+	/// The return kind has no default: it also tells dispatch whether to expect a result
+	/// at all, so neither [`Void`]( ReturnKind::Void ) nor
+	/// [`MayContainResources`]( ReturnKind::MayContainResources ) is safe to assume.
+	pub fn method( self, name: impl Into<String>, return_kind: ReturnKind ) -> Self {
+		self.declare( name, Function::new( FunctionKind::Method, return_kind ))
+	}
+
+	/// Declares a function with custom metadata, e.g. an async or concurrent one,
+	/// replacing any earlier declaration of the same name.
+	pub fn declare( mut self, name: impl Into<String>, function: Function ) -> Self {
+		self.0.functions.insert( name.into(), function );
+		self
+	}
+
+	/// Declares a resource type.
+	pub fn resource( mut self, name: impl Into<String> ) -> Self {
+		self.0.resources.insert( name.into() );
+		self
+	}
+
+	/// Returns the declared interface.
+	pub fn build( self ) -> Interface { self.0 }
+
+}
+
+impl From<Interface> for InterfaceBuilder {
+	fn from( interface: Interface ) -> Self { Self( interface ) }
+}
+
+/// Denotes whether a function is freestanding, a resource method or targeted at a
+/// single plugin. Constructors and static functions are freestanding unless declared
+/// [`Targeted`](Self::Targeted).
 ///
 /// Determines how dispatch is routed during cross-plugin calls:
-/// freestanding functions broadcast to all plugins, while methods
-/// route to the specific plugin that owns the resource.
+/// freestanding functions broadcast to all plugins, methods route to the
+/// specific plugin that owns the resource, and targeted functions route to the
+/// plugin the caller names.
 #[derive( Debug, Clone, Copy, Eq, PartialEq )]
+#[cfg_attr( feature = "serde", derive( serde::Serialize, serde::Deserialize ), serde( rename_all = "kebab-case" ))]
 pub enum FunctionKind {
 	/// A freestanding function — dispatched to all plugins.
 	Freestanding,
 	/// A resource method (has a `self` parameter) — routed to the plugin that owns the resource.
+	///
+	/// The import returns `result<T, dispatch-error>`, as the caller already knows the
+	/// plugin from the resource it holds.
 	Method,
+	/// A constructor or static function routed to the plugin whose id the caller passes
+	/// as an extra first argument.
+	///
+	/// The importing plugin declares the id parameter, while the plugins implementing
+	/// the function export it without. Like methods, the import returns
+	/// `result<T, dispatch-error>` rather than a result per plugin, and resources it
+	/// returns route their methods back to the chosen plugin. Calls made by the host
+	/// through [`Binding::dispatch`]( crate::Binding::dispatch ) are broadcast as usual.
+	Targeted,
 }
 
 /// Metadata about a function declared by an interface.
 ///
 /// Provides information needed during linking to wire up cross-plugin dispatch.
-#[derive( Debug, Clone )]
+#[derive( Debug, Clone, PartialEq, Eq )]
+#[cfg_attr( feature = "serde", derive( serde::Serialize, serde::Deserialize ), serde( rename_all = "kebab-case" ))]
 pub struct Function {
 	/// Whether this function is freestanding or a resource method.
 	kind: FunctionKind,
 	/// The function's return kind for dispatch handling
 	return_kind: ReturnKind,
 	/// Whether the WIT function is declared with the `async` effect.
+	#[cfg_attr( feature = "serde", serde( default, skip_serializing_if = "std::ops::Not::not" ))]
 	is_async: bool,
+	/// Fuel used when the plugin has no fuel limiter
+	#[cfg_attr( feature = "serde", serde( default, skip_serializing_if = "Option::is_none" ))]
+	default_fuel: Option<u64>,
+	/// Epoch deadline used when the plugin has no epoch limiter
+	#[cfg_attr( feature = "serde", serde( default, skip_serializing_if = "Option::is_none" ))]
+	default_epoch_deadline: Option<u64>,
+	/// Whether calls may be served by a replica of the plugin
+	#[cfg_attr( feature = "serde", serde( default, skip_serializing_if = "std::ops::Not::not" ))]
+	concurrent: bool,
 }
 
 impl Function {
@@ -218,7 +404,7 @@ impl Function {
 		kind: FunctionKind,
 		return_kind: ReturnKind,
 	) -> Self {
-		Self { kind, return_kind, is_async: false }
+		Self { kind, return_kind, is_async: false, default_fuel: None, default_epoch_deadline: None, concurrent: false }
 	}
 
 	/// Creates metadata for a WIT function declared with the `async` effect.
@@ -236,7 +422,7 @@ impl Function {
 		kind: FunctionKind,
 		return_kind: ReturnKind,
 	) -> Self {
-		Self { kind, return_kind, is_async: true }
+		Self { kind, return_kind, is_async: true, default_fuel: None, default_epoch_deadline: None, concurrent: false }
 	}
 
 	/// The function's return kind for dispatch handling.
@@ -254,6 +440,52 @@ impl Function {
 	/// ```
 	pub fn is_async( &self ) -> bool { self.is_async }
 
+	/// The fuel inherited from [`Interface::with_default_fuel`], if any.
+	///
+	/// Set on the metadata handed to limiters and listed by [`Interface::functions`],
+	/// not on metadata passed to the interface.
+	pub fn default_fuel( &self ) -> Option<u64> { self.default_fuel }
+
+	/// The epoch deadline inherited from [`Interface::with_default_epoch_deadline`], if any.
+	pub fn default_epoch_deadline( &self ) -> Option<u64> { self.default_epoch_deadline }
+
+	/// Marks the function as safe to call concurrently with other calls into the same plugin.
+	///
+	/// A Wasmtime store can only run one call at a time, so a plugin is never shared
+	/// between concurrent calls. Instead, calls to a concurrent function may be served
+	/// by any idle replica added with [`PluginInstanceSync::with_replicas`]( crate::PluginInstanceSync::with_replicas )
+	/// or [`PluginInstanceAsync::with_replicas`]( crate::PluginInstanceAsync::with_replicas ),
+	/// while all other calls go to the original instance. Only mark functions whose
+	/// result does not depend on state changed by earlier calls.
+	///
+	/// Resources live in the instance that created them, so the marker is ignored for
+	/// resource methods and for functions whose [`ReturnKind`] may contain resources.
+	///
+	/// ```
+	/// # use wasm_link::{ Function, FunctionKind, ReturnKind };
+	/// let function = Function::new( FunctionKind::Freestanding, ReturnKind::AssumeNoResources ).concurrent();
+	/// assert!( function.is_concurrent() );
+	/// ```
+	pub fn concurrent( mut self ) -> Self {
+		self.concurrent = true ;
+		self
+	}
+
+	/// Whether the function is marked [`concurrent`](Self::concurrent).
+	pub fn is_concurrent( &self ) -> bool { self.concurrent }
+
+	/// Whether a call may be served by a replica of the plugin.
+	pub(crate) fn allows_replicas( &self ) -> bool {
+		self.concurrent && self.is_reroutable()
+	}
+
+	/// Whether a call may be served by an instance other than the one owning the
+	/// plugin's resources.
+	pub(crate) fn is_reroutable( &self ) -> bool {
+		self.kind == FunctionKind::Freestanding
+			&& self.return_kind != ReturnKind::MayContainResources
+	}
+
 }
 
 /// Categorizes a function's return for dispatch handling.
@@ -274,6 +506,7 @@ impl Function {
 /// contains no resource handles anywhere in its structure (including nested within
 /// records, variants, lists, etc.).
 #[derive( Copy, Clone, Eq, PartialEq, Hash, Debug, Default )]
+#[cfg_attr( feature = "serde", derive( serde::Serialize, serde::Deserialize ), serde( rename_all = "kebab-case" ))]
 pub enum ReturnKind {
 	/// Function returns nothing (void).
 	#[default] Void,
@@ -8,3 +8,37 @@ fn return_kinds_describe_their_resource_handling_contract() {
 	assert_eq!( ReturnKind::MayContainResources.to_string(), "Return type may contain resources" );
 	assert_eq!( ReturnKind::AssumeNoResources.to_string(), "Function is assumed to not return any resources" );
 }
+
+#[test]
+fn builder_matches_the_maps() {
+	use std::collections::{ HashMap, HashSet };
+	use crate::{ Function, FunctionKind, Interface };
+
+	let built = Interface::builder()
+		.resource( "counter" )
+		.function( "make-counter", ReturnKind::MayContainResources )
+		.method( "[method]counter.get-value", ReturnKind::AssumeNoResources )
+		.declare( "tick", Function::new_async( FunctionKind::Freestanding, ReturnKind::Void ))
+		.build();
+	let expected = Interface::new(
+		HashMap::from([
+			( "make-counter".to_string(), Function::new( FunctionKind::Freestanding, ReturnKind::MayContainResources )),
+			( "[method]counter.get-value".to_string(), Function::new( FunctionKind::Method, ReturnKind::AssumeNoResources )),
+			( "tick".to_string(), Function::new_async( FunctionKind::Freestanding, ReturnKind::Void )),
+		]),
+		HashSet::from([ "counter".to_string() ]),
+	);
+	assert_eq!( built, expected );
+}
+
+#[test]
+fn builder_extends_an_existing_interface() {
+	use crate::{ Function, FunctionKind, Interface, InterfaceBuilder };
+
+	let interface = Interface::builder().resource( "counter" ).build();
+	let extended = InterfaceBuilder::from( interface )
+		.function( "make-counter", ReturnKind::MayContainResources )
+		.build();
+	assert!( extended.has_resource( "counter" ));
+	assert_eq!( extended.function( "make-counter" ), Some( Function::new( FunctionKind::Freestanding, ReturnKind::MayContainResources )));
+}
@@ -32,9 +32,14 @@
 //! 	- [`cardinality::AtMostOne`]`( Option<( Id, T )> )` - zero or one plugin,
 //!			represented as `option<tuple<PluginId, result<T>>>`
 //! 	- [`cardinality::AtLeastOne`]`( nonempty_collections::NEMap<Id, T> )` - one or more plugins,
-//!			represented as `map<PluginId, result<T>>`
+//!			represented as `map<PluginId, result<T>>`, ordered by id
 //! 	- [`cardinality::Any`]`( HashMap<Id, T> )` - zero or more plugins,
-//!			represented as `map<PluginId, result<T>>`
+//!			represented as `map<PluginId, result<T>>`, ordered by id
+//!
+//! 	These shapes apply to freestanding functions, which are broadcast to every plugin.
+//! 	Methods and [targeted]( FunctionKind::Targeted ) functions reach the single plugin
+//! 	the caller already identified, by owning the resource or passing its id, so they
+//! 	are represented as `result<T, dispatch-error>` regardless of cardinality.
 //!
 //! # Re-exports
 //!
@@ -46,9 +51,9 @@
 //! # Example
 //!
 //! ```
-//! use std::collections::{ HashMap, HashSet };
+//! use std::collections::HashMap ;
 //! use wasm_link::{
-//! 	Binding, Interface, Function, FunctionKind, ReturnKind,
+//! 	Binding, ReturnKind,
 //! 	Plugin, PluginContext, Engine, Component, Linker, ResourceTable, Val,
 //! };
 //! use wasm_link::cardinality::ExactlyOne ;
@@ -108,16 +113,10 @@
 //! ).link( &engine, linker, vec![ leaf_binding ])?;
 //!
 //! // Interface tells `wasm_link` which functions exist and how to handle returns.
-//! let root_binding = Binding::new(
-//! 	"my:package",
-//! 	HashMap::from([( "example".to_string(), Interface::new(
-//! 		HashMap::from([( "get-value".into(), Function::new(
-//! 			FunctionKind::Freestanding, ReturnKind::MayContainResources,
-//! 		))]),
-//! 		HashSet::new(),
-//! 	))]),
-//! 	ExactlyOne( "root".to_string(), root ),
-//! );
+//! let root_binding = Binding::builder( "my:package" )
+//! 	.interface( "example", | example | example
+//! 		.function( "get-value", ReturnKind::MayContainResources ))
+//! 	.build( ExactlyOne( "root".to_string(), root ));
 //!
 //! // Now you can call into the plugin graph from the host.
 //! let result = root_binding.dispatch( "example", "get-value", &[ /* args */ ] )?;
@@ -170,6 +169,14 @@
 //! # }
 //! ```
 //!
+//! Bindings are `Send + Sync`, so clones may be dispatched to from any number of host
+//! threads at once. Each plugin instance runs one call at a time. A synchronous
+//! dispatch to a busy instance does not wait for it, so overlapping dispatches never
+//! deadlock, even through shared dependencies. The dispatch fails with
+//! [`DispatchError::LockRejected`] instead. A dependency's rejection reaches the
+//! consuming plugin as the `lock-rejected` case of its result, and the consumer
+//! decides how to proceed. Asynchronous dispatches wait for the instance instead.
+//!
 //! # Multiple Plugins Per Binding
 //!
 //! A single binding can have multiple plugin implementations. Use [`cardinality::AtLeastOne`]
@@ -241,16 +248,24 @@
 //!
 //! - **Fuel** counts WebAssembly instructions. When fuel runs out, execution traps.
 //! 	Enable with [`Config::consume_fuel`]( wasmtime::Config::consume_fuel ).
-//! 	Set initially via [`Plugin::with_initial_fuel`] and per-call via
-//! 	[`Plugin::with_fuel_limiter`].
+//! 	Set initially via [`Plugin::with_initial_fuel`], per-call via
+//! 	[`Plugin::with_fuel_limiter`], and per-interface via [`Interface::with_default_fuel`].
+//! 	A [`limits::FuelProfile`] measures what representative calls consume.
 //!
 //! - **Epoch deadline** counts external timer ticks. When the deadline is reached,
 //! 	execution traps. Enable with [`Config::epoch_interruption`]( wasmtime::Config::epoch_interruption ).
-//! 	Set per-call via [`Plugin::with_epoch_limiter`].
+//! 	Set per-call via [`Plugin::with_epoch_limiter`] and per-interface via
+//! 	[`Interface::with_default_epoch_deadline`]. A [`limits::Watchdog`] registered
+//! 	through [`Plugin::with_watchdog`] covers calls left without a deadline.
 //!
 //! - **Memory** limits linear memory and table growth via wasmtime's
 //! 	[`ResourceLimiter`]( wasmtime::ResourceLimiter ). No engine configuration required.
-//! 	Set once at instantiation via [`Plugin::with_memory_limiter`].
+//! 	Set once at instantiation via [`Plugin::with_memory_limiter`], or use the
+//! 	[`limits::MemoryCap`] preset through [`Plugin::with_memory_cap`].
+//!
+//! - **Payload** limits cap list lengths, string sizes and nesting depth of the values
+//! 	passed to and returned by a plugin. No engine configuration required.
+//! 	Set via [`Plugin::with_payload_limits`] with [`limits::PayloadLimits`].
 //!
 //! ## Fuel and Epoch Limits
 //!
@@ -298,7 +313,8 @@
 //! ## Important Notes
 //!
 //! **Engine configuration is required.** Fuel and epoch deadline limits only work when enabled
-//! in the [`Engine`] configuration. Memory limits require no engine configuration.
+//! in the [`Engine`] configuration, e.g. through an [`EngineBuilder`]. Memory limits require
+//! no engine configuration.
 //! For more information, see the [wasmtime docs](https://docs.rs/wasmtime/latest/wasmtime/).
 //!
 //! **Fuel and epoch deadlines are independent.** A function can have both a fuel limit and an
@@ -361,7 +377,24 @@ mod interface ;
 mod plugin ;
 mod plugin_instance ;
 mod remap ;
+mod fault ;
+mod call_context ;
+mod plugin_lock ;
+mod canary ;
+mod socket_shape ;
+mod export_path ;
+mod engine ;
+mod component_info ;
 pub mod cardinality ;
+pub mod limits ;
+pub mod snapshot ;
+pub mod conformance ;
+pub mod val ;
+pub mod debug ;
+pub mod protocol ;
+#[cfg( feature = "wave" )] pub mod wave ;
+#[cfg( feature = "cbor" )] pub mod cbor ;
+#[cfg( feature = "arbitrary" )] pub mod fuzz ;
 #[cfg(test)] mod cardinality_tests ;
 #[cfg(test)] mod interface_tests ;
 mod linker ;
@@ -374,10 +407,17 @@ pub use wasmtime::component::{ Component, Linker, ResourceTable, Val };
 #[doc( no_inline )]
 pub use nonempty_collections::{ NEMap, nem };
 
-pub use binding::Binding ;
-pub use interface::{ Interface, Function, FunctionKind, ReturnKind };
-pub use plugin::{ PluginContext, Plugin };
+pub use binding::{ Binding, BindingBuilder, InterfaceHandle };
+pub use interface::{ Interface, InterfaceBuilder, Function, FunctionKind, ReturnKind };
+pub use plugin::{ EpochBehavior, PanicPolicy, PluginContext, Plugin };
 pub use plugin_instance::{ PluginInstanceAsync, PluginInstanceSync, DispatchError };
 pub use remap::{ ItemResolutionTable, Remap };
+pub use fault::{ Fault, FaultInjector };
+pub use call_context::{ CallContext, CallInfo, DispatchId, DispatchReport, LongHold };
+pub use plugin_lock::{ BindableInstance, LockStats };
+pub use canary::{ CanaryComparison, CanaryRollout };
+pub use socket_shape::{ ExportError, SocketShapeError };
 pub use binding::BindingAny ;
-pub use resource_wrapper::{ ResourceCreationError, ResourceReceiveError };
+pub use component_info::ComponentInfo ;
+pub use engine::{ CompatWarning, EngineBuilder, verify_engine };
+pub use resource_wrapper::{ ResourceCreationError, ResourceEvent, ResourceEventKind, ResourceReceiveError };
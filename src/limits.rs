@@ -0,0 +1,553 @@
+//! Ready-made resource limiters.
+//!
+//! Writing a [`ResourceLimiter`] for the common "cap this plugin at X MiB" case is
+//! boilerplate. [`MemoryCap`] covers it and can be installed with
+//! [`Plugin::with_memory_cap`]( crate::Plugin::with_memory_cap ) without adding a
+//! field to the plugin context. [`PayloadLimits`] caps the values passed to and
+//! returned by a plugin instead, and a [`Watchdog`] interrupts calls that run for too
+//! long when no other deadline applies. A [`FuelProfile`] records the fuel representative
+//! dispatches consume, to pick fuel budgets from measurements, while a [`Limiter`] such as
+//! [`AdaptiveFuel`] picks them from measurements as the plugin runs.
+
+use std::cell::Cell ;
+use std::collections::{ BTreeMap, HashMap };
+use std::sync::{ Arc, Mutex, PoisonError };
+use std::sync::atomic::{ AtomicBool, Ordering };
+use std::thread::JoinHandle ;
+use std::time::Duration ;
+use wasmtime::{ Engine, ResourceLimiter, Store };
+use wasmtime::component::Val ;
+
+use crate::{ CallInfo, DispatchError, DispatchReport };
+use crate::plugin_lock::HoldMonitors ;
+
+
+
+/// A [`ResourceLimiter`] capping the size of each linear memory and, optionally,
+/// the number of elements of each table.
+///
+/// By default, growth beyond the cap fails the way wasm expects (e.g. `memory.grow`
+/// returns `-1`) and the plugin may recover. Use [`trap_on_failure`](Self::trap_on_failure)
+/// to trap instead.
+///
+/// ```
+/// use wasm_link::limits::MemoryCap ;
+///
+/// let cap = MemoryCap::new( 16 * 1024 * 1024 )
+/// 	.with_max_table_elements( 10_000 )
+/// 	.trap_on_failure( true );
+/// # let _ = cap ;
+/// ```
+#[derive( Debug, Clone, Copy, PartialEq, Eq )]
+pub struct MemoryCap {
+	/// Maximum size of each linear memory in bytes
+	max_memory_bytes: usize,
+	/// Maximum number of elements of each table
+	max_table_elements: Option<usize>,
+	/// Whether exceeding a cap traps rather than failing the growth
+	trap_on_failure: bool,
+}
+
+impl MemoryCap {
+
+	/// Creates a limiter capping each linear memory at `max_memory_bytes`.
+	/// Tables are not capped.
+	pub fn new( max_memory_bytes: usize ) -> Self {
+		Self { max_memory_bytes, max_table_elements: None, trap_on_failure: false }
+	}
+
+	/// Caps each table at `max_table_elements` elements.
+	pub fn with_max_table_elements( mut self, max_table_elements: usize ) -> Self {
+		self.max_table_elements = Some( max_table_elements );
+		self
+	}
+
+	/// Sets whether exceeding a cap traps instead of failing the growth.
+	pub fn trap_on_failure( mut self, trap: bool ) -> Self {
+		self.trap_on_failure = trap ;
+		self
+	}
+
+	fn check( &self, kind: &str, desired: usize, max: Option<usize> ) -> wasmtime::Result<bool> {
+		match max {
+			Some( max ) if desired > max && self.trap_on_failure =>
+				Err( wasmtime::Error::msg( format!( "{kind} growth to {desired} exceeds the cap of {max}" ))),
+			Some( max ) => Ok( desired <= max ),
+			None => Ok( true ),
+		}
+	}
+
+}
+
+impl From<usize> for MemoryCap {
+	fn from( max_memory_bytes: usize ) -> Self { Self::new( max_memory_bytes ) }
+}
+
+thread_local! {
+	/// The cap of the store whose limiter Wasmtime last consulted on this thread
+	static ACTIVE_CAP: Cell<Option<MemoryCap>> = const { Cell::new( None ) };
+}
+
+impl MemoryCap {
+
+	/// Installs the cap as the store's limiter, without storing anything in its data.
+	///
+	/// Wasmtime only accepts a limiter borrowed from the store's data, so the closure
+	/// keeps the cap and hands it to the zero-sized [`ActiveCap`] right before Wasmtime
+	/// consults it, on the same thread.
+	pub(crate) fn install<T>( self, store: &mut Store<T> ) {
+		store.limiter( move | _ | {
+			ACTIVE_CAP.set( Some( self ));
+			// A zero-sized box does not allocate, so leaking it leaks nothing
+			Box::leak( Box::new( ActiveCap ))
+		});
+	}
+
+}
+
+/// Applies the [`MemoryCap`] its store's limiter handed over.
+struct ActiveCap ;
+
+impl ResourceLimiter for ActiveCap {
+	fn memory_growing( &mut self, current: usize, desired: usize, maximum: Option<usize> ) -> wasmtime::Result<bool> {
+		ACTIVE_CAP.get().map_or( Ok( false ), | mut cap | cap.memory_growing( current, desired, maximum ))
+	}
+	fn table_growing( &mut self, current: usize, desired: usize, maximum: Option<usize> ) -> wasmtime::Result<bool> {
+		ACTIVE_CAP.get().map_or( Ok( false ), | mut cap | cap.table_growing( current, desired, maximum ))
+	}
+}
+
+impl ResourceLimiter for MemoryCap {
+	fn memory_growing( &mut self, _current: usize, desired: usize, _maximum: Option<usize> ) -> wasmtime::Result<bool> {
+		self.check( "memory", desired, Some( self.max_memory_bytes ))
+	}
+	fn table_growing( &mut self, _current: usize, desired: usize, _maximum: Option<usize> ) -> wasmtime::Result<bool> {
+		self.check( "table", desired, self.max_table_elements )
+	}
+}
+
+/// Caps on the size of the arguments a plugin receives and of the values it returns.
+///
+/// Installed with [`Plugin::with_payload_limits`]( crate::Plugin::with_payload_limits ),
+/// the caps are checked on every dispatch into the plugin, and a value exceeding any of
+/// them fails the call with [`DispatchError::PayloadTooLarge`] before it reaches the
+/// plugin or its caller. Nothing is capped by default.
+///
+/// ```
+/// use wasm_link::limits::PayloadLimits ;
+///
+/// let limits = PayloadLimits::new()
+/// 	.with_max_list_len( 10_000 )
+/// 	.with_max_string_bytes( 1024 * 1024 )
+/// 	.with_max_depth( 32 );
+/// # let _ = limits ;
+/// ```
+#[derive( Debug, Clone, Copy, Default, PartialEq, Eq )]
+pub struct PayloadLimits {
+	/// Maximum number of elements of each list or map
+	list_len: Option<usize>,
+	/// Maximum length of each string in bytes
+	string_bytes: Option<usize>,
+	/// Maximum nesting depth of each value
+	depth: Option<usize>,
+}
+
+impl PayloadLimits {
+
+	/// Creates limits that cap nothing.
+	pub fn new() -> Self { Self::default() }
+
+	/// Caps each list and map at `max_list_len` elements.
+	pub fn with_max_list_len( mut self, max_list_len: usize ) -> Self {
+		self.list_len = Some( max_list_len );
+		self
+	}
+
+	/// Caps each string at `max_string_bytes` bytes of UTF-8.
+	pub fn with_max_string_bytes( mut self, max_string_bytes: usize ) -> Self {
+		self.string_bytes = Some( max_string_bytes );
+		self
+	}
+
+	/// Caps how deeply values may nest. A scalar has a depth of 1 and every composite
+	/// value one more than its deepest element, so a `list<u32>` has a depth of 2.
+	pub fn with_max_depth( mut self, max_depth: usize ) -> Self {
+		self.depth = Some( max_depth );
+		self
+	}
+
+	/// Checks `values` against the caps without recursing, so hostile values cannot
+	/// exhaust the host stack either.
+	pub(crate) fn check( &self, values: &[Val] ) -> Result<(), DispatchError> {
+		let exceeds = | max: Option<usize>, size: usize | max.is_some_and(| max | size > max );
+		let too_large = | what: String, max: Option<usize> | DispatchError::PayloadTooLarge(
+			format!( "{what} exceeds the cap of {}", max.unwrap_or_default() )
+		);
+		let mut pending = values.iter().map(| value | ( value, 1 )).collect::<Vec<_>>();
+		while let Some(( value, depth )) = pending.pop() {
+			if exceeds( self.depth, depth ) {
+				return Err( too_large( format!( "value nested {depth} deep" ), self.depth ))
+			}
+			match value {
+				Val::String( string ) if exceeds( self.string_bytes, string.len() ) =>
+					return Err( too_large( format!( "string of {} bytes", string.len() ), self.string_bytes )),
+				Val::List( items ) | Val::Tuple( items ) => {
+					if matches!( value, Val::List( _ )) && exceeds( self.list_len, items.len() ) {
+						return Err( too_large( format!( "list of {} elements", items.len() ), self.list_len ))
+					}
+					pending.extend( items.iter().map(| item | ( item, depth + 1 )));
+				},
+				Val::Map( entries ) => {
+					if exceeds( self.list_len, entries.len() ) {
+						return Err( too_large( format!( "map of {} entries", entries.len() ), self.list_len ))
+					}
+					pending.extend( entries.iter().flat_map(|( key, value )| [( key, depth + 1 ), ( value, depth + 1 )]));
+				},
+				Val::Record( fields ) => pending.extend( fields.iter().map(|( _, value )| ( value, depth + 1 ))),
+				Val::Variant( _, Some( value ))
+				| Val::Option( Some( value ))
+				| Val::Result( Ok( Some( value )))
+				| Val::Result( Err( Some( value ))) => pending.push(( value, depth + 1 )),
+				_ => {},
+			}
+		}
+		Ok(())
+	}
+
+}
+
+/// A safety net interrupting calls that run past a hard ceiling, for plugins whose
+/// calls have no other epoch deadline.
+///
+/// Runs a thread incrementing the engine's epoch every `tick`. Calls into plugins
+/// registered with [`Plugin::with_watchdog`]( crate::Plugin::with_watchdog ) that get no
+/// deadline from an epoch limiter or their function's default are given one of `ceiling`
+/// and handled by the plugin's [`EpochBehavior`]( crate::EpochBehavior ), trapping by
+/// default. The engine must have [`epoch_interruption`]( wasmtime::Config::epoch_interruption )
+/// enabled, and deadlines set elsewhere count the watchdog's ticks too.
+///
+/// On every tick the thread also looks for dispatches that have held a registered plugin
+/// past the threshold of its [long hold observer]( crate::Plugin::with_long_hold_observer ),
+/// so they are reported while still running even if no other dispatch contends for the
+/// plugin.
+///
+/// Dropping the watchdog stops the thread, after which registered calls are no longer
+/// interrupted.
+///
+/// ```
+/// # use std::time::Duration ;
+/// use wasm_link::limits::Watchdog ;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut config = wasmtime::Config::new();
+/// config.epoch_interruption( true );
+/// let engine = wasm_link::Engine::new( &config )?;
+/// let watchdog = Watchdog::start( &engine, Duration::from_millis( 10 ), Duration::from_secs( 5 ));
+/// assert_eq!( watchdog.ceiling_ticks(), 500 );
+/// # Ok(()) }
+/// ```
+#[derive( Debug )]
+pub struct Watchdog {
+	/// Time between epoch increments
+	tick: Duration,
+	/// Deadline in epoch ticks given to calls without another deadline
+	ceiling_ticks: u64,
+	/// Tells the ticking thread to stop
+	stop: Arc<AtomicBool>,
+	/// The ticking thread, joined when the watchdog is dropped
+	thread: Option<JoinHandle<()>>,
+	/// Locks of registered plugins scanned for long holds on every tick
+	hold_monitors: HoldMonitors,
+}
+
+impl Watchdog {
+
+	/// Starts incrementing the epoch of `engine` every `tick`, interrupting registered
+	/// calls once they run for `ceiling`, rounded down to whole ticks but at least one.
+	pub fn start( engine: &Engine, tick: Duration, ceiling: Duration ) -> Self {
+		let ceiling_ticks = u64::try_from( ceiling.as_nanos() / tick.as_nanos().max( 1 ))
+			.unwrap_or( u64::MAX )
+			.max( 1 );
+		let stop = Arc::new( AtomicBool::new( false ));
+		let engine = engine.clone();
+		let stopped = Arc::clone( &stop );
+		let hold_monitors = HoldMonitors::default();
+		let monitors = hold_monitors.clone();
+		let thread = std::thread::spawn( move || while !stopped.load( Ordering::Acquire ) {
+			std::thread::park_timeout( tick );
+			engine.increment_epoch();
+			monitors.scan();
+		});
+		Self { tick, ceiling_ticks, stop, thread: Some( thread ), hold_monitors }
+	}
+
+	/// Returns the time between epoch increments.
+	pub fn tick( &self ) -> Duration { self.tick }
+
+	/// Returns the deadline, in epoch ticks, given to calls without another deadline.
+	pub fn ceiling_ticks( &self ) -> u64 { self.ceiling_ticks }
+
+	pub(crate) fn hold_monitors( &self ) -> HoldMonitors { self.hold_monitors.clone() }
+
+}
+
+impl Drop for Watchdog {
+	fn drop( &mut self ) {
+		self.stop.store( true, Ordering::Release );
+		if let Some( thread ) = self.thread.take() {
+			thread.thread().unpark();
+			let _ = thread.join();
+		}
+	}
+}
+
+/// Fuel consumed by the calls to one function of one plugin, as recorded by a [`FuelProfile`].
+#[derive( Debug, Clone, Copy, Default, PartialEq, Eq )]
+pub struct FuelStats {
+	calls: u64,
+	total: u64,
+	max: u64,
+}
+
+impl FuelStats {
+
+	/// Returns the number of recorded calls.
+	pub fn calls( &self ) -> u64 { self.calls }
+
+	/// Returns the fuel consumed by all recorded calls together.
+	pub fn total( &self ) -> u64 { self.total }
+
+	/// Returns the fuel consumed by the most expensive call.
+	pub fn max( &self ) -> u64 { self.max }
+
+	/// Returns the fuel consumed by an average call, rounded down.
+	pub fn mean( &self ) -> u64 { self.total / self.calls.max( 1 ) }
+
+	fn record( &mut self, consumed: u64 ) {
+		self.calls += 1 ;
+		self.total = self.total.saturating_add( consumed );
+		self.max = self.max.max( consumed );
+	}
+
+}
+
+/// Records the fuel consumed per plugin and function, to size the budgets set with
+/// [`Plugin::with_fuel_limiter`]( crate::Plugin::with_fuel_limiter ) or
+/// [`Interface::with_default_fuel`]( crate::Interface::with_default_fuel ).
+///
+/// Install an [`observer`](Self::observer) on each profiled plugin, run representative
+/// dispatches with fuel consumption enabled in the engine, then print the profile for a
+/// table of the calls, mean and maximum fuel of each function. Clones share the recorded
+/// figures. Calls made without fuel are not recorded.
+///
+/// ```
+/// # use wasm_link::{ Plugin, PluginContext, ResourceTable, Component };
+/// use wasm_link::limits::FuelProfile ;
+/// # struct Ctx { resource_table: ResourceTable }
+/// # impl PluginContext for Ctx {
+/// # 	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.resource_table }
+/// # }
+/// # fn example( component: Component ) {
+///
+/// let profile = FuelProfile::new();
+/// let plugin = Plugin::new( component, Ctx { resource_table: ResourceTable::new() })
+/// 	.with_fuel_limiter(| _store, _interface, _function, _metadata | 10_000_000 )
+/// 	.with_dispatch_observer( profile.observer( "my-plugin" ));
+/// // ... link the plugin and run representative dispatches ...
+/// println!( "{profile}" );
+/// # let _ = plugin ;
+/// # }
+/// ```
+#[derive( Debug, Clone, Default )]
+pub struct FuelProfile {
+	/// Recorded figures keyed by plugin, interface path and function name
+	stats: Arc<Mutex<BTreeMap<ProfileKey, FuelStats>>>,
+}
+
+type ProfileKey = ( String, String, String );
+
+impl FuelProfile {
+
+	/// Creates an empty profile.
+	pub fn new() -> Self { Self::default() }
+
+	/// Returns a dispatch observer recording the calls into a plugin under the name `plugin`.
+	pub fn observer( &self, plugin: impl Into<String> ) -> impl FnMut( &DispatchReport<'_> ) + Send + 'static {
+		let plugin = plugin.into();
+		let stats = Arc::clone( &self.stats );
+		move | report | {
+			let Some( consumed ) = report.fuel_consumed() else { return };
+			let key = ( plugin.clone(), report.call().interface_path().to_string(), report.call().function_name().to_string() );
+			stats.lock().unwrap_or_else( PoisonError::into_inner ).entry( key ).or_default().record( consumed );
+		}
+	}
+
+	/// Returns the figures recorded for `function` of `interface_path` in `plugin`.
+	pub fn stats( &self, plugin: &str, interface_path: &str, function: &str ) -> Option<FuelStats> {
+		self.stats.lock().unwrap_or_else( PoisonError::into_inner )
+			.get( &( plugin.to_string(), interface_path.to_string(), function.to_string() ))
+			.copied()
+	}
+
+}
+
+impl std::fmt::Display for FuelProfile {
+	/// Writes one row per plugin and function, ordered by plugin and function.
+	fn fmt( &self, f: &mut std::fmt::Formatter<'_> ) -> std::fmt::Result {
+		let stats = self.stats.lock().unwrap_or_else( PoisonError::into_inner );
+		let rows = std::iter::once([ "plugin".to_string(), "function".to_string(), "calls".to_string(), "mean".to_string(), "max".to_string() ])
+			.chain( stats.iter().map(|(( plugin, interface, function ), stats )| [
+				plugin.clone(),
+				format!( "{interface}#{function}" ),
+				stats.calls().to_string(),
+				stats.mean().to_string(),
+				stats.max().to_string(),
+			]))
+			.collect::<Vec<_>>();
+		let widths = ( 0..5 ).map(| column | rows.iter().map(| row | row[column].len() ).max().unwrap_or( 0 )).collect::<Vec<_>>();
+		rows.iter().try_for_each(| row | writeln!( f, "{:<w0$}  {:<w1$}  {:>w2$}  {:>w3$}  {:>w4$}",
+			row[0], row[1], row[2], row[3], row[4],
+			w0 = widths[0], w1 = widths[1], w2 = widths[2], w3 = widths[3], w4 = widths[4],
+		))
+	}
+}
+
+/// The fuel and epoch deadline a [`Limiter`] gives a call.
+///
+/// A limit left unset falls back to the plugin's limiter closures and then to the
+/// called function's default, as if the limiter was not installed.
+#[derive( Debug, Clone, Copy, Default, PartialEq, Eq )]
+pub struct Limits {
+	/// Fuel the call starts with
+	fuel: Option<u64>,
+	/// Epoch deadline of the call, in ticks
+	epoch_deadline: Option<u64>,
+}
+
+impl Limits {
+
+	/// Creates limits that set nothing.
+	pub fn new() -> Self { Self::default() }
+
+	/// Gives the call `fuel` units of fuel.
+	pub fn with_fuel( mut self, fuel: u64 ) -> Self {
+		self.fuel = Some( fuel );
+		self
+	}
+
+	/// Gives the call an epoch deadline of `ticks`.
+	pub fn with_epoch_deadline( mut self, ticks: u64 ) -> Self {
+		self.epoch_deadline = Some( ticks );
+		self
+	}
+
+	/// Returns the fuel set with [`with_fuel`](Self::with_fuel).
+	pub fn fuel( &self ) -> Option<u64> { self.fuel }
+
+	/// Returns the epoch deadline set with [`with_epoch_deadline`](Self::with_epoch_deadline).
+	pub fn epoch_deadline( &self ) -> Option<u64> { self.epoch_deadline }
+
+}
+
+/// A stateful source of per-call limits, installed with [`Plugin::with_limiter`]( crate::Plugin::with_limiter ).
+///
+/// Unlike the limiter closures, a limiter also learns how each call went, so it can
+/// adapt its budgets to what the plugin actually consumes.
+pub trait Limiter: Send {
+
+	/// Returns the limits of a call about to be made.
+	fn limit( &mut self, call: &CallInfo<'_> ) -> Limits ;
+
+	/// Receives the outcome of a finished call, including the fuel it consumed.
+	///
+	/// Does nothing by default.
+	fn observe( &mut self, report: &DispatchReport<'_> ) { let _ = report ; }
+
+}
+
+/// A [`Limiter`] budgeting each function's fuel from its recent consumption.
+///
+/// Keeps an exponentially weighted moving average of the fuel each function consumes
+/// and gives every call that average plus a headroom. Functions not called yet get
+/// the initial budget. Only meaningful with fuel consumption enabled in the engine.
+///
+/// ```
+/// # use wasm_link::{ Plugin, PluginContext, ResourceTable, Component };
+/// use wasm_link::limits::AdaptiveFuel ;
+/// # struct Ctx { resource_table: ResourceTable }
+/// # impl PluginContext for Ctx {
+/// # 	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.resource_table }
+/// # }
+/// # fn example( component: Component ) {
+///
+/// let plugin = Plugin::new( component, Ctx { resource_table: ResourceTable::new() })
+/// 	.with_limiter( AdaptiveFuel::new( 10_000_000 ).with_headroom_percent( 200 ));
+/// # let _ = plugin ;
+/// # }
+/// ```
+#[derive( Debug, Clone )]
+pub struct AdaptiveFuel {
+	/// Budget of functions without recorded consumption
+	initial: u64,
+	/// Budget above the average, in percent of the average
+	headroom_percent: u64,
+	/// How many samples the average spans; each new sample weighs `1 / window`
+	window: u64,
+	/// Average consumption keyed by interface path and function name
+	averages: HashMap<( String, String ), u64>,
+}
+
+impl AdaptiveFuel {
+
+	/// Creates a limiter giving functions `initial` fuel until their consumption is known,
+	/// then twice their average consumption, averaged over about 8 calls.
+	pub fn new( initial: u64 ) -> Self {
+		Self { initial, headroom_percent: 100, window: 8, averages: HashMap::new() }
+	}
+
+	/// Sets the budget above the average consumption, in percent of the average.
+	pub fn with_headroom_percent( mut self, headroom_percent: u64 ) -> Self {
+		self.headroom_percent = headroom_percent ;
+		self
+	}
+
+	/// Sets how many calls the average consumption spans. A window of 1 budgets from
+	/// the last call alone.
+	pub fn with_window( mut self, window: u64 ) -> Self {
+		self.window = window.max( 1 );
+		self
+	}
+
+	/// Returns the average fuel consumed by `function` of `interface_path` so far.
+	pub fn average( &self, interface_path: &str, function: &str ) -> Option<u64> {
+		self.averages.get( &( interface_path.to_string(), function.to_string() )).copied()
+	}
+
+}
+
+impl Limiter for AdaptiveFuel {
+
+	fn limit( &mut self, call: &CallInfo<'_> ) -> Limits {
+		let fuel = match self.average( call.interface_path(), call.function_name() ) {
+			Some( average ) => average.saturating_add( average.saturating_mul( self.headroom_percent ) / 100 ),
+			None => self.initial,
+		};
+		Limits::new().with_fuel( fuel )
+	}
+
+	fn observe( &mut self, report: &DispatchReport<'_> ) {
+		let Some( consumed ) = report.fuel_consumed() else { return };
+		let key = ( report.call().interface_path().to_string(), report.call().function_name().to_string() );
+		let window = self.window ;
+		self.averages.entry( key )
+			.and_modify(| average | *average = match consumed >= *average {
+				true => *average + ( consumed - *average ) / window,
+				false => *average - ( *average - consumed ) / window,
+			})
+			.or_insert( consumed );
+	}
+
+}
+
+#[cfg(test)]
+mod tests { include!( "limits_tests.rs" ); }
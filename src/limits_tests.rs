@@ -0,0 +1,83 @@
+use wasmtime::ResourceLimiter ;
+use wasmtime::component::Val ;
+use super::{ AdaptiveFuel, Limiter, Limits, MemoryCap, PayloadLimits };
+use crate::{ CallContext, CallInfo, DispatchError, DispatchReport, Function, FunctionKind, ReturnKind };
+
+
+
+#[test]
+fn memory_growth_is_capped() {
+	let mut cap = MemoryCap::new( 2 * 65536 );
+	assert!( cap.memory_growing( 65536, 2 * 65536, None ).unwrap() );
+	assert!( !cap.memory_growing( 2 * 65536, 3 * 65536, None ).unwrap() );
+}
+
+#[test]
+fn tables_are_uncapped_by_default() {
+	let mut cap = MemoryCap::new( 0 );
+	assert!( cap.table_growing( 0, usize::MAX, None ).unwrap() );
+	let mut cap = cap.with_max_table_elements( 10 );
+	assert!( cap.table_growing( 0, 10, None ).unwrap() );
+	assert!( !cap.table_growing( 10, 11, None ).unwrap() );
+}
+
+#[test]
+fn exceeding_a_cap_traps_when_requested() {
+	let mut cap = MemoryCap::new( 65536 ).with_max_table_elements( 1 ).trap_on_failure( true );
+	assert!( cap.memory_growing( 0, 65536, None ).unwrap() );
+	assert_eq!(
+		cap.memory_growing( 65536, 2 * 65536, None ).unwrap_err().to_string(),
+		"memory growth to 131072 exceeds the cap of 65536",
+	);
+	assert!( cap.table_growing( 1, 2, None ).is_err() );
+}
+
+#[test]
+fn payloads_are_uncapped_by_default() {
+	let huge = Val::List( vec![ Val::String( "x".repeat( 1024 )); 1024 ]);
+	assert!( PayloadLimits::new().check( &[ huge ]).is_ok() );
+}
+
+#[test]
+fn payload_caps_reject_long_lists_and_strings() {
+	let limits = PayloadLimits::new().with_max_list_len( 2 ).with_max_string_bytes( 3 );
+	assert!( limits.check( &[ Val::List( vec![ Val::U8( 1 ), Val::U8( 2 ) ]), Val::String( "abc".to_string() )]).is_ok() );
+	match limits.check( &[ Val::Option( Some( Box::new( Val::List( vec![ Val::U8( 1 ); 3 ]))))]) {
+		Err( DispatchError::PayloadTooLarge( message )) => assert_eq!( message, "list of 3 elements exceeds the cap of 2" ),
+		other => panic!( "Expected PayloadTooLarge, found: {other:?}" ),
+	}
+	match limits.check( &[ Val::Record( vec![( "name".to_string(), Val::String( "abcd".to_string() ))])]) {
+		Err( DispatchError::PayloadTooLarge( message )) => assert_eq!( message, "string of 4 bytes exceeds the cap of 3" ),
+		other => panic!( "Expected PayloadTooLarge, found: {other:?}" ),
+	}
+}
+
+#[test]
+fn payload_depth_is_capped() {
+	let nested = ( 0..1_000 ).fold( Val::U8( 0 ), | value, _ | Val::Option( Some( Box::new( value ))));
+	let limits = PayloadLimits::new().with_max_depth( 2 );
+	assert!( limits.check( &[ Val::List( vec![ Val::U8( 0 )])]).is_ok() );
+	match limits.check( &[ nested ]) {
+		Err( DispatchError::PayloadTooLarge( message )) => assert_eq!( message, "value nested 3 deep exceeds the cap of 2" ),
+		other => panic!( "Expected PayloadTooLarge, found: {other:?}" ),
+	}
+}
+
+#[test]
+fn adaptive_fuel_follows_recent_consumption() {
+	let function = Function::new( FunctionKind::Freestanding, ReturnKind::AssumeNoResources );
+	let context = CallContext::new();
+	let call = CallInfo { interface_path: "test:fuel/root", function_name: "burn", function: &function, plugin_id: &(), context: &context };
+	let report = | fuel_consumed | DispatchReport { call: &call, fuel: None, fuel_consumed, elapsed: std::time::Duration::ZERO, succeeded: true };
+	let mut limiter = AdaptiveFuel::new( 1_000 ).with_window( 2 );
+
+	assert_eq!( limiter.limit( &call ), Limits::new().with_fuel( 1_000 ));
+	limiter.observe( &report( Some( 100 )));
+	assert_eq!( limiter.limit( &call ), Limits::new().with_fuel( 200 ));
+	limiter.observe( &report( Some( 300 )));
+	assert_eq!( limiter.average( "test:fuel/root", "burn" ), Some( 200 ));
+	limiter.observe( &report( None ));
+	let mut limiter = limiter.with_headroom_percent( 50 );
+	assert_eq!( limiter.limit( &call ), Limits::new().with_fuel( 300 ));
+	assert_eq!( limiter.average( "test:fuel/root", "other" ), None );
+}
@@ -1,12 +1,14 @@
 use std::sync::Arc ;
 use futures::lock::Mutex ;
 use wasmtime::{ AsContextMut, StoreContextMut };
-use wasmtime::component::{ Accessor, Val };
+use wasmtime::component::{ Accessor, ResourceAny, Val };
 
-use crate::{ Binding, Function, FunctionKind, ReturnKind, PluginContext, DispatchError };
+use crate::{ Binding, CallContext, Function, FunctionKind, ReturnKind, PluginContext, DispatchError };
 use crate::cardinality::Cardinality ;
 use crate::plugin_instance::{ PluginInstanceAsync, PluginInstanceSync };
-use super::resource_wrapper::ResourceWrapper ;
+use crate::binding::Outcomes ;
+use crate::plugin_lock::PluginLock ;
+use super::resource_wrapper::{ ResourceEventKind, ResourceWrapper };
 
 
 
@@ -15,8 +17,17 @@ struct DispatchTarget<'a> {
 	interface_name: &'a str,
 	function_name: &'a str,
 	function: &'a Function,
+	context: &'a CallContext,
 }
 
+/// Wrappers of the resources a plugin passes on from other plugins, by the handle it
+/// returned them under.
+type Forwarded<Id> = Vec<( ResourceAny, ResourceWrapper<Id> )>;
+
+/// Deepest nesting of a value returned across plugins. Values are also subject to the
+/// provider's [`PayloadLimits`]( crate::limits::PayloadLimits ), which may be stricter.
+pub(crate) const MAX_DEPTH: usize = 256 ;
+
 /// Dispatches a non-method function call to all plugins
 pub(crate) fn dispatch_all<PluginId, Ctx, Plugins>(
 	binding: &Binding<PluginId, Ctx, Plugins, PluginInstanceSync<Ctx>>,
@@ -31,29 +42,34 @@ where
 	PluginId: Clone + std::hash::Hash + Eq + Send + Sync + Into<Val> + 'static,
 	Ctx: PluginContext,
 	Plugins: Cardinality<PluginId, PluginInstanceSync<Ctx>>,
-	<Plugins as Cardinality<PluginId, PluginInstanceSync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceSync<Ctx>>>>: Send + Sync,
-	<Plugins as Cardinality<PluginId, PluginInstanceSync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceSync<Ctx>>>>: Cardinality<PluginId, Arc<Mutex<PluginInstanceSync<Ctx>>>>,
-	<<Plugins as Cardinality<PluginId, PluginInstanceSync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceSync<Ctx>>>> as Cardinality<PluginId, Arc<Mutex<PluginInstanceSync<Ctx>>>>>::Rebind<Val>: Into<Val>,
+	<Plugins as Cardinality<PluginId, PluginInstanceSync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceSync<Ctx>>>>: Send + Sync,
+	<Plugins as Cardinality<PluginId, PluginInstanceSync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceSync<Ctx>>>>: Cardinality<PluginId, Arc<PluginLock<PluginInstanceSync<Ctx>>>>,
+	<<Plugins as Cardinality<PluginId, PluginInstanceSync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceSync<Ctx>>>> as Cardinality<PluginId, Arc<PluginLock<PluginInstanceSync<Ctx>>>>>::Rebind<Val>: Into<Val>,
 {
 	debug_assert_eq!( function.kind(), FunctionKind::Freestanding );
+	let context = ctx.data().call_context().cloned().unwrap_or_default();
 	let target = DispatchTarget {
 		package_name,
 		interface_name,
 		function_name,
 		function,
+		context: &context,
 	};
-	binding.plugins().map(| plugin_id, plugin | Val::Result(
-		match dispatch_of(
+	let outcomes = Outcomes::default();
+	let results = binding.plugins().map(| plugin_id, plugin | Val::Result(
+		match outcomes.record( dispatch_of(
 			&mut ctx,
 			plugin_id.clone(),
 			plugin,
 			&target,
 			data,
-		) {
+		)) {
 			Ok( val ) => Ok( Some( Box::new( val ))),
 			Err( err ) => Err( Some( Box::new( err.into() ))),
 		}
-	)).into()
+	));
+	binding.notify_all_failed( interface_name, function_name, &outcomes );
+	binding.socket_results( results.into() )
 }
 
 /// Dispatches a method function call, routing to the correct plugin.
@@ -70,8 +86,8 @@ where
 	PluginId: Clone + std::hash::Hash + Eq + Send + Sync + 'static,
 	Ctx: PluginContext,
 	Plugins: Cardinality<PluginId, PluginInstanceSync<Ctx>>,
-	<Plugins as Cardinality<PluginId, PluginInstanceSync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceSync<Ctx>>>>: Send + Sync,
-	<Plugins as Cardinality<PluginId, PluginInstanceSync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceSync<Ctx>>>>: Cardinality<PluginId, Arc<Mutex<PluginInstanceSync<Ctx>>>>,
+	<Plugins as Cardinality<PluginId, PluginInstanceSync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceSync<Ctx>>>>: Send + Sync,
+	<Plugins as Cardinality<PluginId, PluginInstanceSync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceSync<Ctx>>>>: Cardinality<PluginId, Arc<PluginLock<PluginInstanceSync<Ctx>>>>,
 {
 	debug_assert_eq!( function.kind(), FunctionKind::Method );
 	Val::Result( match route_method(
@@ -92,7 +108,7 @@ where
 fn dispatch_of<PluginId, Ctx>(
 	ctx: &mut StoreContextMut<Ctx>,
 	plugin_id: PluginId,
-	plugin: &Arc<Mutex<PluginInstanceSync<Ctx>>>,
+	plugin: &Arc<PluginLock<PluginInstanceSync<Ctx>>>,
 	target: &DispatchTarget<'_>,
 	data: &[Val],
 ) -> Result<Val, DispatchError>
@@ -101,12 +117,16 @@ where
 	Ctx: PluginContext,
 {
 
-	let mut lock = plugin.try_lock().ok_or( DispatchError::LockRejected )?;
-	let result = lock.dispatch( target.package_name, target.interface_name, target.function_name, target.function, data )?;
+	let mut lock = plugin.try_lock( target.function, target.context.dispatch_id() )?;
+	let result = lock.dispatch( target.package_name, target.interface_name, target.function_name, target.function, data, &plugin_id, target.context )?;
 
 	Ok( match target.function.return_kind() {
 		ReturnKind::Void | ReturnKind::AssumeNoResources => result,
-		ReturnKind::MayContainResources => wrap_resources( result, plugin_id, ctx )?,
+		ReturnKind::MayContainResources => {
+			let mut forwarded = Vec::new();
+			forwarded_resources( &result, &mut lock.store(), &mut forwarded );
+			wrap_resources( result, plugin_id, ctx, &mut forwarded )?
+		}
 	})
 }
 
@@ -124,8 +144,8 @@ where
 	PluginId: Clone + std::hash::Hash + Eq + Send + Sync + 'static,
 	Ctx: PluginContext,
 	Plugins: Cardinality<PluginId, PluginInstanceSync<Ctx>>,
-	<Plugins as Cardinality<PluginId, PluginInstanceSync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceSync<Ctx>>>>: Send + Sync,
-	<Plugins as Cardinality<PluginId, PluginInstanceSync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceSync<Ctx>>>>: Cardinality<PluginId, Arc<Mutex<PluginInstanceSync<Ctx>>>>,
+	<Plugins as Cardinality<PluginId, PluginInstanceSync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceSync<Ctx>>>>: Send + Sync,
+	<Plugins as Cardinality<PluginId, PluginInstanceSync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceSync<Ctx>>>>: Cardinality<PluginId, Arc<PluginLock<PluginInstanceSync<Ctx>>>>,
 {
 
 	let handle = match data.first() {
@@ -139,11 +159,13 @@ where
 
 	let mut data = Vec::from( data );
 	data[0] = Val::Resource( resource.resource_handle );
+	let context = ctx.data().call_context().cloned().unwrap_or_default();
 	let target = DispatchTarget {
 		package_name,
 		interface_name,
 		function_name,
 		function,
+		context: &context,
 	};
 
 	dispatch_of(
@@ -156,6 +178,41 @@ where
 
 }
 
+/// Dispatches a constructor or static function to the plugin whose id is passed as
+/// its first argument.
+pub(crate) fn dispatch_targeted<PluginId, Ctx, Plugins>(
+	binding: &Binding<PluginId, Ctx, Plugins, PluginInstanceSync<Ctx>>,
+	mut ctx: StoreContextMut<Ctx>,
+	package_name: &str,
+	interface_name: &str,
+	function_name: &str,
+	function: &Function,
+	data: &[Val],
+) -> Val
+where
+	PluginId: Clone + std::hash::Hash + Eq + Send + Sync + Into<Val> + 'static,
+	Ctx: PluginContext,
+	Plugins: Cardinality<PluginId, PluginInstanceSync<Ctx>>,
+	<Plugins as Cardinality<PluginId, PluginInstanceSync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceSync<Ctx>>>>: Send + Sync,
+	<Plugins as Cardinality<PluginId, PluginInstanceSync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceSync<Ctx>>>>: Cardinality<PluginId, Arc<PluginLock<PluginInstanceSync<Ctx>>>>,
+{
+	debug_assert_eq!( function.kind(), FunctionKind::Targeted );
+	let context = ctx.data().call_context().cloned().unwrap_or_default();
+	let target = DispatchTarget {
+		package_name,
+		interface_name,
+		function_name,
+		function,
+		context: &context,
+	};
+	let result = target_plugin( binding, data )
+		.and_then(|( plugin_id, plugin )| dispatch_of( &mut ctx, plugin_id, &plugin, &target, &data[1..] ));
+	Val::Result( match result {
+		Ok( val ) => Ok( Some( Box::new( val ))),
+		Err( err ) => Err( Some( Box::new( err.into() ))),
+	})
+}
+
 /// Asynchronously dispatches a non-method function call to all plugins.
 pub(crate) async fn dispatch_all_async<PluginId, Ctx, Plugins>(
 	binding: &Binding<PluginId, Ctx, Plugins, PluginInstanceAsync<Ctx>>,
@@ -170,23 +227,28 @@ where
 	PluginId: Clone + std::hash::Hash + Eq + Send + Sync + Into<Val> + 'static,
 	Ctx: PluginContext,
 	Plugins: Cardinality<PluginId, PluginInstanceAsync<Ctx>>,
-	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceAsync<Ctx>>>>: Send + Sync,
-	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceAsync<Ctx>>>>: Cardinality<PluginId, Arc<Mutex<PluginInstanceAsync<Ctx>>>>,
-	<<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceAsync<Ctx>>>> as Cardinality<PluginId, Arc<Mutex<PluginInstanceAsync<Ctx>>>>>::Rebind<Val>: Into<Val> + Send,
+	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceAsync<Ctx>>>>: Send + Sync,
+	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceAsync<Ctx>>>>: Cardinality<PluginId, Arc<PluginLock<PluginInstanceAsync<Ctx>>>>,
+	<<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceAsync<Ctx>>>> as Cardinality<PluginId, Arc<PluginLock<PluginInstanceAsync<Ctx>>>>>::Rebind<Val>: Into<Val> + Send,
 {
 	debug_assert_eq!( function.kind(), FunctionKind::Freestanding );
+	let context = ctx.with(| mut access | access.data_mut().call_context().cloned() ).unwrap_or_default();
 	let target = DispatchTarget {
 		package_name,
 		interface_name,
 		function_name,
 		function,
+		context: &context,
 	};
-	binding.plugins().map_async(| plugin_id, plugin | async {
-		Val::Result( match dispatch_of_async( ctx, plugin_id, plugin, &target, data ).await {
+	let outcomes = Outcomes::default();
+	let results = binding.plugins().map_async(| plugin_id, plugin | async {
+		Val::Result( match outcomes.record( dispatch_of_async( ctx, plugin_id, plugin, &target, data ).await ) {
 			Ok( val ) => Ok( Some( Box::new( val ))),
 			Err( err ) => Err( Some( Box::new( err.into() ))),
 		})
-	}).await.into()
+	}).await;
+	binding.notify_all_failed( interface_name, function_name, &outcomes );
+	binding.socket_results( results.into() )
 }
 
 /// Asynchronously dispatches a method call to the plugin owning its resource.
@@ -203,8 +265,8 @@ where
 	PluginId: Clone + std::hash::Hash + Eq + Send + Sync + 'static,
 	Ctx: PluginContext,
 	Plugins: Cardinality<PluginId, PluginInstanceAsync<Ctx>>,
-	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceAsync<Ctx>>>>: Send + Sync,
-	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceAsync<Ctx>>>>: Cardinality<PluginId, Arc<Mutex<PluginInstanceAsync<Ctx>>>>,
+	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceAsync<Ctx>>>>: Send + Sync,
+	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceAsync<Ctx>>>>: Cardinality<PluginId, Arc<PluginLock<PluginInstanceAsync<Ctx>>>>,
 {
 	debug_assert_eq!( function.kind(), FunctionKind::Method );
 	Val::Result( match route_method_async(
@@ -221,6 +283,43 @@ where
 	})
 }
 
+/// Asynchronously dispatches a constructor or static function to the plugin whose id
+/// is passed as its first argument.
+pub(crate) async fn dispatch_targeted_async<PluginId, Ctx, Plugins>(
+	binding: &Binding<PluginId, Ctx, Plugins, PluginInstanceAsync<Ctx>>,
+	ctx: &Accessor<Ctx>,
+	package_name: &str,
+	interface_name: &str,
+	function_name: &str,
+	function: &Function,
+	data: &[Val],
+) -> Val
+where
+	PluginId: Clone + std::hash::Hash + Eq + Send + Sync + Into<Val> + 'static,
+	Ctx: PluginContext,
+	Plugins: Cardinality<PluginId, PluginInstanceAsync<Ctx>>,
+	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceAsync<Ctx>>>>: Send + Sync,
+	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceAsync<Ctx>>>>: Cardinality<PluginId, Arc<PluginLock<PluginInstanceAsync<Ctx>>>>,
+{
+	debug_assert_eq!( function.kind(), FunctionKind::Targeted );
+	let context = ctx.with(| mut access | access.data_mut().call_context().cloned() ).unwrap_or_default();
+	let target = DispatchTarget {
+		package_name,
+		interface_name,
+		function_name,
+		function,
+		context: &context,
+	};
+	let result = match target_plugin( binding, data ) {
+		Ok(( plugin_id, plugin )) => dispatch_of_async( ctx, plugin_id, plugin, &target, &data[1..] ).await,
+		Err( err ) => Err( err ),
+	};
+	Val::Result( match result {
+		Ok( val ) => Ok( Some( Box::new( val ))),
+		Err( err ) => Err( Some( Box::new( err.into() ))),
+	})
+}
+
 /// Asynchronously implements a synchronous WIT import without blocking its host thread.
 pub(crate) async fn dispatch_all_async_blocking<PluginId, Ctx, Plugins>(
 	binding: &Binding<PluginId, Ctx, Plugins, PluginInstanceAsync<Ctx>>,
@@ -235,24 +334,29 @@ where
 	PluginId: Clone + std::hash::Hash + Eq + Send + Sync + Into<Val> + 'static,
 	Ctx: PluginContext,
 	Plugins: Cardinality<PluginId, PluginInstanceAsync<Ctx>>,
-	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceAsync<Ctx>>>>: Send + Sync,
-	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceAsync<Ctx>>>>: Cardinality<PluginId, Arc<Mutex<PluginInstanceAsync<Ctx>>>>,
-	<<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceAsync<Ctx>>>> as Cardinality<PluginId, Arc<Mutex<PluginInstanceAsync<Ctx>>>>>::Rebind<Val>: Into<Val> + Send,
+	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceAsync<Ctx>>>>: Send + Sync,
+	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceAsync<Ctx>>>>: Cardinality<PluginId, Arc<PluginLock<PluginInstanceAsync<Ctx>>>>,
+	<<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceAsync<Ctx>>>> as Cardinality<PluginId, Arc<PluginLock<PluginInstanceAsync<Ctx>>>>>::Rebind<Val>: Into<Val> + Send,
 {
 	debug_assert_eq!( function.kind(), FunctionKind::Freestanding );
+	let context = ctx.data().call_context().cloned().unwrap_or_default();
 	let ctx = Mutex::new( ctx );
 	let target = DispatchTarget {
 		package_name,
 		interface_name,
 		function_name,
 		function,
+		context: &context,
 	};
-	binding.plugins().map_async(| plugin_id, plugin | async {
-		Val::Result( match dispatch_of_async_blocking( &ctx, plugin_id, plugin, &target, data ).await {
+	let outcomes = Outcomes::default();
+	let results = binding.plugins().map_async(| plugin_id, plugin | async {
+		Val::Result( match outcomes.record( dispatch_of_async_blocking( &ctx, plugin_id, plugin, &target, data ).await ) {
 			Ok( val ) => Ok( Some( Box::new( val ))),
 			Err( err ) => Err( Some( Box::new( err.into() ))),
 		})
-	}).await.into()
+	}).await;
+	binding.notify_all_failed( interface_name, function_name, &outcomes );
+	binding.socket_results( results.into() )
 }
 
 /// Asynchronously implements a synchronous WIT method import.
@@ -269,8 +373,8 @@ where
 	PluginId: Clone + std::hash::Hash + Eq + Send + Sync + 'static,
 	Ctx: PluginContext,
 	Plugins: Cardinality<PluginId, PluginInstanceAsync<Ctx>>,
-	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceAsync<Ctx>>>>: Send + Sync,
-	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceAsync<Ctx>>>>: Cardinality<PluginId, Arc<Mutex<PluginInstanceAsync<Ctx>>>>,
+	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceAsync<Ctx>>>>: Send + Sync,
+	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceAsync<Ctx>>>>: Cardinality<PluginId, Arc<PluginLock<PluginInstanceAsync<Ctx>>>>,
 {
 	debug_assert_eq!( function.kind(), FunctionKind::Method );
 	let ctx = Mutex::new( ctx );
@@ -291,7 +395,7 @@ where
 async fn dispatch_of_async<PluginId, Ctx>(
 	ctx: &Accessor<Ctx>,
 	plugin_id: PluginId,
-	plugin: Arc<Mutex<PluginInstanceAsync<Ctx>>>,
+	plugin: Arc<PluginLock<PluginInstanceAsync<Ctx>>>,
 	target: &DispatchTarget<'_>,
 	data: &[Val],
 ) -> Result<Val, DispatchError>
@@ -299,28 +403,37 @@ where
 	PluginId: Clone + std::hash::Hash + Eq + Send + Sync + 'static,
 	Ctx: PluginContext,
 {
-	let lock = plugin.lock().await;
+	let lock = plugin.lock( target.function, target.context.dispatch_id() ).await?;
 	let result = lock.dispatch_async(
 		target.package_name,
 		target.interface_name,
 		target.function_name,
 		target.function,
 		data,
+		&plugin_id,
+		target.context,
 	).await?;
 
 	match target.function.return_kind() {
 		ReturnKind::Void | ReturnKind::AssumeNoResources => Ok( result ),
-		ReturnKind::MayContainResources => ctx.with(| mut access | {
-			let mut store = access.as_context_mut();
-			wrap_resources( result, plugin_id, &mut store )
-		}),
+		ReturnKind::MayContainResources => {
+			let mut forwarded = lock.with_store(| mut store | {
+				let mut forwarded = Vec::new();
+				forwarded_resources( &result, &mut store, &mut forwarded );
+				forwarded
+			}).await ;
+			ctx.with(| mut access | {
+				let mut store = access.as_context_mut();
+				wrap_resources( result, plugin_id, &mut store, &mut forwarded )
+			})
+		}
 	}
 }
 
 async fn dispatch_of_async_blocking<PluginId, Ctx>(
 	ctx: &Mutex<StoreContextMut<'_, Ctx>>,
 	plugin_id: PluginId,
-	plugin: Arc<Mutex<PluginInstanceAsync<Ctx>>>,
+	plugin: Arc<PluginLock<PluginInstanceAsync<Ctx>>>,
 	target: &DispatchTarget<'_>,
 	data: &[Val],
 ) -> Result<Val, DispatchError>
@@ -328,24 +441,87 @@ where
 	PluginId: Clone + std::hash::Hash + Eq + Send + Sync + 'static,
 	Ctx: PluginContext,
 {
-	let lock = plugin.lock().await;
+	let lock = plugin.lock( target.function, target.context.dispatch_id() ).await?;
 	let result = lock.dispatch_async(
 		target.package_name,
 		target.interface_name,
 		target.function_name,
 		target.function,
 		data,
+		&plugin_id,
+		target.context,
 	).await?;
 
 	match target.function.return_kind() {
 		ReturnKind::Void | ReturnKind::AssumeNoResources => Ok( result ),
 		ReturnKind::MayContainResources => {
+			let mut forwarded = lock.with_store(| mut store | {
+				let mut forwarded = Vec::new();
+				forwarded_resources( &result, &mut store, &mut forwarded );
+				forwarded
+			}).await ;
 			let mut store = ctx.lock().await;
-			wrap_resources( result, plugin_id, &mut store )
+			wrap_resources( result, plugin_id, &mut store, &mut forwarded )
 		}
 	}
 }
 
+/// Asynchronously implements a synchronous WIT constructor or static function import.
+pub(crate) async fn dispatch_targeted_async_blocking<PluginId, Ctx, Plugins>(
+	binding: &Binding<PluginId, Ctx, Plugins, PluginInstanceAsync<Ctx>>,
+	ctx: StoreContextMut<'_, Ctx>,
+	package_name: &str,
+	interface_name: &str,
+	function_name: &str,
+	function: &Function,
+	data: &[Val],
+) -> Val
+where
+	PluginId: Clone + std::hash::Hash + Eq + Send + Sync + Into<Val> + 'static,
+	Ctx: PluginContext,
+	Plugins: Cardinality<PluginId, PluginInstanceAsync<Ctx>>,
+	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceAsync<Ctx>>>>: Send + Sync,
+	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceAsync<Ctx>>>>: Cardinality<PluginId, Arc<PluginLock<PluginInstanceAsync<Ctx>>>>,
+{
+	debug_assert_eq!( function.kind(), FunctionKind::Targeted );
+	let context = ctx.data().call_context().cloned().unwrap_or_default();
+	let ctx = Mutex::new( ctx );
+	let target = DispatchTarget {
+		package_name,
+		interface_name,
+		function_name,
+		function,
+		context: &context,
+	};
+	let result = match target_plugin( binding, data ) {
+		Ok(( plugin_id, plugin )) => dispatch_of_async_blocking( &ctx, plugin_id, plugin, &target, &data[1..] ).await,
+		Err( err ) => Err( err ),
+	};
+	Val::Result( match result {
+		Ok( val ) => Ok( Some( Box::new( val ))),
+		Err( err ) => Err( Some( Box::new( err.into() ))),
+	})
+}
+
+/// Finds the plugin whose id is passed as the first argument of a targeted call.
+fn target_plugin<PluginId, Ctx, Plugins, Instance>(
+	binding: &Binding<PluginId, Ctx, Plugins, Instance>,
+	data: &[Val],
+) -> Result<( PluginId, Arc<PluginLock<Instance>> ), DispatchError>
+where
+	PluginId: Clone + std::hash::Hash + Eq + Send + Sync + Into<Val> + 'static,
+	Ctx: PluginContext,
+	Instance: Send + 'static,
+	Plugins: Cardinality<PluginId, Instance>,
+	<Plugins as Cardinality<PluginId, Instance>>::Rebind<Arc<PluginLock<Instance>>>: Send + Sync,
+	<Plugins as Cardinality<PluginId, Instance>>::Rebind<Arc<PluginLock<Instance>>>: Cardinality<PluginId, Arc<PluginLock<Instance>>>,
+{
+	let id = data.first().ok_or( DispatchError::InvalidArgumentList )?;
+	binding.plugins().find(| plugin_id, _ | plugin_id.clone().into() == *id )
+		.map(|( plugin_id, plugin )| ( plugin_id.clone(), Arc::clone( plugin )))
+		.ok_or( DispatchError::InvalidArgumentList )
+}
+
 async fn route_method_async<PluginId, Ctx, Plugins>(
 	binding: &Binding<PluginId, Ctx, Plugins, PluginInstanceAsync<Ctx>>,
 	ctx: &Accessor<Ctx>,
@@ -359,8 +535,8 @@ where
 	PluginId: Clone + std::hash::Hash + Eq + Send + Sync + 'static,
 	Ctx: PluginContext,
 	Plugins: Cardinality<PluginId, PluginInstanceAsync<Ctx>>,
-	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceAsync<Ctx>>>>: Send + Sync,
-	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceAsync<Ctx>>>>: Cardinality<PluginId, Arc<Mutex<PluginInstanceAsync<Ctx>>>>,
+	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceAsync<Ctx>>>>: Send + Sync,
+	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceAsync<Ctx>>>>: Cardinality<PluginId, Arc<PluginLock<PluginInstanceAsync<Ctx>>>>,
 {
 	let handle = match data.first() {
 		Some( Val::Resource( handle )) => Ok( *handle ),
@@ -377,11 +553,13 @@ where
 
 	let mut data = Vec::from( data );
 	data[0] = Val::Resource( resource_handle );
+	let context = ctx.with(| mut access | access.data_mut().call_context().cloned() ).unwrap_or_default();
 	let target = DispatchTarget {
 		package_name,
 		interface_name,
 		function_name,
 		function,
+		context: &context,
 	};
 
 	dispatch_of_async( ctx, plugin_id, plugin, &target, &data ).await
@@ -400,8 +578,8 @@ where
 	PluginId: Clone + std::hash::Hash + Eq + Send + Sync + 'static,
 	Ctx: PluginContext,
 	Plugins: Cardinality<PluginId, PluginInstanceAsync<Ctx>>,
-	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceAsync<Ctx>>>>: Send + Sync,
-	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<Mutex<PluginInstanceAsync<Ctx>>>>: Cardinality<PluginId, Arc<Mutex<PluginInstanceAsync<Ctx>>>>,
+	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceAsync<Ctx>>>>: Send + Sync,
+	<Plugins as Cardinality<PluginId, PluginInstanceAsync<Ctx>>>::Rebind<Arc<PluginLock<PluginInstanceAsync<Ctx>>>>: Cardinality<PluginId, Arc<PluginLock<PluginInstanceAsync<Ctx>>>>,
 {
 	let handle = match data.first() {
 		Some( Val::Resource( handle )) => Ok( *handle ),
@@ -417,21 +595,74 @@ where
 		.clone();
 	let mut data = Vec::from( data );
 	data[0] = Val::Resource( resource_handle );
+	let context = ctx.lock().await.data().call_context().cloned().unwrap_or_default();
 	let target = DispatchTarget {
 		package_name,
 		interface_name,
 		function_name,
 		function,
+		context: &context,
 	};
 
 	dispatch_of_async_blocking( ctx, plugin_id, plugin, &target, &data ).await
 }
 
-fn wrap_resources<T, Id>( val: Val, plugin_id: Id, store: &mut StoreContextMut<T> ) -> Result<Val, DispatchError>
+/// Collects the wrappers of all resources in `val` that the plugin owning `store` received
+/// from other plugins, so passing them on keeps them pointing at their true owner
+/// instead of the plugin that merely forwarded them.
+fn forwarded_resources<T, Id>( val: &Val, store: &mut StoreContextMut<T>, forwarded: &mut Forwarded<Id> )
+where
+	T: PluginContext,
+	Id: Clone + Send + Sync + 'static,
+{
+	forwarded_resources_at( val, 1, store, forwarded );
+}
+
+/// Stops descending past [`MAX_DEPTH`], leaving the value to be rejected by [`wrap_resources`].
+fn forwarded_resources_at<T, Id>( val: &Val, depth: usize, store: &mut StoreContextMut<T>, forwarded: &mut Forwarded<Id> )
 where
 	T: PluginContext,
 	Id: Clone + Send + Sync + 'static,
 {
+	if depth > MAX_DEPTH { return }
+	let depth = depth + 1 ;
+	match val {
+		Val::List( items ) | Val::Tuple( items ) => items.iter().for_each(| item | forwarded_resources_at( item, depth, store, forwarded )),
+		Val::Map( entries ) => entries.iter().for_each(|( key, value )| {
+			forwarded_resources_at( key, depth, store, forwarded );
+			forwarded_resources_at( value, depth, store, forwarded );
+		}),
+		Val::Record( entries ) => entries.iter().for_each(|( _, value )| forwarded_resources_at( value, depth, store, forwarded )),
+		Val::Variant( _, Some( data_box ))
+		| Val::Option( Some( data_box ))
+		| Val::Result( Ok( Some( data_box )))
+		| Val::Result( Err( Some( data_box ))) => forwarded_resources_at( data_box, depth, store, forwarded ),
+		Val::Resource( handle ) => if let Some( wrapper ) = ResourceWrapper::forwarded( *handle, store ) {
+			forwarded.push(( *handle, wrapper ));
+		},
+		_ => {}
+	}
+}
+
+fn wrap_resources<T, Id>( val: Val, plugin_id: Id, store: &mut StoreContextMut<T>, forwarded: &mut Forwarded<Id> ) -> Result<Val, DispatchError>
+where
+	T: PluginContext,
+	Id: Clone + Send + Sync + 'static,
+{
+	wrap_resources_at( val, 1, plugin_id, store, forwarded )
+}
+
+/// Fails with [`DispatchError::PayloadTooLarge`] rather than overflowing the host stack
+/// on values nested deeper than [`MAX_DEPTH`].
+fn wrap_resources_at<T, Id>( val: Val, depth: usize, plugin_id: Id, store: &mut StoreContextMut<T>, forwarded: &mut Forwarded<Id> ) -> Result<Val, DispatchError>
+where
+	T: PluginContext,
+	Id: Clone + Send + Sync + 'static,
+{
+	if depth > MAX_DEPTH {
+		return Err( DispatchError::PayloadTooLarge( format!( "value nested {depth} deep exceeds the cap of {MAX_DEPTH}" )))
+	}
+	let depth = depth + 1 ;
 	Ok( match val {
 		Val::Bool( _ )
 		| Val::S8( _ ) | Val::S16( _ ) | Val::S32( _ ) | Val::S64( _ )
@@ -444,24 +675,30 @@ where
 		| Val::Variant( _, Option::None )
 		| Val::Option( None )
 		| Val::Result( Ok( Option::None )) | Val::Result( Err( Option::None )) => val,
-		Val::List( list ) => Val::List( list.into_iter().map(| item | wrap_resources( item, plugin_id.clone(), store )).collect::<Result<_,_>>()? ),
+		Val::List( list ) => Val::List( list.into_iter().map(| item | wrap_resources_at( item, depth, plugin_id.clone(), store, forwarded )).collect::<Result<_,_>>()? ),
 		Val::Map( entries ) => Val::Map( entries.into_iter()
 			.map(|( key, value )| Ok::<_, DispatchError>((
-				wrap_resources( key, plugin_id.clone(), store )?,
-				wrap_resources( value, plugin_id.clone(), store )?
+				wrap_resources_at( key, depth, plugin_id.clone(), store, forwarded )?,
+				wrap_resources_at( value, depth, plugin_id.clone(), store, forwarded )?
 			)) )
 			.collect::<Result<_,_>>()?
 		),
 		Val::Record( entries ) => Val::Record( entries.into_iter()
-			.map(|( key, value )| Ok::<_, DispatchError>(( key, wrap_resources( value, plugin_id.clone(), store )?)) )
+			.map(|( key, value )| Ok::<_, DispatchError>(( key, wrap_resources_at( value, depth, plugin_id.clone(), store, forwarded )?)) )
 			.collect::<Result<_,_>>()?
 		),
-		Val::Tuple( list ) => Val::Tuple( list.into_iter().map(| item | wrap_resources( item, plugin_id.clone(), store )).collect::<Result<_,_>>()? ),
-		Val::Variant( variant, Some( data_box )) => Val::Variant( variant, Some( Box::new( wrap_resources( *data_box, plugin_id, store )? ))),
-		Val::Option( Some( data_box )) => Val::Option( Some( Box::new( wrap_resources( *data_box, plugin_id, store )? ))),
-		Val::Result( Ok( Some( data_box ))) => Val::Result( Ok( Some( Box::new( wrap_resources( *data_box, plugin_id, store )? )))),
-		Val::Result( Err( Some( data_box ))) => Val::Result( Err( Some( Box::new( wrap_resources( *data_box, plugin_id, store )? )))),
-		Val::Resource( handle ) => Val::Resource( ResourceWrapper::new( plugin_id, handle ).attach( store )? ),
+		Val::Tuple( list ) => Val::Tuple( list.into_iter().map(| item | wrap_resources_at( item, depth, plugin_id.clone(), store, forwarded )).collect::<Result<_,_>>()? ),
+		Val::Variant( variant, Some( data_box )) => Val::Variant( variant, Some( Box::new( wrap_resources_at( *data_box, depth, plugin_id, store, forwarded )? ))),
+		Val::Option( Some( data_box )) => Val::Option( Some( Box::new( wrap_resources_at( *data_box, depth, plugin_id, store, forwarded )? ))),
+		Val::Result( Ok( Some( data_box ))) => Val::Result( Ok( Some( Box::new( wrap_resources_at( *data_box, depth, plugin_id, store, forwarded )? )))),
+		Val::Result( Err( Some( data_box ))) => Val::Result( Err( Some( Box::new( wrap_resources_at( *data_box, depth, plugin_id, store, forwarded )? )))),
+		Val::Resource( handle ) => {
+			let ( wrapper, kind ) = match forwarded.iter().position(|( forwarded, _ )| *forwarded == handle ) {
+				Some( index ) => ( forwarded.swap_remove( index ).1, ResourceEventKind::Received ),
+				None => ( ResourceWrapper::new( plugin_id, handle ), ResourceEventKind::Wrapped ),
+			};
+			Val::Resource( wrapper.attach( store, kind )? )
+		}
 		Val::Future( _ ) => return Err( DispatchError::UnsupportedType( "future".to_string() )),
 		Val::Stream( _ ) => return Err( DispatchError::UnsupportedType( "stream".to_string() )),
 		Val::ErrorContext( _ ) => return Err( DispatchError::UnsupportedType( "error-context".to_string() )),
@@ -1,7 +1,7 @@
 use wasmtime::{ AsContextMut, Config, Engine, Store };
 use wasmtime::component::{ Component, FutureReader, Linker, ResourceTable, StreamReader, Val };
 
-use super::wrap_resources ;
+use super::{ MAX_DEPTH, wrap_resources };
 use crate::PluginContext ;
 
 
@@ -28,11 +28,22 @@ fn wraps_nested_component_values_without_resources() -> Result<(), crate::Dispat
 	];
 
 	values.into_iter().try_for_each(| value |
-		wrap_resources( value, "plugin".to_string(), &mut store.as_context_mut() ).map( drop )
+		wrap_resources( value, "plugin".to_string(), &mut store.as_context_mut(), &mut Vec::new() ).map( drop )
 	)?;
 	Ok(())
 }
 
+#[test]
+fn rejects_values_nested_past_the_depth_cap() {
+	let mut store = Store::new( &Engine::default(), Context { table: ResourceTable::new() });
+	let nested = | depth: usize | ( 1..depth ).fold( Val::U8( 0 ), | value, _ | Val::Option( Some( Box::new( value ))));
+	assert!( wrap_resources( nested( MAX_DEPTH ), "plugin".to_string(), &mut store.as_context_mut(), &mut Vec::new() ).is_ok() );
+	assert!( matches!(
+		wrap_resources( nested( MAX_DEPTH + 1 ), "plugin".to_string(), &mut store.as_context_mut(), &mut Vec::new() ),
+		Err( crate::DispatchError::PayloadTooLarge( message )) if message == format!( "value nested {} deep exceeds the cap of {MAX_DEPTH}", MAX_DEPTH + 1 )
+	));
+}
+
 #[test]
 fn rejects_async_values_during_cross_plugin_transfer() -> Result<(), Box<dyn std::error::Error>> {
 	let mut config = Config::new();
@@ -45,11 +56,11 @@ fn rejects_async_values_during_cross_plugin_transfer() -> Result<(), Box<dyn std
 		.try_into_stream_any( &mut store )?;
 
 	assert!( matches!(
-		wrap_resources( Val::Future( future ), "plugin".to_string(), &mut store.as_context_mut() ),
+		wrap_resources( Val::Future( future ), "plugin".to_string(), &mut store.as_context_mut(), &mut Vec::new() ),
 		Err( crate::DispatchError::UnsupportedType( name )) if name == "future"
 	));
 	assert!( matches!(
-		wrap_resources( Val::Stream( stream ), "plugin".to_string(), &mut store.as_context_mut() ),
+		wrap_resources( Val::Stream( stream ), "plugin".to_string(), &mut store.as_context_mut(), &mut Vec::new() ),
 		Err( crate::DispatchError::UnsupportedType( name )) if name == "stream"
 	));
 	Ok(())
@@ -74,9 +85,148 @@ fn rejects_error_contexts_during_cross_plugin_transfer() -> Result<(), Box<dyn s
 		let mut results = [ Val::Bool( false ) ];
 		function.call_async( &mut store, &[], &mut results ).await?;
 		assert!( matches!(
-			wrap_resources( results[0].clone(), "plugin".to_string(), &mut store.as_context_mut() ),
+			wrap_resources( results[0].clone(), "plugin".to_string(), &mut store.as_context_mut(), &mut Vec::new() ),
 			Err( crate::DispatchError::UnsupportedType( name )) if name == "error-context"
 		));
 		Ok::<_, Box<dyn std::error::Error>>(())
 	})
 }
+
+#[cfg( feature = "arbitrary" )]
+#[test]
+fn wrapping_arbitrary_values_preserves_structure_and_ownership() -> Result<(), Box<dyn std::error::Error>> {
+	use std::collections::HashMap ;
+	use wasmtime::component::{ Resource, ResourceAny };
+	use wasmtime::component::types::ComponentItem ;
+	use wit_component::{ ComponentEncoder, StringEncoding, dummy_module, embed_component_metadata };
+	use crate::cardinality::Any ;
+	use crate::fuzz::ValGenerator ;
+
+	let mut resolve = wit_parser::Resolve::new();
+	let package = resolve.push_str( "fuzz.wit", r"
+		package test:fuzz ;
+		interface api {
+			resource res ;
+			flags perms { read, write }
+			enum color { red, green }
+			variant shape { circle( f32 ), none, boxed( res ) }
+			record rec {
+				items: list<res>,
+				shape: shape,
+				maybe: option<tuple<u8, string>>,
+				outcome: result<res, perms>,
+				bare: result,
+				color: color,
+				lookup: map<string, res>,
+			}
+			f: func( r: rec, c: char, s: s64, u: u64, x: f64, b: bool, small: tuple<s8, u8, s16, u16, s32, u32, f32> );
+		}
+		world fuzz { export api ; }
+	" )?;
+	let world = resolve.select_world( &[ package ], Some( "fuzz" ))?;
+	let mut module = dummy_module( &resolve, world, wit_parser::ManglingAndAbi::Standard32 );
+	embed_component_metadata( &mut module, &resolve, world, StringEncoding::UTF8 )?;
+	let component = ComponentEncoder::default().module( &module )?.validate( true ).encode()?;
+
+	let mut config = wasmtime::Config::new();
+	config.wasm_component_model_map( true );
+	let engine = Engine::new( &config )?;
+	let component = Component::new( &engine, component )?;
+	let Some( ComponentItem::ComponentInstance( api )) = component.component_type().get_export( &engine, "test:fuzz/api" ).map(| export | export.ty )
+		else { return Err( "missing api export".into() ) };
+	let Some( ComponentItem::ComponentFunc( function )) = api.get_export( &engine, "f" ).map(| export | export.ty )
+		else { return Err( "missing f export".into() ) };
+
+	let mut store = Store::new( &engine, Context { table: ResourceTable::new() });
+	let mut next_rep = 0_u32 ;
+	let generated = seeded_inputs( 2048 ).try_fold( 0_usize, | generated, input | {
+		let mut u = arbitrary::Unstructured::new( &input );
+		let args = {
+			let store = &mut store ;
+			let mut generator = ValGenerator::new().with_resources(| _ | {
+				next_rep += 1 ;
+				ResourceAny::try_from_resource( Resource::<u32>::new_own( next_rep ), &mut *store ).expect( "host resource conversion" )
+			});
+			match generator.args( &mut u, &function ) {
+				Ok( args ) => args,
+				Err( arbitrary::Error::NotEnoughData ) => return Ok::<_, Box<dyn std::error::Error>>( generated ),
+				Err( err ) => return Err( err.into() ),
+			}
+		};
+
+		args.iter().try_for_each(| arg | {
+			let wrapped = wrap_resources( arg.clone(), "owner".to_string(), &mut store.as_context_mut(), &mut Vec::new() )?;
+			assert_wrapped( arg, &wrapped, &mut store );
+			Ok::<_, Box<dyn std::error::Error>>(())
+		})?;
+
+		let ids = args.iter().enumerate().map(|( index, _ )| format!( "plugin-{index}" )).collect::<Vec<_>>();
+		let converted = Val::from( Any( ids.iter().cloned().zip( args.iter().cloned() ).collect::<HashMap<_, _>>() ));
+		let Val::Map( entries ) = converted else { panic!( "Any did not convert into a map" ) };
+		assert_eq!( entries.len(), args.len() );
+		assert!( ids.iter().zip( &args ).all(|( id, arg )| entries.contains( &( Val::String( id.clone() ), arg.clone() ))));
+		Ok( generated + 1 )
+	})?;
+	assert!( generated >= 1024, "only {generated} of 2048 inputs produced arguments" );
+	Ok(())
+}
+
+#[cfg( feature = "arbitrary" )]
+use crate::resource_wrapper::ResourceWrapper ;
+
+/// Pseudo-random fuzzer inputs of varying length, reproducible across runs.
+#[cfg( feature = "arbitrary" )]
+fn seeded_inputs( count: u64 ) -> impl Iterator<Item = Vec<u8>> {
+	// SplitMix64, so every seed yields an unrelated byte stream
+	fn mix( state: u64 ) -> u64 {
+		let z = state.wrapping_add( 0x9E37_79B9_7F4A_7C15 );
+		let z = ( z ^ ( z >> 30 )).wrapping_mul( 0xBF58_476D_1CE4_E5B9 );
+		let z = ( z ^ ( z >> 27 )).wrapping_mul( 0x94D0_49BB_1331_11EB );
+		z ^ ( z >> 31 )
+	}
+	( 0..count ).map( move | seed | {
+		let len = usize::try_from( mix( seed ) % 2048 ).unwrap_or( 0 );
+		std::iter::successors( Some( mix( seed.wrapping_add( count ))), | state | Some( mix( *state )))
+			.flat_map( u64::to_le_bytes )
+			.take( len )
+			.collect()
+	})
+}
+
+#[cfg( feature = "arbitrary" )]
+fn assert_wrapped( original: &Val, wrapped: &Val, store: &mut Store<Context> ) {
+	match ( original, wrapped ) {
+		( Val::Resource( original ), Val::Resource( handle )) => {
+			let mut context = store.as_context_mut();
+			let found = ResourceWrapper::<String>::from_handle( *handle, &mut context ).expect( "wrapped handle must resolve" );
+			assert_eq!( found.plugin_id, "owner" );
+			assert_eq!( &found.resource_handle, original );
+		}
+		( Val::List( a ), Val::List( b )) | ( Val::Tuple( a ), Val::Tuple( b )) => {
+			assert_eq!( a.len(), b.len() );
+			a.iter().zip( b ).for_each(|( a, b )| assert_wrapped( a, b, store ));
+		}
+		( Val::Map( a ), Val::Map( b )) => {
+			assert_eq!( a.len(), b.len() );
+			a.iter().zip( b ).for_each(|(( ak, av ), ( bk, bv ))| {
+				assert_wrapped( ak, bk, store );
+				assert_wrapped( av, bv, store );
+			});
+		}
+		( Val::Record( a ), Val::Record( b )) => {
+			assert_eq!( a.len(), b.len() );
+			a.iter().zip( b ).for_each(|(( an, av ), ( bn, bv ))| {
+				assert_eq!( an, bn );
+				assert_wrapped( av, bv, store );
+			});
+		}
+		( Val::Variant( an, Some( a )), Val::Variant( bn, Some( b ))) => {
+			assert_eq!( an, bn );
+			assert_wrapped( a, b, store );
+		}
+		( Val::Option( Some( a )), Val::Option( Some( b )))
+		| ( Val::Result( Ok( Some( a ))), Val::Result( Ok( Some( b ))))
+		| ( Val::Result( Err( Some( a ))), Val::Result( Err( Some( b )))) => assert_wrapped( a, b, store ),
+		( a, b ) => assert_eq!( a, b ),
+	}
+}
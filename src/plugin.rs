@@ -5,15 +5,25 @@
 //! (its **sockets**). The plug declares what the plugin exports; sockets declare what
 //! the plugin expects to import from other plugins.
 
-use std::collections::HashMap ;
-use wasmtime::{ Engine, Store };
+use std::sync::Arc ;
+use std::collections::{ HashMap, HashSet };
+use std::path::PathBuf ;
+use std::time::Duration ;
+use wasmtime::{ Engine, Store, StoreContextMut, UpdateDeadline };
 use wasmtime::component::{ Component, ResourceTable, Linker, Val };
 use futures::task::Spawn ;
 
 use crate::BindingAny ;
-use crate::plugin_instance::{ PluginInstanceAsync, PluginInstanceSync };
-use crate::Function ;
+use crate::plugin_instance::{ CallLimiter, DispatchObserver, PluginInstanceAsync, PluginInstanceSync };
+use crate::{ CallContext, CallInfo, DispatchReport, FaultInjector, Function, LongHold, ResourceEvent };
 use crate::Remap ;
+use crate::limits::{ Limiter, MemoryCap, PayloadLimits, Watchdog };
+use crate::debug::CoreDumps ;
+use crate::plugin_lock::{ LongHoldObserver, PluginWiring };
+use crate::component_info::ComponentInfo ;
+use crate::snapshot::GraphSnapshot ;
+use crate::export_path ;
+use crate::socket_shape::{ self, ExportError };
 
 /// Trait for accessing a [`ResourceTable`] from the store's data type.
 ///
@@ -42,6 +52,70 @@ use crate::Remap ;
 pub trait PluginContext: Send {
 	/// Returns a mutable reference to a resource table.
 	fn resource_table( &mut self ) -> &mut ResourceTable ;
+
+	/// Records the [`CallContext`] of the dispatch about to run in this plugin.
+	///
+	/// Called before every call into the plugin. Override it together with
+	/// [`call_context`](Self::call_context) and [`clear_call_context`](Self::clear_call_context)
+	/// to make the context available to host functions and limiters, and to propagate it
+	/// to the nested dispatches this plugin makes into other plugins. By default the
+	/// context is discarded and every nested dispatch starts a new one.
+	///
+	/// ```
+	/// use wasm_link::{ CallContext, PluginContext, ResourceTable };
+	///
+	/// struct MyPluginData {
+	/// 	resource_table: ResourceTable,
+	/// 	call_context: Option<CallContext>,
+	/// }
+	///
+	/// impl PluginContext for MyPluginData {
+	/// 	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.resource_table }
+	/// 	fn set_call_context( &mut self, context: CallContext ) { self.call_context = Some( context ); }
+	/// 	fn call_context( &self ) -> Option<&CallContext> { self.call_context.as_ref() }
+	/// 	fn clear_call_context( &mut self ) { self.call_context = None ; }
+	/// }
+	/// ```
+	fn set_call_context( &mut self, _context: CallContext ) {}
+
+	/// Returns the [`CallContext`] recorded by [`set_call_context`](Self::set_call_context).
+	fn call_context( &self ) -> Option<&CallContext> { None }
+
+	/// Forgets the [`CallContext`] recorded by [`set_call_context`](Self::set_call_context).
+	///
+	/// Called after every call into the plugin, whether it succeeded or not, so that no
+	/// context outlives its dispatch.
+	fn clear_call_context( &mut self ) {}
+
+	/// Notified whenever a resource of another plugin enters or leaves this plugin's
+	/// [`resource_table`](Self::resource_table).
+	///
+	/// Override it to debug resource leaks or to enforce per-plugin handle quotas, e.g.
+	/// by counting the live resources of each plugin and failing the calls of a plugin
+	/// over its quota from a limiter or host function. Does nothing by default.
+	///
+	/// ```
+	/// use std::collections::HashMap ;
+	/// use wasm_link::{ PluginContext, ResourceEvent, ResourceEventKind, ResourceTable };
+	///
+	/// struct MyPluginData {
+	/// 	resource_table: ResourceTable,
+	/// 	live: HashMap<String, usize>,
+	/// }
+	///
+	/// impl PluginContext for MyPluginData {
+	/// 	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.resource_table }
+	/// 	fn on_resource_event( &mut self, event: &ResourceEvent<'_> ) {
+	/// 		let Some( owner ) = event.plugin_id::<String>() else { return };
+	/// 		let live = self.live.entry( owner.clone() ).or_default();
+	/// 		match event.kind() {
+	/// 			ResourceEventKind::Wrapped | ResourceEventKind::Received => *live += 1,
+	/// 			ResourceEventKind::Forwarded | ResourceEventKind::Dropped => *live -= 1,
+	/// 		}
+	/// 	}
+	/// }
+	/// ```
+	fn on_resource_event( &mut self, _event: &ResourceEvent<'_> ) {}
 }
 
 /// A WASM component bundled with its runtime context, ready for instantiation.
@@ -85,14 +159,38 @@ pub struct Plugin<Ctx: 'static> {
 	/// Fuel assigned to the store before component instantiation
 	initial_fuel: Option<u64>,
 	/// Closure that determines fuel for each function call
-	#[allow( clippy::type_complexity )]
-	fuel_limiter: Option<Box<dyn FnMut( &mut Store<Ctx>, &str, &str, &Function ) -> u64 + Send>>,
+	fuel_limiter: Option<CallLimiter<Ctx>>,
 	/// Closure that determines epoch deadline for each function call
-	#[allow( clippy::type_complexity )]
-	epoch_limiter: Option<Box<dyn FnMut( &mut Store<Ctx>, &str, &str, &Function ) -> u64 + Send>>,
+	epoch_limiter: Option<CallLimiter<Ctx>>,
+	/// What happens to the instance when a host closure panics during a call
+	panic_policy: PanicPolicy,
+	/// Stateful source of per-call limits, taking precedence over the closures
+	limiter: Option<Box<dyn Limiter>>,
+	/// What happens when a call reaches its epoch deadline
+	epoch_behavior: EpochBehavior<Ctx>,
+	/// Epoch deadline of calls without another one, from a [`Watchdog`]
+	watchdog_ticks: Option<u64>,
+	/// Time between epoch increments, from a [`Watchdog`]
+	watchdog_tick: Option<Duration>,
 	/// Closure that returns a mutable reference to the `ResourceLimiter` in the context
 	#[allow( clippy::type_complexity )]
 	memory_limiter: Option<Box<dyn (FnMut( &mut Ctx ) -> &mut dyn wasmtime::ResourceLimiter) + Send + Sync>>,
+	/// Preset limiter kept in the resource table instead of the context
+	memory_cap: Option<MemoryCap>,
+	/// Chaos-testing faults injected into this plugin's dispatches
+	fault_injector: Option<FaultInjector>,
+	/// Closure notified after each call into the plugin
+	dispatch_observer: Option<DispatchObserver>,
+	/// Closure notified after calls into the plugin taking at least the given time
+	slow_call_observer: Option<( Duration, DispatchObserver )>,
+	/// Caps on the values passed to and returned by the plugin
+	payload_limits: Option<PayloadLimits>,
+	/// Directory the core dumps of trapping calls are written to
+	core_dump_dir: Option<PathBuf>,
+	/// Component identity and sockets recorded in graph snapshots, and the long hold observer
+	wiring: PluginWiring,
+	/// WIT paths of the only interfaces the component may export
+	strict_exports: Option<HashSet<String>>,
 }
 
 impl<Ctx> Plugin<Ctx>
@@ -115,7 +213,20 @@ where
 			initial_fuel: None,
 			fuel_limiter: None,
 			epoch_limiter: None,
+			limiter: None,
+			panic_policy: PanicPolicy::default(),
+			epoch_behavior: EpochBehavior::Trap,
+			watchdog_ticks: None,
+			watchdog_tick: None,
 			memory_limiter: None,
+			memory_cap: None,
+			fault_injector: None,
+			dispatch_observer: None,
+			slow_call_observer: None,
+			payload_limits: None,
+			core_dump_dir: None,
+			wiring: PluginWiring::default(),
+			strict_exports: None,
 		}
 	}
 
@@ -169,7 +280,32 @@ where
 	/// 	.with_fuel_limiter(| _store, _interface, _function, _metadata | 100_000 );
 	/// # }
 	/// ```
-	pub fn with_fuel_limiter( mut self, limiter: impl FnMut( &mut Store<Ctx>, &str, &str, &Function ) -> u64 + Send + 'static ) -> Self {
+	pub fn with_fuel_limiter( self, mut limiter: impl FnMut( &mut Store<Ctx>, &str, &str, &Function ) -> u64 + Send + 'static ) -> Self {
+		self.with_call_fuel_limiter( move | store, call | limiter( store, call.interface_path(), call.function_name(), call.function() ))
+	}
+
+	/// Sets a closure that determines the fuel limit for each function call, given the
+	/// full [`CallInfo`].
+	///
+	/// Unlike [`with_fuel_limiter`](Self::with_fuel_limiter), the closure also learns which
+	/// plugin id is being called and which dispatch the call belongs to, allowing
+	/// per-tenant budgets. Replaces any limiter set with `with_fuel_limiter`.
+	///
+	/// ```
+	/// # use wasm_link::{ Plugin, PluginContext, ResourceTable, Component };
+	/// # struct Ctx { resource_table: ResourceTable }
+	/// # impl PluginContext for Ctx {
+	/// # 	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.resource_table }
+	/// # }
+	/// # fn example( component: Component ) {
+	/// let plugin = Plugin::new( component, Ctx { resource_table: ResourceTable::new() })
+	/// 	.with_call_fuel_limiter(| _store, call | match call.plugin_id::<String>().map( String::as_str ) {
+	/// 		Some( "trusted" ) => 1_000_000,
+	/// 		_ => 10_000,
+	/// 	});
+	/// # }
+	/// ```
+	pub fn with_call_fuel_limiter( mut self, limiter: impl FnMut( &mut Store<Ctx>, &CallInfo<'_> ) -> u64 + Send + 'static ) -> Self {
 		self.fuel_limiter = Some( Box::new( limiter ));
 		self
 	}
@@ -195,11 +331,132 @@ where
 	/// 	.with_epoch_limiter(| _store, _interface, _function, _metadata | 5 );
 	/// # }
 	/// ```
-	pub fn with_epoch_limiter( mut self, limiter: impl FnMut( &mut Store<Ctx>, &str, &str, &Function ) -> u64 + Send + 'static ) -> Self {
+	pub fn with_epoch_limiter( self, mut limiter: impl FnMut( &mut Store<Ctx>, &str, &str, &Function ) -> u64 + Send + 'static ) -> Self {
+		self.with_call_epoch_limiter( move | store, call | limiter( store, call.interface_path(), call.function_name(), call.function() ))
+	}
+
+	/// Sets a closure that determines the epoch deadline for each function call, given
+	/// the full [`CallInfo`].
+	///
+	/// The epoch counterpart of [`with_call_fuel_limiter`](Self::with_call_fuel_limiter).
+	/// Replaces any limiter set with [`with_epoch_limiter`](Self::with_epoch_limiter).
+	///
+	/// ```
+	/// # use wasm_link::{ Plugin, PluginContext, ResourceTable, Component };
+	/// # struct Ctx { resource_table: ResourceTable }
+	/// # impl PluginContext for Ctx {
+	/// # 	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.resource_table }
+	/// # }
+	/// # fn example( component: Component ) {
+	/// let plugin = Plugin::new( component, Ctx { resource_table: ResourceTable::new() })
+	/// 	.with_call_epoch_limiter(| _store, call | {
+	/// 		eprintln!( "{} -> {}", call.dispatch_id(), call.function_name() );
+	/// 		5
+	/// 	});
+	/// # }
+	/// ```
+	pub fn with_call_epoch_limiter( mut self, limiter: impl FnMut( &mut Store<Ctx>, &CallInfo<'_> ) -> u64 + Send + 'static ) -> Self {
 		self.epoch_limiter = Some( Box::new( limiter ));
 		self
 	}
 
+	/// Sets a [`Limiter`] giving each call into this plugin its fuel and epoch deadline.
+	///
+	/// The limits it sets take precedence over the limiter closures, which, like the
+	/// function defaults, still apply to the limits it leaves unset. The limiter also
+	/// receives a [`DispatchReport`] of every finished call, so it may adapt its limits
+	/// to the plugin's actual consumption, as [`AdaptiveFuel`]( crate::limits::AdaptiveFuel ) does.
+	///
+	/// ```
+	/// # use wasm_link::{ CallInfo, DispatchReport, Plugin, PluginContext, ResourceTable, Component };
+	/// use wasm_link::limits::{ Limiter, Limits };
+	/// # struct Ctx { resource_table: ResourceTable }
+	/// # impl PluginContext for Ctx {
+	/// # 	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.resource_table }
+	/// # }
+	///
+	/// /// Gives every call the most fuel any call has consumed so far, doubled.
+	/// struct Peak( u64 );
+	///
+	/// impl Limiter for Peak {
+	/// 	fn limit( &mut self, _call: &CallInfo<'_> ) -> Limits { Limits::new().with_fuel( self.0 * 2 ) }
+	/// 	fn observe( &mut self, report: &DispatchReport<'_> ) {
+	/// 		self.0 = self.0.max( report.fuel_consumed().unwrap_or( 0 ));
+	/// 	}
+	/// }
+	///
+	/// # fn example( component: Component ) {
+	/// let plugin = Plugin::new( component, Ctx { resource_table: ResourceTable::new() })
+	/// 	.with_limiter( Peak( 50_000 ));
+	/// # }
+	/// ```
+	pub fn with_limiter( mut self, limiter: impl Limiter + 'static ) -> Self {
+		self.limiter = Some( Box::new( limiter ));
+		self
+	}
+
+	/// Sets what happens when a call reaches its epoch deadline. Defaults to
+	/// [`EpochBehavior::Trap`].
+	///
+	/// [`EpochBehavior::YieldAndContinue`] lets long-running plugins of an async host
+	/// be time-sliced cooperatively instead of being killed.
+	///
+	/// ```
+	/// # use wasm_link::{ EpochBehavior, Plugin, PluginContext, ResourceTable, Component };
+	/// # struct Ctx { resource_table: ResourceTable }
+	/// # impl PluginContext for Ctx {
+	/// # 	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.resource_table }
+	/// # }
+	/// # fn example( component: Component ) {
+	/// let plugin = Plugin::new( component, Ctx { resource_table: ResourceTable::new() })
+	/// 	.with_epoch_limiter(| _store, _interface, _function, _metadata | 1 )
+	/// 	.with_epoch_behavior( EpochBehavior::YieldAndContinue( 1 ));
+	/// # let _ = plugin ;
+	/// # }
+	/// ```
+	pub fn with_epoch_behavior( mut self, behavior: EpochBehavior<Ctx> ) -> Self {
+		self.epoch_behavior = behavior ;
+		self
+	}
+
+	/// Sets what happens to this plugin when a host closure panics during a call into
+	/// it. Defaults to [`PanicPolicy::Disable`].
+	///
+	/// Panics of limiters, observers, host functions and the plugin context, including
+	/// those of nested dispatches into this plugin's sockets, fail the call with
+	/// [`DispatchError::HostPanic`]( crate::DispatchError::HostPanic ) rather than
+	/// unwinding into the caller, unless the policy is [`PanicPolicy::Propagate`].
+	pub fn with_panic_policy( mut self, policy: PanicPolicy ) -> Self {
+		self.panic_policy = policy ;
+		self
+	}
+
+	/// Lets `watchdog` interrupt calls into this plugin that have no epoch deadline from
+	/// an epoch limiter or their function's default, and calls running past the
+	/// [`deadline`]( CallContext::with_deadline ) of their dispatch. The watchdog also
+	/// reports the plugin's [long holds](Self::with_long_hold_observer) as they happen.
+	///
+	/// ```
+	/// # use std::time::Duration ;
+	/// # use wasm_link::{ Engine, Plugin, PluginContext, ResourceTable, Component };
+	/// # use wasm_link::limits::Watchdog ;
+	/// # struct Ctx { resource_table: ResourceTable }
+	/// # impl PluginContext for Ctx {
+	/// # 	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.resource_table }
+	/// # }
+	/// # fn example( engine: &Engine, component: Component ) {
+	/// let watchdog = Watchdog::start( engine, Duration::from_millis( 10 ), Duration::from_secs( 5 ));
+	/// let plugin = Plugin::new( component, Ctx { resource_table: ResourceTable::new() })
+	/// 	.with_watchdog( &watchdog );
+	/// # }
+	/// ```
+	pub fn with_watchdog( mut self, watchdog: &Watchdog ) -> Self {
+		self.watchdog_ticks = Some( watchdog.ceiling_ticks() );
+		self.watchdog_tick = Some( watchdog.tick() );
+		self.wiring.hold_monitors = Some( watchdog.hold_monitors() );
+		self
+	}
+
 	/// Sets a closure that returns a mutable reference to a [`ResourceLimiter`]( wasmtime::ResourceLimiter )
 	/// embedded in the plugin context.
 	///
@@ -230,13 +487,307 @@ where
 		limiter: impl (FnMut( &mut Ctx ) -> &mut dyn wasmtime::ResourceLimiter) + Send + Sync + 'static,
 	) -> Self {
 		self.memory_limiter = Some( Box::new( limiter ));
+		self.memory_cap = None ;
+		self
+	}
+
+	/// Caps the plugin's memory and table growth with a [`MemoryCap`].
+	///
+	/// Unlike [`with_memory_limiter`](Self::with_memory_limiter), this requires no field
+	/// in the plugin context: the cap is kept alongside the store by wasm-link itself.
+	/// Replaces any limiter set with `with_memory_limiter`. Accepts a plain byte count
+	/// for the common case.
+	///
+	/// ```
+	/// # use wasm_link::{ Plugin, PluginContext, ResourceTable, Component };
+	/// # use wasm_link::limits::MemoryCap ;
+	/// # struct Ctx { resource_table: ResourceTable }
+	/// # impl PluginContext for Ctx {
+	/// # 	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.resource_table }
+	/// # }
+	/// # fn example( component: Component, other: Component ) {
+	/// let plugin = Plugin::new( component, Ctx { resource_table: ResourceTable::new() })
+	/// 	.with_memory_cap( 10 * 1024 * 1024 ); // 10 MiB
+	/// let strict = Plugin::new( other, Ctx { resource_table: ResourceTable::new() })
+	/// 	.with_memory_cap( MemoryCap::new( 1024 * 1024 ).trap_on_failure( true ));
+	/// # }
+	/// ```
+	pub fn with_memory_cap( mut self, cap: impl Into<MemoryCap> ) -> Self {
+		self.memory_cap = Some( cap.into() );
+		self.memory_limiter = None ;
 		self
 	}
 
+	/// Caps the size of the arguments passed to this plugin and of the values it returns
+	/// with [`PayloadLimits`].
+	///
+	/// Protects the host and consuming plugins from providers returning huge or deeply
+	/// nested values. Calls exceeding a cap fail with
+	/// [`PayloadTooLarge`]( crate::DispatchError::PayloadTooLarge ).
+	///
+	/// ```
+	/// # use wasm_link::{ Plugin, PluginContext, ResourceTable, Component };
+	/// # use wasm_link::limits::PayloadLimits ;
+	/// # struct Ctx { resource_table: ResourceTable }
+	/// # impl PluginContext for Ctx {
+	/// # 	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.resource_table }
+	/// # }
+	/// # fn example( component: Component ) {
+	/// let plugin = Plugin::new( component, Ctx { resource_table: ResourceTable::new() })
+	/// 	.with_payload_limits( PayloadLimits::new().with_max_list_len( 10_000 ).with_max_depth( 32 ));
+	/// # let _ = plugin ;
+	/// # }
+	/// ```
+	pub fn with_payload_limits( mut self, limits: PayloadLimits ) -> Self {
+		self.payload_limits = Some( limits );
+		self
+	}
+
+	/// Writes a core dump to `dir` for every call into this plugin that traps.
+	///
+	/// Core dumps are only produced by engines with
+	/// [`coredump_on_trap`]( wasmtime::Config::coredump_on_trap ) enabled, such as one
+	/// created from [`debug::config`]( crate::debug::config ). Each file is named after
+	/// the number of dumps written before it and the trapping function, e.g.
+	/// `0-add.coredump`, and can be inspected with tools such as `wasmgdb`. The directory
+	/// is created when the first dump is written; files from earlier runs are overwritten.
+	///
+	/// ```
+	/// # use wasm_link::{ Plugin, PluginContext, ResourceTable, Component };
+	/// # struct Ctx { resource_table: ResourceTable }
+	/// # impl PluginContext for Ctx {
+	/// # 	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.resource_table }
+	/// # }
+	/// # fn example( component: Component ) {
+	/// let plugin = Plugin::new( component, Ctx { resource_table: ResourceTable::new() })
+	/// 	.with_core_dumps( "target/core-dumps/my-plugin" );
+	/// # let _ = plugin ;
+	/// # }
+	/// ```
+	pub fn with_core_dumps( mut self, dir: impl Into<PathBuf> ) -> Self {
+		self.core_dump_dir = Some( dir.into() );
+		self
+	}
+
+	/// Sets a [`FaultInjector`] that makes this plugin's dispatches misbehave on purpose.
+	///
+	/// Intended for chaos testing: hosts can verify how they cope with rejected locks,
+	/// traps, slow calls, and unexpected return values without writing faulty plugins.
+	/// To make a whole binding misbehave, give each of its plugins an injector.
+	///
+	/// ```
+	/// # use wasm_link::{ Fault, FaultInjector, Plugin, PluginContext, ResourceTable, Component };
+	/// # struct Ctx { resource_table: ResourceTable }
+	/// # impl PluginContext for Ctx {
+	/// # 	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.resource_table }
+	/// # }
+	/// # fn example( component: Component ) {
+	/// let plugin = Plugin::new( component, Ctx { resource_table: ResourceTable::new() })
+	/// 	.with_fault_injector( FaultInjector::new( 1, 0.05, vec![ Fault::LockRejected ]));
+	/// # let _ = plugin ;
+	/// # }
+	/// ```
+	pub fn with_fault_injector( mut self, injector: FaultInjector ) -> Self {
+		self.fault_injector = Some( injector );
+		self
+	}
+
+	/// Sets a closure that is notified after each call into this plugin.
+	///
+	/// The closure receives a [`DispatchReport`] with the call's [`CallInfo`], the fuel
+	/// it consumed, and the time it took. Calls that fail before entering the plugin,
+	/// such as dispatches to unknown functions, are not reported.
+	///
+	/// ```
+	/// # use wasm_link::{ Plugin, PluginContext, ResourceTable, Component };
+	/// # struct Ctx { resource_table: ResourceTable }
+	/// # impl PluginContext for Ctx {
+	/// # 	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.resource_table }
+	/// # }
+	/// # fn example( component: Component ) {
+	/// let plugin = Plugin::new( component, Ctx { resource_table: ResourceTable::new() })
+	/// 	.with_fuel_limiter(| _store, _interface, _function, _metadata | 100_000 )
+	/// 	.with_dispatch_observer(| report | if let Some( consumed ) = report.fuel_consumed() {
+	/// 		eprintln!( "{} used {consumed} fuel", report.call().function_name() );
+	/// 	});
+	/// # }
+	/// ```
+	pub fn with_dispatch_observer( mut self, observer: impl FnMut( &DispatchReport<'_> ) + Send + 'static ) -> Self {
+		self.dispatch_observer = Some( Box::new( observer ));
+		self
+	}
+
+	/// Sets a closure that is notified after each call into this plugin taking `threshold`
+	/// or longer.
+	///
+	/// Receives the same [`DispatchReport`] as [`with_dispatch_observer`](Self::with_dispatch_observer),
+	/// including the called plugin's id and the fuel the call consumed, and may be used
+	/// alongside it. Useful for finding misbehaving plugins in production without
+	/// observing every call.
+	///
+	/// ```
+	/// # use std::time::Duration ;
+	/// # use wasm_link::{ Plugin, PluginContext, ResourceTable, Component };
+	/// # struct Ctx { resource_table: ResourceTable }
+	/// # impl PluginContext for Ctx {
+	/// # 	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.resource_table }
+	/// # }
+	/// # fn example( component: Component ) {
+	/// let plugin = Plugin::new( component, Ctx { resource_table: ResourceTable::new() })
+	/// 	.with_slow_call_observer( Duration::from_millis( 100 ), | report | eprintln!(
+	/// 		"slow call to {}#{}: {:?}",
+	/// 		report.call().interface_path(),
+	/// 		report.call().function_name(),
+	/// 		report.elapsed(),
+	/// 	));
+	/// # }
+	/// ```
+	pub fn with_slow_call_observer(
+		mut self,
+		threshold: Duration,
+		observer: impl FnMut( &DispatchReport<'_> ) + Send + 'static,
+	) -> Self {
+		self.slow_call_observer = Some(( threshold, Box::new( observer )));
+		self
+	}
+
+	/// Sets a closure that is notified when a dispatch finds this plugin busy because
+	/// another dispatch has held it for `threshold` or longer.
+	///
+	/// A hold this long usually means a runaway call into a plugin without fuel or epoch
+	/// limits. The [`LongHold`] names the holding dispatch and how long it has held the
+	/// plugin so far; each hold is reported at most once. Unlike the
+	/// [`slow call observer`](Self::with_slow_call_observer), which runs after the call
+	/// returns, this one runs while the call is still running: on the contending
+	/// dispatch's thread, or on the thread of the plugin's [watchdog](Self::with_watchdog),
+	/// which looks for long holds on every tick whether or not the plugin is contended.
+	/// Add [`with_long_hold_interrupt`](Self::with_long_hold_interrupt) to also end such
+	/// calls.
+	///
+	/// ```
+	/// # use std::time::Duration ;
+	/// # use wasm_link::{ Plugin, PluginContext, ResourceTable, Component };
+	/// # struct Ctx { resource_table: ResourceTable }
+	/// # impl PluginContext for Ctx {
+	/// # 	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.resource_table }
+	/// # }
+	/// # fn example( component: Component ) {
+	/// let plugin = Plugin::new( component, Ctx { resource_table: ResourceTable::new() })
+	/// 	.with_long_hold_observer( Duration::from_secs( 5 ), | hold | eprintln!(
+	/// 		"dispatch {} has held the plugin for {:?}",
+	/// 		hold.dispatch_id(),
+	/// 		hold.held_for(),
+	/// 	));
+	/// # }
+	/// ```
+	pub fn with_long_hold_observer(
+		mut self,
+		threshold: Duration,
+		observer: impl Fn( &LongHold ) + Send + Sync + 'static,
+	) -> Self {
+		self.wiring.long_hold = Some( LongHoldObserver { threshold, observer: Arc::new( observer ) });
+		self
+	}
+
+	/// Interrupts calls into this plugin once they have held it for the threshold of its
+	/// [long hold observer](Self::with_long_hold_observer), as handled by the plugin's
+	/// [`EpochBehavior`], trapping by default.
+	///
+	/// The hold is measured in ticks of the plugin's [watchdog](Self::with_watchdog), so
+	/// this has no effect without both a watchdog and a long hold observer.
+	///
+	/// ```
+	/// # use std::time::Duration ;
+	/// # use wasm_link::{ Engine, Plugin, PluginContext, ResourceTable, Component };
+	/// # use wasm_link::limits::Watchdog ;
+	/// # struct Ctx { resource_table: ResourceTable }
+	/// # impl PluginContext for Ctx {
+	/// # 	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.resource_table }
+	/// # }
+	/// # fn example( engine: &Engine, component: Component ) {
+	/// let watchdog = Watchdog::start( engine, Duration::from_millis( 10 ), Duration::from_secs( 30 ));
+	/// let plugin = Plugin::new( component, Ctx { resource_table: ResourceTable::new() })
+	/// 	.with_watchdog( &watchdog )
+	/// 	.with_long_hold_observer( Duration::from_secs( 5 ), | hold | eprintln!( "interrupting {}", hold.dispatch_id() ))
+	/// 	.with_long_hold_interrupt();
+	/// # }
+	/// ```
+	pub fn with_long_hold_interrupt( mut self ) -> Self {
+		self.wiring.interrupt_long_holds = true ;
+		self
+	}
+
+	/// Sets the identity of this plugin's component recorded in graph snapshots.
+	///
+	/// Typically a content hash of the component's wasm file. It is handed back to the
+	/// resolver of [`GraphSnapshot::load`]( crate::snapshot::GraphSnapshot::load ) to
+	/// find the same component again.
+	///
+	/// ```
+	/// # use wasm_link::{ Plugin, PluginContext, ResourceTable, Component };
+	/// # struct Ctx { resource_table: ResourceTable }
+	/// # impl PluginContext for Ctx {
+	/// # 	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.resource_table }
+	/// # }
+	/// # fn example( component: Component ) {
+	/// let plugin = Plugin::new( component, Ctx { resource_table: ResourceTable::new() })
+	/// 	.with_component_hash( "sha256:9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08" );
+	/// # }
+	/// ```
+	pub fn with_component_hash( mut self, hash: impl Into<String> ) -> Self {
+		self.wiring.component = Some( hash.into() );
+		self
+	}
+
+	/// Rejects the plugin when instantiated if its component exports an interface other
+	/// than the given WIT paths (e.g., `"my:package/api"`), failing with an
+	/// [`ExportError`]( crate::ExportError ).
+	///
+	/// Without this, an export under a mistyped name is only noticed when dispatching
+	/// to it fails with [`InvalidInterfacePath`]( crate::DispatchError::InvalidInterfacePath ).
+	/// Interfaces remapped with [`remap_interfaces`](Self::remap_interfaces) may be
+	/// exported under their remapped name, and any interface may be exported versioned.
+	///
+	/// ```
+	/// # use wasm_link::{ Plugin, PluginContext, ResourceTable, Component };
+	/// # struct Ctx { resource_table: ResourceTable }
+	/// # impl PluginContext for Ctx {
+	/// # 	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.resource_table }
+	/// # }
+	/// # fn example( component: Component ) {
+	/// let plugin = Plugin::new( component, Ctx { resource_table: ResourceTable::new() })
+	/// 	.with_strict_exports([ "my:package/api", "my:package/events" ]);
+	/// # let _ = plugin ;
+	/// # }
+	/// ```
+	pub fn with_strict_exports( mut self, interfaces: impl IntoIterator<Item = impl Into<String>> ) -> Self {
+		self.strict_exports = Some( interfaces.into_iter().map( Into::into ).collect() );
+		self
+	}
+
+	fn check_exports( &self, engine: &Engine ) -> Result<(), ExportError> {
+		let Some( interfaces ) = &self.strict_exports else { return Ok(()) };
+		let declared = interfaces.iter().flat_map(| ident | {
+			let remapped = ident.split_once( '/' ).and_then(|( package, interface )| self.interface_remaps
+				.get( interface )
+				.map(| remap | format!( "{package}/{}", remap.interface_name( interface )))
+			);
+			std::iter::once( ident.clone() ).chain( remapped )
+		}).collect();
+		socket_shape::check_exports( &self.component.component_type(), engine, &declared )
+	}
+
+	pub(crate) fn component_hash( &self ) -> Option<&str> {
+		self.wiring.component.as_deref()
+	}
+
 	/// Sets interface export remaps for this plugin.
 	///
 	/// Use this when a plugin implements the same interface types as its binding
 	/// but exports one or more interfaces or functions under different names.
+	/// No remap is needed for versioned exports such as `my:package/api@1.2.0`, or for
+	/// interfaces exported inside another exported instance: an exact export is used
+	/// first, then the highest version, then a nested instance.
 	///
 	/// The outer map is a lookup table from requested interface name to [`Remap`].
 	/// Each [`Remap`] describes where that requested interface, and optionally
@@ -275,15 +826,23 @@ where
 	///
 	/// Takes ownership of the `linker` because socket bindings are added to it. If you need
 	/// to reuse the same linker for multiple plugins, clone it before passing it in.
+	/// Only the socket functions and resources the component imports are added, so
+	/// bindings may declare members older plugins do not know about.
+	///
+	/// Socket interfaces the `linker` already defines are left to the host: the plugin
+	/// calls the host's functions instead of the plugged plugins, and the interface is
+	/// recorded in [`PluginSnapshot::host_provided`]( crate::snapshot::PluginSnapshot::host_provided ).
 	///
 	/// # Type Parameters
 	/// - `PluginId`: Must implement `Into<Val>` so plugin IDs can be passed to WASM when
 	/// 	dispatching to multi-plugin sockets (the ID identifies which plugin produced each result).
 	///
 	/// # Errors
-	/// Returns an error if linking or instantiation fails.
+	/// Returns a [`SocketShapeError`]( crate::SocketShapeError ) if the component imports a
+	/// socket interface in a shape the socket does not provide, and an error if two sockets
+	/// implement the same interface or linking or instantiation fails.
 	pub fn link<PluginId, Sockets>(
-		self,
+		mut self,
 		engine: &Engine,
 		mut linker: Linker<Ctx>,
 		sockets: Sockets,
@@ -293,9 +852,18 @@ where
 		Sockets: IntoIterator,
 		Sockets::Item: Into<BindingAny<PluginId, Ctx>>,
 	{
-		sockets.into_iter()
-			.map( Into::into )
-			.try_for_each(| binding | binding.add_to_linker( &mut linker ))?;
+		let sockets = sockets.into_iter().map( Into::into ).collect::<Vec<_>>();
+		reject_shared_interfaces( &sockets )?;
+		let component_type = self.component.component_type();
+		let imports = socket_shape::imports( &component_type, engine );
+		let host_defined = host_defined( &linker, sockets.iter().flat_map( BindingAny::interface_idents ))?;
+		let host_provided = sockets.iter()
+			.map(| binding | binding.add_to_linker( &mut linker, &imports, &host_defined ))
+			.collect::<Result<Vec<_>, _>>()?
+			.concat();
+		sockets.iter().try_for_each(| binding | binding.check_imports( &component_type, engine, &host_provided ))?;
+		self.wiring.sockets = GraphSnapshot::capture_sockets( &sockets );
+		self.wiring.host_provided = host_provided ;
 		Self::instantiate( self, engine, &linker )
 	}
 
@@ -308,6 +876,9 @@ where
 	/// thread pool to drive many independent plugin stores without reserving a
 	/// worker for each plugin.
 	///
+	/// Socket interfaces the `linker` already defines are left to the host, as with
+	/// [`link`](Self::link).
+	///
 	/// # Example
 	///
 	/// ```
@@ -332,9 +903,11 @@ where
 	/// ```
 	///
 	/// # Errors
-	/// Returns an error if linking or instantiation fails.
+	/// Returns a [`SocketShapeError`]( crate::SocketShapeError ) if the component imports a
+	/// socket interface in a shape the socket does not provide, and an error if two sockets
+	/// implement the same interface or linking or instantiation fails.
 	pub async fn link_async<PluginId, Sockets, Executor>(
-		self,
+		mut self,
 		engine: &Engine,
 		mut linker: Linker<Ctx>,
 		sockets: Sockets,
@@ -346,31 +919,56 @@ where
 		Sockets::Item: Into<BindingAny<PluginId, Ctx, PluginInstanceAsync<Ctx>>>,
 		Executor: Spawn + Send + Sync + 'static,
 	{
-		sockets.into_iter()
-			.map( Into::into )
-			.try_for_each(| binding | binding.add_to_linker_async( &mut linker ))?;
+		let sockets = sockets.into_iter().map( Into::into ).collect::<Vec<_>>();
+		reject_shared_interfaces( &sockets )?;
+		let component_type = self.component.component_type();
+		let imports = socket_shape::imports( &component_type, engine );
+		let host_defined = host_defined( &linker, sockets.iter().flat_map( BindingAny::interface_idents ))?;
+		let host_provided = sockets.iter()
+			.map(| binding | binding.add_to_linker_async( &mut linker, &imports, &host_defined ))
+			.collect::<Result<Vec<_>, _>>()?
+			.concat();
+		sockets.iter().try_for_each(| binding | binding.check_imports( &component_type, engine, &host_provided ))?;
+		self.wiring.sockets = GraphSnapshot::capture_sockets( &sockets );
+		self.wiring.host_provided = host_provided ;
 		Self::instantiate_async( self, engine, &linker, executor ).await
 	}
 
 	/// A convenience alias for [`Plugin::link`] with 0 sockets
 	///
 	/// # Errors
-	/// Returns an error if instantiation fails.
+	/// Returns an [`ExportError`]( crate::ExportError ) if the component exports an
+	/// interface not allowed by [`with_strict_exports`](Self::with_strict_exports), and
+	/// an error if instantiation fails.
 	pub fn instantiate(
-		self,
+		mut self,
 		engine: &Engine,
 		linker: &Linker<Ctx>
 	) -> Result<PluginInstanceSync<Ctx>, wasmtime::Error> {
+		self.check_exports( engine )?;
+		let export_paths = export_path::resolve( &self.component.component_type(), engine );
+		self.wiring.info = ComponentInfo::new( &self.component, engine );
 		let mut store = Store::new( engine, self.context );
 		if let Some( fuel ) = self.initial_fuel { store.set_fuel( fuel )?; }
 		if let Some( limiter ) = self.memory_limiter { store.limiter( limiter ); }
+		if let Some( cap ) = self.memory_cap { cap.install( &mut store ); }
+		self.epoch_behavior.install( &mut store );
 		let instance = linker.instantiate( &mut store, &self.component )?;
 		Ok( PluginInstanceSync::new_sync(
 			store,
 			instance,
 			self.interface_remaps,
+			export_paths,
 			self.fuel_limiter,
-			self.epoch_limiter,
+			self.epoch_limiter.or_else(|| self.watchdog_ticks.map( watchdog_limiter )),
+			self.limiter,
+			self.fault_injector,
+			observer( self.dispatch_observer, self.slow_call_observer ),
+			self.payload_limits,
+			self.core_dump_dir.map( CoreDumps::new ),
+			self.panic_policy,
+			self.watchdog_tick,
+			self.wiring,
 		))
 	}
 
@@ -384,6 +982,12 @@ where
 	/// Wasmtime concurrency support, which is enabled by default, must not be disabled
 	/// on the `engine` used for asynchronous instances.
 	///
+	/// To pin a plugin to a dedicated thread instead, e.g. for cache locality, give it
+	/// its own single-threaded pool such as `ThreadPool::builder().pool_size( 1 )`.
+	/// Every call to the instance then runs on that thread, and synchronous callers can
+	/// wait for [`Binding::dispatch_async`]( crate::Binding::dispatch_async ) with
+	/// [`futures::executor::block_on`].
+	///
 	/// # Example
 	///
 	/// ```
@@ -403,9 +1007,11 @@ where
 	/// ```
 	///
 	/// # Errors
-	/// Returns an error if instantiation fails.
+	/// Returns an [`ExportError`]( crate::ExportError ) if the component exports an
+	/// interface not allowed by [`with_strict_exports`](Self::with_strict_exports), and
+	/// an error if instantiation fails.
 	pub async fn instantiate_async<Executor>(
-		self,
+		mut self,
 		engine: &Engine,
 		linker: &Linker<Ctx>,
 		executor: Executor,
@@ -413,16 +1019,30 @@ where
 	where
 		Executor: Spawn + Send + Sync + 'static,
 	{
+		self.check_exports( engine )?;
+		let export_paths = export_path::resolve( &self.component.component_type(), engine );
+		self.wiring.info = ComponentInfo::new( &self.component, engine );
 		let mut store = Store::new( engine, self.context );
 		if let Some( fuel ) = self.initial_fuel { store.set_fuel( fuel )?; }
 		if let Some( limiter ) = self.memory_limiter { store.limiter( limiter ); }
+		if let Some( cap ) = self.memory_cap { cap.install( &mut store ); }
+		self.epoch_behavior.install( &mut store );
 		let instance = linker.instantiate_async( &mut store, &self.component ).await?;
 		Ok( PluginInstanceAsync::new(
 			store,
 			instance,
 			self.interface_remaps,
+			export_paths,
 			self.fuel_limiter,
-			self.epoch_limiter,
+			self.epoch_limiter.or_else(|| self.watchdog_ticks.map( watchdog_limiter )),
+			self.limiter,
+			self.fault_injector,
+			observer( self.dispatch_observer, self.slow_call_observer ),
+			self.payload_limits,
+			self.core_dump_dir.map( CoreDumps::new ),
+			self.panic_policy,
+			self.watchdog_tick,
+			self.wiring,
 			executor,
 		))
 	}
@@ -438,7 +1058,127 @@ impl<Ctx: std::fmt::Debug + 'static> std::fmt::Debug for Plugin<Ctx> {
 			.field( "initial_fuel", &self.initial_fuel )
 			.field( "fuel_limiter", &self.fuel_limiter.as_ref().map(| _ | "<closure>" ))
 			.field( "epoch_limiter", &self.epoch_limiter.as_ref().map(| _ | "<closure>" ))
+			.field( "limiter", &self.limiter.as_ref().map(| _ | "<limiter>" ))
+			.field( "epoch_behavior", &self.epoch_behavior )
+			.field( "panic_policy", &self.panic_policy )
+			.field( "watchdog_ticks", &self.watchdog_ticks )
+			.field( "watchdog_tick", &self.watchdog_tick )
 			.field( "memory_limiter", &self.memory_limiter.as_ref().map(| _ | "<closure>" ))
+			.field( "memory_cap", &self.memory_cap )
+			.field( "fault_injector", &self.fault_injector )
+			.field( "dispatch_observer", &self.dispatch_observer.as_ref().map(| _ | "<closure>" ))
+			.field( "slow_call_observer", &self.slow_call_observer.as_ref().map(|( threshold, _ )| threshold ))
+			.field( "payload_limits", &self.payload_limits )
+			.field( "core_dump_dir", &self.core_dump_dir )
+			.field( "component_hash", &self.wiring.component )
+			.field( "strict_exports", &self.strict_exports )
 			.finish_non_exhaustive()
 	}
 }
+
+/// What happens to a plugin when a host closure panics during a call into it.
+///
+/// Set with [`Plugin::with_panic_policy`]. Unless propagated, the panic fails the call
+/// with [`DispatchError::HostPanic`]( crate::DispatchError::HostPanic ).
+#[derive( Debug, Clone, Copy, Default, PartialEq, Eq )]
+pub enum PanicPolicy {
+	/// Resumes unwinding into the caller of the dispatch, as if the panic was not caught.
+	Propagate,
+	/// Keeps the plugin in service; later calls run as usual.
+	Continue,
+	/// Fails every later call into the plugin with the same error, since a panic in the
+	/// middle of a call may leave the plugin's store in an inconsistent state.
+	#[default]
+	Disable,
+}
+
+/// What happens when a call into a plugin reaches its epoch deadline.
+///
+/// Set with [`Plugin::with_epoch_behavior`]. Deadlines are set per call by an epoch
+/// limiter or [`Interface::with_default_epoch_deadline`]( crate::Interface::with_default_epoch_deadline ).
+pub enum EpochBehavior<Ctx: 'static> {
+	/// Fails the call with a [`RuntimeException`]( crate::DispatchError::RuntimeException ).
+	Trap,
+	/// Yields to the async executor, then extends the deadline by the given number of
+	/// ticks and continues.
+	///
+	/// Only applicable to async instances; [`Plugin::instantiate`] and [`Plugin::link`]
+	/// fail for plugins configured with this behavior.
+	YieldAndContinue( u64 ),
+	/// Lets a callback decide, based on the plugin's store. Returning an error fails the
+	/// call with a [`RuntimeException`]( crate::DispatchError::RuntimeException ).
+	#[allow( clippy::type_complexity )]
+	Callback( Box<dyn FnMut( StoreContextMut<'_, Ctx> ) -> Result<UpdateDeadline, wasmtime::Error> + Send + Sync> ),
+}
+
+impl<Ctx: 'static> std::fmt::Debug for EpochBehavior<Ctx> {
+	fn fmt( &self, f: &mut std::fmt::Formatter<'_> ) -> std::fmt::Result {
+		match self {
+			Self::Trap => write!( f, "Trap" ),
+			Self::YieldAndContinue( ticks ) => f.debug_tuple( "YieldAndContinue" ).field( ticks ).finish(),
+			Self::Callback( _ ) => f.debug_tuple( "Callback" ).field( &"<closure>" ).finish(),
+		}
+	}
+}
+
+impl<Ctx: 'static> EpochBehavior<Ctx> {
+	fn install( self, store: &mut Store<Ctx> ) {
+		match self {
+			Self::Trap => store.epoch_deadline_trap(),
+			Self::YieldAndContinue( ticks ) => store.epoch_deadline_async_yield_and_update( ticks ),
+			Self::Callback( callback ) => store.epoch_deadline_callback( callback ),
+		}
+	}
+}
+
+/// Gives calls without a default epoch deadline the watchdog's ceiling.
+fn watchdog_limiter<Ctx>( ticks: u64 ) -> CallLimiter<Ctx> {
+	Box::new( move | _store, call | call.function().default_epoch_deadline().unwrap_or( ticks ))
+}
+
+/// Combines the dispatch observer with the slow call observer, which only sees calls
+/// taking at least its threshold.
+fn observer(
+	dispatch_observer: Option<DispatchObserver>,
+	slow_call_observer: Option<( Duration, DispatchObserver )>,
+) -> Option<DispatchObserver> {
+	let Some(( threshold, mut slow_call_observer )) = slow_call_observer else { return dispatch_observer };
+	let mut dispatch_observer = dispatch_observer ;
+	Some( Box::new( move | report | {
+		if let Some( observer ) = dispatch_observer.as_mut() { observer( report ); }
+		if report.elapsed() >= threshold { slow_call_observer( report ); }
+	}))
+}
+
+/// Fails if two sockets implement the same interface, so that only interfaces the
+/// host defined before linking are mistaken for host-provided ones.
+fn reject_shared_interfaces<PluginId, Ctx, Instance>( sockets: &[BindingAny<PluginId, Ctx, Instance>] ) -> Result<(), wasmtime::Error>
+where
+	PluginId: std::hash::Hash + Eq + Clone + Send + Sync + 'static,
+	Ctx: PluginContext + 'static,
+	Instance: Send + 'static,
+{
+	let mut seen = HashSet::new();
+	match sockets.iter().flat_map( BindingAny::interface_idents ).find(| ident | !seen.insert( ident.clone() )) {
+		Some( ident ) => Err( wasmtime::Error::msg( format!( "{ident} is implemented by more than one socket" ))),
+		None => Ok(()),
+	}
+}
+
+/// Returns which of `idents` the host already defined in `linker`, probing a single
+/// copy of it.
+///
+/// Wasmtime only reports existing definitions by refusing to redefine them, so each
+/// instance is defined in the copy with shadowing refused, and a clash is told apart
+/// from other errors, such as an invalid name, by retrying with shadowing allowed.
+/// The idents must be distinct, as [`reject_shared_interfaces`] ensures.
+fn host_defined<Ctx: 'static>( linker: &Linker<Ctx>, idents: impl IntoIterator<Item = String> ) -> Result<HashSet<String>, wasmtime::Error> {
+	let mut probe = linker.clone();
+	idents.into_iter().filter_map(| ident | {
+		let Err( err ) = probe.allow_shadowing( false ).root().instance( &ident ).map( drop ) else { return None };
+		match probe.allow_shadowing( true ).root().instance( &ident ) {
+			Ok( _ ) => Some( Ok( ident )),
+			Err( _ ) => Some( Err( err )),
+		}
+	}).collect()
+}
@@ -1,16 +1,28 @@
+use std::any::Any ;
+use std::borrow::Cow ;
 use std::collections::HashMap ;
+use std::panic::AssertUnwindSafe ;
 use std::sync::Arc ;
-use futures::future::BoxFuture ;
+use std::time::{ Duration, Instant };
+use futures::future::{ BoxFuture, FutureExt };
 use futures::lock::Mutex ;
 use futures::task::{ FutureObj, Spawn };
 use thiserror::Error ;
 use wasmtime::component::{ Instance, Val };
-use wasmtime::Store ;
+use wasmtime::{ AsContextMut, Store, StoreContextMut };
 
-use crate::{ Function, PluginContext, Remap, ReturnKind };
+use crate::{ CallContext, CallInfo, CanaryRollout, DispatchReport, Fault, FaultInjector, Function, PanicPolicy, PluginContext, Remap, ReturnKind };
+use crate::canary::Canary ;
+use crate::component_info::ComponentInfo ;
+use crate::export_path::ExportPaths ;
+use crate::limits::{ Limiter, Limits, PayloadLimits };
+use crate::linker::MAX_DEPTH ;
+use crate::debug::CoreDumps ;
+use crate::plugin_lock::{ PluginWiring, Replicated, Wired };
 use crate::resource_wrapper::{ ResourceCreationError, ResourceReceiveError };
 
-type CallLimiter<Ctx> = Box<dyn FnMut( &mut Store<Ctx>, &str, &str, &Function ) -> u64 + Send>;
+pub(crate) type CallLimiter<Ctx> = Box<dyn FnMut( &mut Store<Ctx>, &CallInfo<'_> ) -> u64 + Send>;
+pub(crate) type DispatchObserver = Box<dyn FnMut( &DispatchReport<'_> ) + Send>;
 
 
 /// A synchronously instantiated plugin, ready for synchronous dispatch.
@@ -19,6 +31,9 @@ type CallLimiter<Ctx> = Box<dyn FnMut( &mut Store<Ctx>, &str, &str, &Function )
 /// or [`Plugin::link`]( crate::Plugin::link ).
 pub struct PluginInstanceSync<Ctx: 'static> {
 	state: PluginState<Ctx>,
+	replicas: Vec<Self>,
+	canary: Option<Box<Canary<Self>>>,
+	wiring: Arc<PluginWiring>,
 }
 
 /// An asynchronously instantiated plugin, ready for asynchronous dispatch.
@@ -30,14 +45,33 @@ pub struct PluginInstanceSync<Ctx: 'static> {
 pub struct PluginInstanceAsync<Ctx: 'static> {
 	state: Arc<Mutex<PluginState<Ctx>>>,
 	executor: Arc<dyn Spawn + Send + Sync>,
+	replicas: Vec<Self>,
+	canary: Option<Box<Canary<Self>>>,
+	wiring: Arc<PluginWiring>,
 }
 
 struct PluginState<Ctx: 'static> {
 	store: Store<Ctx>,
 	instance: Instance,
 	interface_remaps: HashMap<String, Remap>,
+	export_paths: ExportPaths,
 	fuel_limiter: Option<CallLimiter<Ctx>>,
 	epoch_limiter: Option<CallLimiter<Ctx>>,
+	limiter: Option<Box<dyn Limiter>>,
+	fault_injector: Option<FaultInjector>,
+	dispatch_observer: Option<DispatchObserver>,
+	payload_limits: Option<PayloadLimits>,
+	core_dumps: Option<CoreDumps>,
+	panic_policy: PanicPolicy,
+	/// Time between epoch increments, if a watchdog increments them
+	epoch_tick: Option<Duration>,
+	/// Longest a call may hold the instance before it is interrupted, under
+	/// [`Plugin::with_long_hold_interrupt`]( crate::Plugin::with_long_hold_interrupt )
+	hold_limit: Option<Duration>,
+	/// Message of the panic that disabled the instance under [`PanicPolicy::Disable`]
+	panicked: Option<String>,
+	/// Epoch deadline given to the latest call, in ticks beyond the epoch it started at
+	epoch_deadline: Option<u64>,
 }
 
 impl<Ctx: std::fmt::Debug + 'static> std::fmt::Debug for PluginInstanceSync<Ctx> {
@@ -48,6 +82,12 @@ impl<Ctx: std::fmt::Debug + 'static> std::fmt::Debug for PluginInstanceSync<Ctx>
 			.field( "interface_remaps", &self.state.interface_remaps )
 			.field( "fuel_limiter", &self.state.fuel_limiter.as_ref().map(| _ | "<closure>" ))
 			.field( "epoch_limiter", &self.state.epoch_limiter.as_ref().map(| _ | "<closure>" ))
+			.field( "limiter", &self.state.limiter.as_ref().map(| _ | "<limiter>" ))
+			.field( "fault_injector", &self.state.fault_injector )
+			.field( "dispatch_observer", &self.state.dispatch_observer.as_ref().map(| _ | "<closure>" ))
+			.field( "core_dumps", &self.state.core_dumps )
+			.field( "replicas", &self.replicas.len() )
+			.field( "canary", &self.canary.as_ref().map(| canary | &canary.rollout ))
 			.finish_non_exhaustive()
 	}
 }
@@ -57,6 +97,8 @@ impl<Ctx: 'static> std::fmt::Debug for PluginInstanceAsync<Ctx> {
 		f.debug_struct( "PluginInstanceAsync" )
 			.field( "state", &"<serialized store>" )
 			.field( "executor", &"<executor>" )
+			.field( "replicas", &self.replicas.len() )
+			.field( "canary", &self.canary.as_ref().map(| canary | &canary.rollout ))
 			.finish_non_exhaustive()
 	}
 }
@@ -65,11 +107,16 @@ impl<Ctx: 'static> std::fmt::Debug for PluginInstanceAsync<Ctx> {
 ///
 /// Returned inside a cardinality wrapper from
 /// [`Binding::dispatch`]( crate::binding::Binding::dispatch )
-/// when a function call fails at runtime.
+/// when a function call fails at runtime. Match on [`code`](Self::code) rather than the
+/// displayed message when reporting errors to other systems; with the `serde` feature
+/// enabled, errors serialize as `{ "code": .., "message": .. }`. To tell which dispatch
+/// an error belongs to, dispatch with [`Binding::dispatch_traced`]( crate::Binding::dispatch_traced ).
 #[derive( Error, Debug )]
 pub enum DispatchError {
 	/// Failed to acquire lock on plugin instance (another call is in progress).
 	#[error( "Lock Rejected" )] LockRejected,
+	/// The plugin was disabled with [`Binding::set_enabled`]( crate::Binding::set_enabled ).
+	#[error( "Plugin Disabled" )] PluginDisabled,
 	/// The specified interface path doesn't match any known interface.
 	#[error( "Invalid Interface Path: {0}" )] InvalidInterfacePath( String ),
 	/// The specified function doesn't exist on the interface.
@@ -84,15 +131,56 @@ pub enum DispatchError {
 	#[error( "Unsupported type: {0}" )] UnsupportedType( String ),
 	/// The executor supplied for an async plugin rejected a dispatch task.
 	#[error( "Async executor unavailable" )] ExecutorUnavailable,
+	/// An argument or the result exceeds the plugin's
+	/// [`PayloadLimits`]( crate::limits::PayloadLimits ).
+	#[error( "Payload Too Large: {0}" )] PayloadTooLarge( String ),
+	/// Every plugin of the binding failed, reported instead of their individual errors
+	/// once a hook is set with [`Binding::on_all_failed`]( crate::Binding::on_all_failed ).
+	#[error( "All Implementations Failed" )] AllImplementationsFailed,
+	/// A host closure, such as a limiter, observer or host function, panicked during
+	/// the call. Handled according to the plugin's [`PanicPolicy`]( crate::PanicPolicy ).
+	#[error( "Host Panic: {0}" )] HostPanic( String ),
 	/// Failed to create a resource handle for cross-plugin transfer.
 	#[error( "Resource Create Error: {0}" )] ResourceCreationError( #[from] ResourceCreationError ),
 	/// Failed to receive a resource handle from another plugin.
 	#[error( "Resource Receive Error: {0}" )] ResourceReceiveError( #[from] ResourceReceiveError ),
 }
 
+impl DispatchError {
+
+	/// Returns a stable identifier of the error's kind, the name of its case in the
+	/// `dispatch-error` variant of `wit/wasm-link.wit`, e.g. `"lock-rejected"`. All of
+	/// them are listed in [`protocol::DISPATCH_ERROR_CASES`]( crate::protocol::DISPATCH_ERROR_CASES ).
+	pub fn code( &self ) -> &'static str { match self {
+		Self::LockRejected => "lock-rejected",
+		Self::PluginDisabled => "plugin-disabled",
+		Self::InvalidInterfacePath( _ ) => "invalid-interface-path",
+		Self::InvalidFunction( _ ) => "invalid-function",
+		Self::MissingResponse => "missing-response",
+		Self::RuntimeException( _ ) => "runtime-exception",
+		Self::InvalidArgumentList => "invalid-argument-list",
+		Self::UnsupportedType( _ ) => "unsupported-type",
+		Self::ExecutorUnavailable => "executor-unavailable",
+		Self::PayloadTooLarge( _ ) => "payload-too-large",
+		Self::AllImplementationsFailed => "all-implementations-failed",
+		Self::HostPanic( _ ) => "host-panic",
+		Self::ResourceCreationError( err ) => err.code(),
+		Self::ResourceReceiveError( err ) => err.code(),
+	}}
+
+}
+
+#[cfg( feature = "serde" )]
+impl serde::Serialize for DispatchError {
+	fn serialize<S: serde::Serializer>( &self, serializer: S ) -> Result<S::Ok, S::Error> {
+		crate::snapshot::serialize_error( "DispatchError", self.code(), self, serializer )
+	}
+}
+
 impl From<DispatchError> for Val {
 	fn from( error: DispatchError ) -> Val { match error {
 		DispatchError::LockRejected => Val::Variant( "lock-rejected".to_string(), None ),
+		DispatchError::PluginDisabled => Val::Variant( "plugin-disabled".to_string(), None ),
 		DispatchError::InvalidInterfacePath( package ) => Val::Variant( "invalid-interface-path".to_string(), Some( Box::new( Val::String( package )))),
 		DispatchError::InvalidFunction( function ) => Val::Variant( "invalid-function".to_string(), Some( Box::new( Val::String( function )))),
 		DispatchError::MissingResponse => Val::Variant( "missing-response".to_string(), None ),
@@ -100,28 +188,138 @@ impl From<DispatchError> for Val {
 		DispatchError::InvalidArgumentList => Val::Variant( "invalid-argument-list".to_string(), None ),
 		DispatchError::UnsupportedType( name ) => Val::Variant( "unsupported-type".to_string(), Some( Box::new( Val::String( name )))),
 		DispatchError::ExecutorUnavailable => Val::Variant( "executor-unavailable".to_string(), None ),
+		DispatchError::PayloadTooLarge( limit ) => Val::Variant( "payload-too-large".to_string(), Some( Box::new( Val::String( limit )))),
+		DispatchError::AllImplementationsFailed => Val::Variant( "all-implementations-failed".to_string(), None ),
+		DispatchError::HostPanic( message ) => Val::Variant( "host-panic".to_string(), Some( Box::new( Val::String( message )))),
 		DispatchError::ResourceCreationError( err ) => err.into(),
 		DispatchError::ResourceReceiveError( err ) => err.into(),
 	}}
 }
 
 impl<Ctx: PluginContext + 'static> PluginInstanceSync<Ctx> {
+	#[allow( clippy::too_many_arguments )]
 	pub(crate) fn new_sync(
 		store: Store<Ctx>,
 		instance: Instance,
 		interface_remaps: HashMap<String, Remap>,
+		export_paths: ExportPaths,
 		fuel_limiter: Option<CallLimiter<Ctx>>,
 		epoch_limiter: Option<CallLimiter<Ctx>>,
+		limiter: Option<Box<dyn Limiter>>,
+		fault_injector: Option<FaultInjector>,
+		dispatch_observer: Option<DispatchObserver>,
+		payload_limits: Option<PayloadLimits>,
+		core_dumps: Option<CoreDumps>,
+		panic_policy: PanicPolicy,
+		epoch_tick: Option<Duration>,
+		wiring: PluginWiring,
 	) -> Self {
 		Self { state: PluginState {
 			store,
 			instance,
 			interface_remaps,
+			export_paths,
 			fuel_limiter,
 			epoch_limiter,
-		}}
+			limiter,
+			fault_injector,
+			dispatch_observer,
+			payload_limits,
+			core_dumps,
+			panic_policy,
+			epoch_tick,
+			hold_limit: wiring.hold_limit(),
+			panicked: None,
+			epoch_deadline: None,
+		}, replicas: Vec::new(), canary: None, wiring: Arc::new( wiring ) }
 	}
 
+	/// Returns facts about the compiled component this instance was created from.
+	///
+	/// ```
+	/// # use wasm_link::{ Component, Engine, Linker, Plugin, PluginContext, ResourceTable };
+	/// # struct Context { table: ResourceTable }
+	/// # impl PluginContext for Context { fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.table } }
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// # let engine = Engine::default();
+	/// # let linker = Linker::new( &engine );
+	/// let plugin = Plugin::new( Component::new( &engine, "(component)" )?, Context { table: ResourceTable::new() })
+	/// 	.instantiate( &engine, &linker )?;
+	/// assert!( plugin.component_info().imports().is_empty() );
+	/// # Ok(()) }
+	/// ```
+	pub fn component_info( &self ) -> &ComponentInfo { &self.wiring.info }
+
+	/// Returns the fuel left in the plugin's store, or `None` if the engine does not
+	/// consume fuel.
+	///
+	/// Between calls this is the fuel the latest call did not use, since every call is
+	/// given fresh fuel by the fuel limiter or the function's default. Replicas and
+	/// canaries have stores of their own and are not included. Once the instance is
+	/// bound, use [`Binding::remaining_fuel`]( crate::Binding::remaining_fuel ).
+	pub fn remaining_fuel( &self ) -> Option<u64> { self.state.store.get_fuel().ok() }
+
+	/// Returns the epoch deadline given to the latest call, in ticks beyond the epoch
+	/// it started at, or `None` if the latest call was not given one.
+	///
+	/// Wasmtime does not expose the engine's current epoch, so the deadline is relative
+	/// to the start of the call rather than absolute. Once the instance is bound, use
+	/// [`Binding::current_epoch_deadline`]( crate::Binding::current_epoch_deadline ).
+	pub fn current_epoch_deadline( &self ) -> Option<u64> { self.state.epoch_deadline }
+
+	/// Adds instances of the same plugin that may serve calls to
+	/// [`concurrent`]( crate::Function::concurrent ) functions while this instance is busy.
+	///
+	/// Each replica is instantiated separately, with its own context and, when it has
+	/// sockets, linked against the same bindings. Replicas do not share state with this
+	/// instance or with each other.
+	///
+	/// ```
+	/// # use wasm_link::{ Component, Engine, Linker, Plugin, PluginContext, ResourceTable };
+	/// # struct Context { table: ResourceTable }
+	/// # impl PluginContext for Context { fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.table } }
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// # let engine = Engine::default();
+	/// # let linker = Linker::new( &engine );
+	/// let component = Component::new( &engine, "(component)" )?;
+	/// let instantiate = || Plugin::new( component.clone(), Context { table: ResourceTable::new() })
+	/// 	.instantiate( &engine, &linker );
+	/// let plugin = instantiate()?.with_replicas([ instantiate()?, instantiate()? ]);
+	/// # let _ = plugin ;
+	/// # Ok(()) }
+	/// ```
+	pub fn with_replicas( mut self, replicas: impl IntoIterator<Item = Self> ) -> Self {
+		self.replicas.extend( replicas.into_iter().flat_map( flatten_replicas ));
+		self
+	}
+
+	/// Adds a new version of the plugin that serves the calls `rollout` routes to it,
+	/// while this instance keeps serving the rest.
+	///
+	/// Use to upgrade a critical plugin gradually, widening the rollout as confidence
+	/// grows. Replicas and canaries of the canary itself are not used.
+	///
+	/// ```
+	/// # use wasm_link::{ CanaryRollout, Component, Engine, Linker, Plugin, PluginContext, ResourceTable };
+	/// # struct Context { table: ResourceTable }
+	/// # impl PluginContext for Context { fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.table } }
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// # let engine = Engine::default();
+	/// # let linker = Linker::new( &engine );
+	/// let stable = Component::new( &engine, "(component)" )?;
+	/// let candidate = Component::new( &engine, "(component)" )?;
+	/// let instantiate = | component | Plugin::new( component, Context { table: ResourceTable::new() })
+	/// 	.instantiate( &engine, &linker );
+	/// let plugin = instantiate( stable )?.with_canary( instantiate( candidate )?, CanaryRollout::percentage( 5 ));
+	/// # let _ = plugin ;
+	/// # Ok(()) }
+	/// ```
+	pub fn with_canary( mut self, canary: Self, rollout: CanaryRollout ) -> Self {
+		self.canary = Some( Box::new( Canary { instance: canary, rollout }));
+		self
+	}
+
+	#[allow( clippy::too_many_arguments )]
 	pub(crate) fn dispatch(
 		&mut self,
 		package_name: &str,
@@ -129,18 +327,64 @@ impl<Ctx: PluginContext + 'static> PluginInstanceSync<Ctx> {
 		function_name: &str,
 		function: &Function,
 		data: &[Val],
+		plugin_id: &( dyn Any + Send + Sync ),
+		context: &CallContext,
 	) -> Result<Val, DispatchError> {
-		self.state.dispatch( package_name, interface_name, function_name, function, data )
+		macro_rules! call {( $state: expr ) => {
+			$state.dispatch( package_name, interface_name, function_name, function, data, plugin_id, context )
+		}}
+		let Some( canary ) = self.canary.as_deref_mut().filter(| _ | function.is_reroutable() ) else {
+			return call!( self.state )
+		};
+		let to_canary = canary.rollout.routes_to_canary( context );
+		match ( canary.rollout.compares(), to_canary ) {
+			( false, true ) => call!( canary.instance.state ),
+			( false, false ) => call!( self.state ),
+			( true, _ ) => {
+				let stable = call!( self.state );
+				let candidate = call!( canary.instance.state );
+				canary.compare( package_name, interface_name, function_name, context, to_canary, &stable, &candidate );
+				match to_canary { true => candidate, false => stable }
+			}
+		}
+	}
+
+	pub(crate) fn store( &mut self ) -> StoreContextMut<'_, Ctx> {
+		self.state.store.as_context_mut()
+	}
+
+	/// Returns the type of the plugin's export of the given function.
+	#[cfg( feature = "wave" )]
+	pub(crate) fn function_type(
+		&mut self,
+		package_name: &str,
+		interface_name: &str,
+		function_name: &str,
+	) -> Result<wasmtime::component::types::ComponentFunc, DispatchError> {
+		let interface_path = format!( "{}/{}", package_name, interface_name );
+		let ( interface_path, function_name ) = self.state.resolve_export( &interface_path, package_name, interface_name, function_name );
+		let func = self.state.function( &interface_path, &function_name )?;
+		Ok( func.ty( &self.state.store ))
 	}
 }
 
 impl<Ctx: PluginContext + 'static> PluginInstanceAsync<Ctx> {
+	#[allow( clippy::too_many_arguments )]
 	pub(crate) fn new(
 		store: Store<Ctx>,
 		instance: Instance,
 		interface_remaps: HashMap<String, Remap>,
+		export_paths: ExportPaths,
 		fuel_limiter: Option<CallLimiter<Ctx>>,
 		epoch_limiter: Option<CallLimiter<Ctx>>,
+		limiter: Option<Box<dyn Limiter>>,
+		fault_injector: Option<FaultInjector>,
+		dispatch_observer: Option<DispatchObserver>,
+		payload_limits: Option<PayloadLimits>,
+		core_dumps: Option<CoreDumps>,
+		panic_policy: PanicPolicy,
+		epoch_tick: Option<Duration>,
+		wiring: PluginWiring,
 		executor: impl Spawn + Send + Sync + 'static,
 	) -> Self {
 		Self {
@@ -148,20 +392,102 @@ impl<Ctx: PluginContext + 'static> PluginInstanceAsync<Ctx> {
 				store,
 				instance,
 				interface_remaps,
+				export_paths,
 				fuel_limiter,
 				epoch_limiter,
+				limiter,
+				fault_injector,
+				dispatch_observer,
+				payload_limits,
+				core_dumps,
+				panic_policy,
+				epoch_tick,
+				hold_limit: wiring.hold_limit(),
+				panicked: None,
+				epoch_deadline: None,
 			})),
 			executor: Arc::new( executor ),
+			replicas: Vec::new(),
+			canary: None,
+			wiring: Arc::new( wiring ),
 		}
 	}
 
-	pub(crate) async fn dispatch_async(
+	/// Returns facts about the compiled component this instance was created from.
+	pub fn component_info( &self ) -> &ComponentInfo { &self.wiring.info }
+
+	/// Returns the fuel left in the plugin's store, waiting for a call in progress to
+	/// finish. See [`PluginInstanceSync::remaining_fuel`].
+	pub async fn remaining_fuel( &self ) -> Option<u64> { self.state.lock().await.store.get_fuel().ok() }
+
+	/// Returns the epoch deadline given to the latest call, waiting for a call in
+	/// progress to finish. See [`PluginInstanceSync::current_epoch_deadline`].
+	pub async fn current_epoch_deadline( &self ) -> Option<u64> { self.state.lock().await.epoch_deadline }
+
+	/// Adds instances of the same plugin that may serve calls to
+	/// [`concurrent`]( crate::Function::concurrent ) functions while this instance is busy.
+	///
+	/// See [`PluginInstanceSync::with_replicas`].
+	pub fn with_replicas( mut self, replicas: impl IntoIterator<Item = Self> ) -> Self {
+		self.replicas.extend( replicas.into_iter().flat_map( flatten_replicas ));
+		self
+	}
+
+	/// Adds a new version of the plugin that serves the calls `rollout` routes to it,
+	/// while this instance keeps serving the rest.
+	///
+	/// When comparing, both versions are invoked concurrently.
+	/// See [`PluginInstanceSync::with_canary`].
+	pub fn with_canary( mut self, canary: Self, rollout: CanaryRollout ) -> Self {
+		self.canary = Some( Box::new( Canary { instance: canary, rollout }));
+		self
+	}
+
+	#[allow( clippy::too_many_arguments )]
+	pub(crate) async fn dispatch_async<PluginId: Clone + Send + Sync + 'static>(
+		&self,
+		package_name: &str,
+		interface_name: &str,
+		function_name: &str,
+		function: &Function,
+		data: &[Val],
+		plugin_id: &PluginId,
+		context: &CallContext,
+	) -> Result<Val, DispatchError> {
+		macro_rules! call {( $instance: expr ) => {
+			$instance.submit( package_name, interface_name, function_name, function, data, plugin_id, context )
+		}}
+		let Some( canary ) = self.canary.as_deref().filter(| _ | function.is_reroutable() ) else {
+			return call!( self ).await
+		};
+		let to_canary = canary.rollout.routes_to_canary( context );
+		match ( canary.rollout.compares(), to_canary ) {
+			( false, true ) => call!( canary.instance ).await,
+			( false, false ) => call!( self ).await,
+			( true, _ ) => {
+				let ( stable, candidate ) = futures::future::join( call!( self ), call!( canary.instance )).await;
+				canary.compare( package_name, interface_name, function_name, context, to_canary, &stable, &candidate );
+				match to_canary { true => candidate, false => stable }
+			}
+		}
+	}
+
+	pub(crate) async fn with_store<R>( &self, f: impl FnOnce( StoreContextMut<'_, Ctx> ) -> R ) -> R {
+		let mut state = self.state.lock().await ;
+		f( state.store.as_context_mut() )
+	}
+
+	/// Runs the call on this instance's store, ignoring its canary.
+	#[allow( clippy::too_many_arguments )]
+	async fn submit<PluginId: Clone + Send + Sync + 'static>(
 		&self,
 		package_name: &str,
 		interface_name: &str,
 		function_name: &str,
 		function: &Function,
 		data: &[Val],
+		plugin_id: &PluginId,
+		context: &CallContext,
 	) -> Result<Val, DispatchError> {
 		let state = Arc::clone( &self.state );
 		let package_name = package_name.to_string();
@@ -169,6 +495,8 @@ impl<Ctx: PluginContext + 'static> PluginInstanceAsync<Ctx> {
 		let function_name = function_name.to_string();
 		let function = function.clone();
 		let data = data.to_vec();
+		let plugin_id = plugin_id.clone();
+		let context = context.clone();
 		let ( response, result ) = futures::channel::oneshot::channel();
 		let task: BoxFuture<'static, ()> = Box::pin( async move {
 			let result = state.lock().await.dispatch_async(
@@ -177,6 +505,8 @@ impl<Ctx: PluginContext + 'static> PluginInstanceAsync<Ctx> {
 				&function_name,
 				&function,
 				&data,
+				&plugin_id,
+				&context,
 			).await;
 			let _ = response.send( result );
 		});
@@ -187,10 +517,33 @@ impl<Ctx: PluginContext + 'static> PluginInstanceAsync<Ctx> {
 
 }
 
+impl<Ctx: 'static> Replicated for PluginInstanceSync<Ctx> {
+	fn take_replicas( &mut self ) -> Vec<Self> { std::mem::take( &mut self.replicas ) }
+}
+
+impl<Ctx: 'static> Replicated for PluginInstanceAsync<Ctx> {
+	fn take_replicas( &mut self ) -> Vec<Self> { std::mem::take( &mut self.replicas ) }
+}
+
+impl<Ctx: 'static> Wired for PluginInstanceSync<Ctx> {
+	fn wiring( &self ) -> Arc<PluginWiring> { Arc::clone( &self.wiring ) }
+}
+
+impl<Ctx: 'static> Wired for PluginInstanceAsync<Ctx> {
+	fn wiring( &self ) -> Arc<PluginWiring> { Arc::clone( &self.wiring ) }
+}
+
+fn flatten_replicas<Instance: Replicated>( mut instance: Instance ) -> Vec<Instance> {
+	let nested = instance.take_replicas();
+	std::iter::once( instance ).chain( nested ).collect()
+}
+
 impl<Ctx: PluginContext + 'static> PluginState<Ctx> {
 	const PLACEHOLDER_VAL: Val = Val::Option( None );
 	const VOID_RETURN_VAL: Val = Val::Option( None );
 
+	/// Calls `function`, catching panics of host closures as [`DispatchError::HostPanic`].
+	#[allow( clippy::too_many_arguments )]
 	fn dispatch(
 		&mut self,
 		package_name: &str,
@@ -198,15 +551,25 @@ impl<Ctx: PluginContext + 'static> PluginState<Ctx> {
 		function_name: &str,
 		function: &Function,
 		data: &[Val],
+		plugin_id: &( dyn Any + Send + Sync ),
+		context: &CallContext,
 	) -> Result<Val, DispatchError> {
-		ensure_supported_values( data )?;
-		let mut buffer = self.prepare_call( package_name, interface_name, function_name, function )?;
-		let ( exported_interface_path, exported_function_name ) = self.resolve_export( package_name, interface_name, function_name );
-		let func = self.function( &exported_interface_path, &exported_function_name )?;
-		let call_result = func.call( &mut self.store, data, &mut buffer );
-		Self::finish_call( function, buffer, call_result )
+		self.ensure_not_panicked()?;
+		let result = std::panic::catch_unwind( AssertUnwindSafe(|| self.call(
+			package_name,
+			interface_name,
+			function_name,
+			function,
+			data,
+			plugin_id,
+			context,
+		)));
+		self.store.data_mut().clear_call_context();
+		self.settle_panic( result )
 	}
 
+	/// The async counterpart of [`dispatch`](Self::dispatch).
+	#[allow( clippy::too_many_arguments )]
 	async fn dispatch_async(
 		&mut self,
 		package_name: &str,
@@ -214,43 +577,205 @@ impl<Ctx: PluginContext + 'static> PluginState<Ctx> {
 		function_name: &str,
 		function: &Function,
 		data: &[Val],
+		plugin_id: &( dyn Any + Send + Sync ),
+		context: &CallContext,
+	) -> Result<Val, DispatchError> {
+		self.ensure_not_panicked()?;
+		let result = AssertUnwindSafe( self.call_async(
+			package_name,
+			interface_name,
+			function_name,
+			function,
+			data,
+			plugin_id,
+			context,
+		)).catch_unwind().await ;
+		self.store.data_mut().clear_call_context();
+		self.settle_panic( result )
+	}
+
+	fn ensure_not_panicked( &self ) -> Result<(), DispatchError> {
+		match &self.panicked {
+			Some( message ) => Err( DispatchError::HostPanic( message.clone() )),
+			None => Ok(()),
+		}
+	}
+
+	/// Turns a caught panic into [`DispatchError::HostPanic`] and applies the panic policy.
+	fn settle_panic( &mut self, result: std::thread::Result<Result<Val, DispatchError>> ) -> Result<Val, DispatchError> {
+		let payload = match result {
+			Ok( result ) => return result,
+			Err( payload ) => payload,
+		};
+		let message = match ( payload.downcast_ref::<&str>(), payload.downcast_ref::<String>() ) {
+			( Some( message ), _ ) => ( *message ).to_string(),
+			( None, Some( message )) => message.clone(),
+			( None, None ) => "Box<dyn Any>".to_string(),
+		};
+		match self.panic_policy {
+			PanicPolicy::Propagate => std::panic::resume_unwind( payload ),
+			PanicPolicy::Continue => {},
+			PanicPolicy::Disable => self.panicked = Some( message.clone() ),
+		}
+		Err( DispatchError::HostPanic( message ))
+	}
+
+	#[allow( clippy::too_many_arguments )]
+	fn call(
+		&mut self,
+		package_name: &str,
+		interface_name: &str,
+		function_name: &str,
+		function: &Function,
+		data: &[Val],
+		plugin_id: &( dyn Any + Send + Sync ),
+		context: &CallContext,
 	) -> Result<Val, DispatchError> {
 		ensure_supported_values( data )?;
-		let mut buffer = self.prepare_call( package_name, interface_name, function_name, function )?;
-		let ( exported_interface_path, exported_function_name ) = self.resolve_export( package_name, interface_name, function_name );
+		self.check_payload( data )?;
+		let fault = self.inject_fault()?;
+		if let Some( Fault::Latency( delay )) = fault { std::thread::sleep( delay ); }
+		let interface_path = format!( "{}/{}", package_name, interface_name );
+		let call = CallInfo { interface_path: &interface_path, function_name, function, plugin_id, context };
+		let corruption = Self::corruption( fault.as_ref(), &call );
+		let mut buffer = self.prepare_call( &call )?;
+		let ( exported_interface_path, exported_function_name ) = self.resolve_export( &interface_path, package_name, interface_name, function_name );
 		let func = self.function( &exported_interface_path, &exported_function_name )?;
-		let call_result = func.call_async( &mut self.store, data, &mut buffer ).await;
-		Self::finish_call( function, buffer, call_result )
+		let fuel = self.store.get_fuel().ok();
+		let started = Instant::now();
+		let call_result = func.call( &mut self.store, data, &mut buffer );
+		self.report( &call, fuel, started, call_result.is_ok() );
+		if let ( Some( core_dumps ), Err( error )) = ( self.core_dumps.as_mut(), &call_result ) {
+			core_dumps.write( &mut self.store, &interface_path, function_name, error );
+		}
+		let result = Self::finish_call( function, buffer, call_result, corruption )?;
+		self.check_payload( std::slice::from_ref( &result ))?;
+		Ok( result )
 	}
 
-	fn prepare_call(
+	#[allow( clippy::too_many_arguments )]
+	async fn call_async(
 		&mut self,
 		package_name: &str,
 		interface_name: &str,
 		function_name: &str,
 		function: &Function,
-	) -> Result<Vec<Val>, DispatchError> {
-		let canonical_interface_path = format!( "{}/{}", package_name, interface_name );
-		if let Some( mut limiter ) = self.fuel_limiter.take() {
-			let fuel = limiter( &mut self.store, &canonical_interface_path, function_name, function );
-			self.fuel_limiter = Some( limiter );
+		data: &[Val],
+		plugin_id: &( dyn Any + Send + Sync ),
+		context: &CallContext,
+	) -> Result<Val, DispatchError> {
+		ensure_supported_values( data )?;
+		self.check_payload( data )?;
+		let fault = self.inject_fault()?;
+		if let Some( Fault::Latency( delay )) = fault { crate::fault::delay( delay ).await ; }
+		let interface_path = format!( "{}/{}", package_name, interface_name );
+		let call = CallInfo { interface_path: &interface_path, function_name, function, plugin_id, context };
+		let corruption = Self::corruption( fault.as_ref(), &call );
+		let mut buffer = self.prepare_call( &call )?;
+		let ( exported_interface_path, exported_function_name ) = self.resolve_export( &interface_path, package_name, interface_name, function_name );
+		let func = self.function( &exported_interface_path, &exported_function_name )?;
+		let fuel = self.store.get_fuel().ok();
+		let started = Instant::now();
+		let call_result = func.call_async( &mut self.store, data, &mut buffer ).await;
+		self.report( &call, fuel, started, call_result.is_ok() );
+		if let ( Some( core_dumps ), Err( error )) = ( self.core_dumps.as_mut(), &call_result ) {
+			core_dumps.write( &mut self.store, &interface_path, function_name, error );
+		}
+		let result = Self::finish_call( function, buffer, call_result, corruption )?;
+		self.check_payload( std::slice::from_ref( &result ))?;
+		Ok( result )
+	}
+
+	/// Draws the fault chosen by the fault injector, if any, failing the call for the
+	/// faults that skip the plugin.
+	fn inject_fault( &mut self ) -> Result<Option<Fault>, DispatchError> {
+		match self.fault_injector.as_mut().and_then( FaultInjector::next_fault ) {
+			Some( Fault::LockRejected ) => Err( DispatchError::LockRejected ),
+			Some( Fault::Trap( message )) => Err( DispatchError::RuntimeException( wasmtime::Error::msg( message ))),
+			fault => Ok( fault ),
+		}
+	}
+
+	/// Returns the value that should replace the call's result for [`Fault::Corrupt`].
+	fn corruption( fault: Option<&Fault>, call: &CallInfo<'_> ) -> Option<Val> {
+		match fault {
+			Some( Fault::Corrupt( corrupt )) => corrupt( call ),
+			_ => None,
+		}
+	}
+
+	fn check_payload( &self, values: &[Val] ) -> Result<(), DispatchError> {
+		self.payload_limits.as_ref().map_or( Ok(()), | limits | limits.check( values ))
+	}
+
+	fn prepare_call( &mut self, call: &CallInfo<'_> ) -> Result<Vec<Val>, DispatchError> {
+		self.store.data_mut().set_call_context( call.context.clone() );
+		let limits = self.limiter.as_mut().map_or_else( Limits::new, | limiter | limiter.limit( call ));
+		let fuel = call.context.fuel()
+			.or( limits.fuel() )
+			.or_else(|| self.fuel_limiter.as_mut().map(| limiter | limiter( &mut self.store, call )))
+			.or_else(|| call.function.default_fuel() );
+		if let Some( fuel ) = fuel {
 			self.store.set_fuel( fuel ).map_err( DispatchError::RuntimeException )?;
 		}
-		if let Some( mut limiter ) = self.epoch_limiter.take() {
-			let ticks = limiter( &mut self.store, &canonical_interface_path, function_name, function );
-			self.epoch_limiter = Some( limiter );
+		let ticks = call.context.epoch_deadline()
+			.or( limits.epoch_deadline() )
+			.or_else(|| self.epoch_limiter.as_mut().map(| limiter | limiter( &mut self.store, call )))
+			.or_else(|| call.function.default_epoch_deadline() );
+		let deadline = call.context.deadline().into_iter()
+			.chain( self.hold_limit.map(| limit | Instant::now() + limit ))
+			.min();
+		let ticks = match deadline {
+			Some( deadline ) => self.deadline_ticks( deadline )?.map_or( ticks, | remaining | Some( ticks.map_or( remaining, | ticks | ticks.min( remaining )))),
+			None => ticks,
+		};
+		if let Some( ticks ) = ticks {
 			self.store.set_epoch_deadline( ticks );
 		}
-		Ok( match function.return_kind() != ReturnKind::Void {
+		self.epoch_deadline = ticks ;
+		Ok( match call.function.return_kind() != ReturnKind::Void {
 			true => vec![ Self::PLACEHOLDER_VAL ],
 			false => Vec::with_capacity( 0 ),
 		})
 	}
 
+	/// Converts the time left until `deadline` into epoch ticks, if a watchdog
+	/// increments them, failing calls that start past it as interrupted.
+	fn deadline_ticks( &self, deadline: Instant ) -> Result<Option<u64>, DispatchError> {
+		let remaining = deadline.checked_duration_since( Instant::now() )
+			.filter(| remaining | !remaining.is_zero() )
+			.ok_or_else(|| DispatchError::RuntimeException( wasmtime::Error::new( wasmtime::Trap::Interrupt )))?;
+		Ok( self.epoch_tick.map(| tick | u64::try_from( remaining.as_nanos() / tick.as_nanos().max( 1 ))
+			.unwrap_or( u64::MAX )
+			.max( 1 )
+		))
+	}
+
+	/// Hands a [`DispatchReport`] of a finished call to the limiter and the dispatch
+	/// observer, if any.
+	fn report( &mut self, call: &CallInfo<'_>, fuel: Option<u64>, started: Instant, succeeded: bool ) {
+		let elapsed = started.elapsed();
+		let remaining_fuel = self.store.get_fuel().ok();
+		let report = DispatchReport {
+			call,
+			fuel,
+			fuel_consumed: fuel.zip( remaining_fuel ).map(|( fuel, remaining )| fuel.saturating_sub( remaining )),
+			elapsed,
+			succeeded,
+		};
+		if let Some( limiter ) = self.limiter.as_mut() { limiter.observe( &report ); }
+		if !call.context.is_observed() { return }
+		if let Some( observer ) = self.dispatch_observer.as_mut() { observer( &report ); }
+	}
+
 	fn function( &mut self, interface_path: &str, function_name: &str ) -> Result<wasmtime::component::Func, DispatchError> {
-		let interface_index = self.instance
-			.get_export_index( &mut self.store, None, interface_path )
-			.ok_or_else(|| DispatchError::InvalidInterfacePath( interface_path.to_string() ))?;
+		let interface_index = match self.export_paths.get( interface_path ) {
+			Some( path ) => path.iter().try_fold( None, | parent, name | self.instance
+				.get_export_index( &mut self.store, parent.as_ref(), name )
+				.map( Some )
+			).flatten(),
+			None => self.instance.get_export_index( &mut self.store, None, interface_path ),
+		}.ok_or_else(|| DispatchError::InvalidInterfacePath( interface_path.to_string() ))?;
 		let func_index = self.instance
 			.get_export_index( &mut self.store, Some( &interface_index ), function_name )
 			.ok_or_else(|| DispatchError::InvalidFunction( format!( "{interface_path}:{function_name}" )))?;
@@ -263,52 +788,65 @@ impl<Ctx: PluginContext + 'static> PluginState<Ctx> {
 		function: &Function,
 		mut buffer: Vec<Val>,
 		call_result: Result<(), wasmtime::Error>,
+		corruption: Option<Val>,
 	) -> Result<Val, DispatchError> {
 		call_result.map_err( DispatchError::RuntimeException )?;
 		let result = match function.return_kind() != ReturnKind::Void {
-			true => buffer.pop().ok_or( DispatchError::MissingResponse )?,
+			true => match ( buffer.pop().ok_or( DispatchError::MissingResponse )?, corruption ) {
+				( _, Some( corrupted )) => corrupted,
+				( result, None ) => result,
+			},
 			false => Self::VOID_RETURN_VAL,
 		};
 		ensure_supported_value( &result )?;
 		Ok( result )
 	}
 
-	fn resolve_export( &self, package_name: &str, interface_name: &str, function_name: &str ) -> (String, String) {
+	/// Resolves the export names of a function, borrowing the requested `interface_path`
+	/// unless the interface is remapped, so that unremapped calls allocate nothing.
+	fn resolve_export<'a>(
+		&self,
+		interface_path: &'a str,
+		package_name: &str,
+		interface_name: &str,
+		function_name: &'a str,
+	) -> ( Cow<'a, str>, Cow<'a, str> ) {
 		match self.interface_remaps.get( interface_name ) {
 			Some( remap ) => (
-				format!( "{}/{}", package_name, remap.interface_name( interface_name )),
-				remap.item_name( function_name ).to_string(),
-			),
-			None => (
-				format!( "{}/{}", package_name, interface_name ),
-				function_name.to_string(),
+				Cow::Owned( format!( "{}/{}", package_name, remap.interface_name( interface_name ))),
+				Cow::Owned( remap.item_name( function_name ).to_string() ),
 			),
+			None => ( Cow::Borrowed( interface_path ), Cow::Borrowed( function_name )),
 		}
 	}
 
 }
 
 fn ensure_supported_values( values: &[Val] ) -> Result<(), DispatchError> {
-	values.iter().try_for_each( ensure_supported_value )
+	let mut pending = values.iter().map(| value | ( value, 1 )).collect::<Vec<_>>();
+	while let Some(( value, depth )) = pending.pop() {
+		if depth > MAX_DEPTH {
+			return Err( DispatchError::PayloadTooLarge( format!( "value nested {depth} deep exceeds the cap of {MAX_DEPTH}" )))
+		}
+		match value {
+			Val::List( values ) | Val::Tuple( values ) => pending.extend( values.iter().map(| value | ( value, depth + 1 ))),
+			Val::Map( values ) => pending.extend( values.iter().flat_map(|( key, value )| [( key, depth + 1 ), ( value, depth + 1 )])),
+			Val::Record( values ) => pending.extend( values.iter().map(|( _, value )| ( value, depth + 1 ))),
+			Val::Variant( _, Some( value ))
+			| Val::Option( Some( value ))
+			| Val::Result( Ok( Some( value )))
+			| Val::Result( Err( Some( value ))) => pending.push(( value, depth + 1 )),
+			Val::Future( _ ) => return Err( DispatchError::UnsupportedType( "future".to_string() )),
+			Val::Stream( _ ) => return Err( DispatchError::UnsupportedType( "stream".to_string() )),
+			Val::ErrorContext( _ ) => return Err( DispatchError::UnsupportedType( "error-context".to_string() )),
+			_ => {},
+		}
+	}
+	Ok(())
 }
 
 fn ensure_supported_value( value: &Val ) -> Result<(), DispatchError> {
-	match value {
-		Val::List( values ) | Val::Tuple( values ) => ensure_supported_values( values ),
-		Val::Map( values ) => values.iter().try_for_each(|( key, value )| {
-			ensure_supported_value( key )?;
-			ensure_supported_value( value )
-		}),
-		Val::Record( values ) => values.iter().try_for_each(|( _, value )| ensure_supported_value( value )),
-		Val::Variant( _, Some( value ))
-		| Val::Option( Some( value ))
-		| Val::Result( Ok( Some( value )))
-		| Val::Result( Err( Some( value ))) => ensure_supported_value( value ),
-		Val::Future( _ ) => Err( DispatchError::UnsupportedType( "future".to_string() )),
-		Val::Stream( _ ) => Err( DispatchError::UnsupportedType( "stream".to_string() )),
-		Val::ErrorContext( _ ) => Err( DispatchError::UnsupportedType( "error-context".to_string() )),
-		_ => Ok(()),
-	}
+	ensure_supported_values( std::slice::from_ref( value ))
 }
 
 #[cfg(test)] mod tests { include!( "plugin_instance_tests.rs" ); }
@@ -2,7 +2,8 @@ use wasmtime::{ Config, Engine, Store };
 use wasmtime::component::{ Component, FutureReader, Linker, ResourceTable, StreamReader, Val };
 
 use super::ensure_supported_value ;
-use crate::{ DispatchError, PluginContext };
+use crate::linker::MAX_DEPTH ;
+use crate::{ CallContext, DispatchError, Function, FunctionKind, Plugin, PluginContext, ReturnKind };
 
 struct Context { table: ResourceTable }
 
@@ -10,6 +11,36 @@ impl PluginContext for Context {
 	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.table }
 }
 
+struct RecordingContext { table: ResourceTable, call_context: Option<CallContext> }
+
+impl PluginContext for RecordingContext {
+	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.table }
+	fn set_call_context( &mut self, context: CallContext ) { self.call_context = Some( context ); }
+	fn call_context( &self ) -> Option<&CallContext> { self.call_context.as_ref() }
+	fn clear_call_context( &mut self ) { self.call_context = None ; }
+}
+
+#[test]
+fn clears_the_call_context_after_each_call() -> Result<(), Box<dyn std::error::Error>> {
+	let engine = Engine::default();
+	let component = Component::from_file(
+		&engine,
+		concat!( env!( "CARGO_MANIFEST_DIR" ), "/tests/dispatching/dispatch_id/plugins/child/root.wat" ),
+	)?;
+	let mut instance = Plugin::new( component, RecordingContext { table: ResourceTable::new(), call_context: None })
+		.instantiate( &engine, &Linker::new( &engine ))?;
+	let function = Function::new( FunctionKind::Freestanding, ReturnKind::AssumeNoResources );
+
+	let result = instance.dispatch( "test:child", "root", "get-value", &function, &[], &(), &CallContext::new() );
+	assert!( matches!( result, Ok( Val::U32( 42 ))));
+	assert!( instance.store().data().call_context.is_none() );
+
+	let result = instance.dispatch( "test:child", "root", "missing", &function, &[], &(), &CallContext::new() );
+	assert!( matches!( result, Err( DispatchError::InvalidFunction( _ ))));
+	assert!( instance.store().data().call_context.is_none() );
+	Ok(())
+}
+
 #[test]
 fn accepts_nested_component_values() -> Result<(), DispatchError> {
 	let value = Val::Record( vec![
@@ -24,6 +55,16 @@ fn accepts_nested_component_values() -> Result<(), DispatchError> {
 	ensure_supported_value( &value )
 }
 
+#[test]
+fn rejects_values_nested_past_the_depth_cap() {
+	let nested = | depth: usize | ( 1..depth ).fold( Val::U8( 0 ), | value, _ | Val::List( vec![ value ]));
+	assert!( ensure_supported_value( &nested( MAX_DEPTH )).is_ok() );
+	assert!( matches!(
+		ensure_supported_value( &nested( MAX_DEPTH + 1 )),
+		Err( DispatchError::PayloadTooLarge( message )) if message == format!( "value nested {} deep exceeds the cap of {MAX_DEPTH}", MAX_DEPTH + 1 )
+	));
+}
+
 #[test]
 fn rejects_future_and_stream_values() -> Result<(), Box<dyn std::error::Error>> {
 	let mut config = Config::new();
@@ -71,3 +112,10 @@ fn rejects_error_context_values() -> Result<(), Box<dyn std::error::Error>> {
 		Ok(())
 	})
 }
+
+#[cfg( feature = "json" )]
+#[test]
+fn dispatch_errors_serialize_as_code_and_message() {
+	let error = DispatchError::InvalidFunction( "add".to_string() );
+	assert_eq!( serde_json::to_string( &error ).unwrap(), r#"{"code":"invalid-function","message":"Invalid Function: add"}"# );
+}
@@ -0,0 +1,394 @@
+use std::sync::{ Arc, Weak };
+use std::sync::atomic::{ AtomicBool, AtomicU64, Ordering };
+use std::time::{ Duration, Instant };
+use futures::lock::{ Mutex, MutexGuard };
+
+use crate::{ DispatchError, DispatchId, Function, LongHold };
+use crate::component_info::ComponentInfo ;
+
+
+
+/// Lock contention statistics of a single plugin within a [`Binding`]( crate::Binding ).
+///
+/// Returned by [`Binding::lock_stats`]( crate::Binding::lock_stats ). A plugin whose
+/// dispatches are often [`rejected`](Self::rejected) or spend a long time waiting is
+/// a hotspot that may be limiting the throughput of the whole binding.
+#[derive( Debug, Clone, Copy, PartialEq, Eq, Default )]
+pub struct LockStats {
+	/// Number of times the lock was acquired
+	pub acquisitions: u64,
+	/// Number of acquisitions that had to wait for another dispatch to finish
+	pub contended: u64,
+	/// Number of dispatches that failed with [`DispatchError::LockRejected`]( crate::DispatchError::LockRejected )
+	/// because the plugin was busy
+	pub rejected: u64,
+	/// Total time spent waiting for the lock
+	pub total_wait: Duration,
+	/// Longest time spent waiting for the lock
+	pub max_wait: Duration,
+	/// Histogram of wait times; bucket `i` counts acquisitions that waited less than
+	/// [`LockStats::BUCKET_BOUNDS`]`[i]`, the last bucket counts the rest
+	pub wait_histogram: [u64; LockStats::BUCKETS],
+}
+
+impl LockStats {
+	/// Number of buckets in [`wait_histogram`](Self::wait_histogram).
+	pub const BUCKETS: usize = 6 ;
+	/// Exclusive upper bounds of all but the last histogram bucket.
+	pub const BUCKET_BOUNDS: [Duration; LockStats::BUCKETS - 1] = [
+		Duration::from_micros( 10 ),
+		Duration::from_micros( 100 ),
+		Duration::from_millis( 1 ),
+		Duration::from_millis( 10 ),
+		Duration::from_millis( 100 ),
+	];
+}
+
+/// Plugin instances a [`Binding`]( crate::Binding ) can be created from, i.e.
+/// [`PluginInstanceSync`]( crate::PluginInstanceSync ) and
+/// [`PluginInstanceAsync`]( crate::PluginInstanceAsync ).
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait BindableInstance: Replicated + Wired {}
+
+impl<Instance: Replicated + Wired> BindableInstance for Instance {}
+
+pub(crate) use sealed::{ PluginWiring, Replicated, Wired };
+
+/// Items reachable through the bounds of [`BindableInstance`] but unnameable outside
+/// of this crate, which keeps it sealed.
+mod sealed {
+
+	use std::sync::Arc ;
+	use std::time::Duration ;
+
+	use crate::snapshot::{ GraphSnapshot, SnapshotError };
+	use crate::component_info::ComponentInfo ;
+	use super::{ HoldMonitors, LongHoldObserver };
+
+	/// Plugin instances that may carry replicas serving [`concurrent`]( crate::Function::concurrent )
+	/// calls.
+	///
+	/// A Wasmtime store needs exclusive access for every call, so shared locking of a
+	/// single instance is impossible; concurrency comes from a pool of instances instead.
+	pub trait Replicated: Sized {
+		/// Detaches the replicas added to this instance.
+		fn take_replicas( &mut self ) -> Vec<Self> ;
+	}
+
+	/// Plugin instances recording how they were created, for graph snapshots.
+	pub trait Wired {
+		/// Returns the component identity and sockets of the instance.
+		fn wiring( &self ) -> Arc<PluginWiring> ;
+	}
+
+	/// What an instance records about how it was created.
+	#[derive( Debug, Clone )]
+	pub struct PluginWiring {
+		pub(crate) component: Option<String>,
+		pub(crate) sockets: Result<GraphSnapshot, SnapshotError>,
+		pub(crate) host_provided: Vec<String>,
+		pub(crate) info: ComponentInfo,
+		pub(crate) long_hold: Option<LongHoldObserver>,
+		pub(crate) interrupt_long_holds: bool,
+		pub(crate) hold_monitors: Option<HoldMonitors>,
+	}
+
+	impl Default for PluginWiring {
+		fn default() -> Self { Self {
+			component: None,
+			sockets: Ok( GraphSnapshot::default() ),
+			host_provided: Vec::new(),
+			info: ComponentInfo::default(),
+			long_hold: None,
+			interrupt_long_holds: false,
+			hold_monitors: None,
+		}}
+	}
+
+	impl PluginWiring {
+
+		/// Returns how long a call may hold the instance before it is interrupted.
+		pub(crate) fn hold_limit( &self ) -> Option<Duration> {
+			self.long_hold.as_ref()
+				.filter(| _ | self.interrupt_long_holds )
+				.map(| long_hold | long_hold.threshold )
+		}
+
+	}
+
+}
+
+/// Notified when a dispatch finds the plugin held for at least `threshold`.
+#[derive( Clone )]
+pub(crate) struct LongHoldObserver {
+	pub(crate) threshold: Duration,
+	pub(crate) observer: Arc<dyn Fn( &LongHold ) + Send + Sync>,
+}
+
+impl std::fmt::Debug for LongHoldObserver {
+	fn fmt( &self, f: &mut std::fmt::Formatter<'_> ) -> std::fmt::Result {
+		f.debug_struct( "LongHoldObserver" ).field( "threshold", &self.threshold ).finish_non_exhaustive()
+	}
+}
+
+/// Locks whose long holds are looked for on every tick of a [`Watchdog`]( crate::limits::Watchdog ),
+/// so holds are reported while they last even if no other dispatch contends for the plugin.
+#[derive( Clone, Default )]
+pub(crate) struct HoldMonitors( Arc<std::sync::Mutex<Vec<Weak<dyn HoldMonitor>>>> );
+
+impl HoldMonitors {
+
+	pub(crate) fn register( &self, monitor: Weak<dyn HoldMonitor> ) {
+		self.0.lock().unwrap_or_else( std::sync::PoisonError::into_inner ).push( monitor );
+	}
+
+	/// Reports the long holds of every registered lock that is still alive, forgetting
+	/// the dropped ones.
+	pub(crate) fn scan( &self ) {
+		let monitors = {
+			let mut registered = self.0.lock().unwrap_or_else( std::sync::PoisonError::into_inner );
+			registered.retain(| monitor | monitor.strong_count() > 0 );
+			registered.iter().filter_map( Weak::upgrade ).collect::<Vec<_>>()
+		};
+		// Scanned after releasing the registry so a slow observer cannot delay registrations
+		for monitor in &monitors { monitor.report_long_holds(); }
+	}
+
+}
+
+impl std::fmt::Debug for HoldMonitors {
+	fn fmt( &self, f: &mut std::fmt::Formatter<'_> ) -> std::fmt::Result {
+		f.debug_struct( "HoldMonitors" ).finish_non_exhaustive()
+	}
+}
+
+/// A lock whose long holds can be reported from outside of a dispatch.
+pub(crate) trait HoldMonitor: Send + Sync {
+	/// Notifies the long hold observer of every instance held for at least its
+	/// threshold that has not been reported yet.
+	fn report_long_holds( &self );
+}
+
+impl<Instance: Send> HoldMonitor for PluginLock<Instance> {
+	fn report_long_holds( &self ) {
+		self.report_holds( &self.holders );
+	}
+}
+
+/// The dispatch currently holding an instance and since when.
+#[derive( Debug, Clone, Copy )]
+struct Hold {
+	dispatch_id: DispatchId,
+	since: Instant,
+	reported: bool,
+}
+
+/// Exclusive access to one of a plugin's instances, forgetting its holder on release.
+pub(crate) struct PluginGuard<'a, Instance> {
+	guard: MutexGuard<'a, Instance>,
+	holder: &'a std::sync::Mutex<Option<Hold>>,
+}
+
+impl<Instance> std::ops::Deref for PluginGuard<'_, Instance> {
+	type Target = Instance ;
+	fn deref( &self ) -> &Instance { &self.guard }
+}
+
+impl<Instance> std::ops::DerefMut for PluginGuard<'_, Instance> {
+	fn deref_mut( &mut self ) -> &mut Instance { &mut self.guard }
+}
+
+impl<Instance> Drop for PluginGuard<'_, Instance> {
+	fn drop( &mut self ) {
+		*self.holder.lock().unwrap_or_else( std::sync::PoisonError::into_inner ) = None ;
+	}
+}
+
+/// The lock serializing dispatches to a plugin, recording contention statistics.
+///
+/// Calls to functions allowing replicas go to whichever of the instance and its
+/// replicas is idle first; all other calls go to the instance itself.
+pub struct PluginLock<Instance> {
+	instance: Mutex<Instance>,
+	replicas: Vec<Mutex<Instance>>,
+	/// Holder of the instance followed by those of the replicas, in order
+	holders: Vec<std::sync::Mutex<Option<Hold>>>,
+	wiring: Arc<PluginWiring>,
+	enabled: AtomicBool,
+	acquisitions: AtomicU64,
+	contended: AtomicU64,
+	rejected: AtomicU64,
+	total_wait_nanos: AtomicU64,
+	max_wait_nanos: AtomicU64,
+	wait_histogram: [AtomicU64; LockStats::BUCKETS],
+}
+
+impl<Instance: std::fmt::Debug> std::fmt::Debug for PluginLock<Instance> {
+	fn fmt( &self, f: &mut std::fmt::Formatter<'_> ) -> std::fmt::Result {
+		self.instance.fmt( f )
+	}
+}
+
+impl<Instance> PluginLock<Instance> {
+
+	/// Wraps `instance` in a shared lock, registering it with the watchdog the plugin
+	/// was given, if any, when it has a long hold observer.
+	pub(crate) fn shared( instance: Instance ) -> Arc<Self> where Instance: Replicated + Wired + Send + 'static {
+		let lock = Arc::new( Self::new( instance ));
+		if let ( Some( monitors ), Some( _ )) = ( &lock.wiring.hold_monitors, &lock.wiring.long_hold ) {
+			let monitor: Weak<dyn HoldMonitor> = Arc::downgrade( &lock ) as Weak<PluginLock<Instance>>;
+			monitors.register( monitor );
+		}
+		lock
+	}
+
+	pub(crate) fn new( mut instance: Instance ) -> Self where Instance: Replicated + Wired {
+		let replicas: Vec<_> = instance.take_replicas().into_iter().map( Mutex::new ).collect();
+		Self {
+			wiring: instance.wiring(),
+			instance: Mutex::new( instance ),
+			holders: ( 0..=replicas.len() ).map(| _ | std::sync::Mutex::new( None )).collect(),
+			replicas,
+			enabled: AtomicBool::new( true ),
+			acquisitions: AtomicU64::new( 0 ),
+			contended: AtomicU64::new( 0 ),
+			rejected: AtomicU64::new( 0 ),
+			total_wait_nanos: AtomicU64::new( 0 ),
+			max_wait_nanos: AtomicU64::new( 0 ),
+			wait_histogram: Default::default(),
+		}
+	}
+
+	/// Acquires an instance able to serve `function` for `dispatch_id` without waiting.
+	///
+	/// # Errors
+	/// Fails with [`DispatchError::PluginDisabled`] if the plugin is disabled and with
+	/// [`DispatchError::LockRejected`] if all candidate instances are busy.
+	pub(crate) fn try_lock( &self, function: &Function, dispatch_id: DispatchId ) -> Result<PluginGuard<'_, Instance>, DispatchError> {
+		self.ensure_enabled()?;
+		if let Some(( index, guard )) = self.candidates( function ).enumerate().find_map(|( index, mutex )| Some(( index, mutex.try_lock()? ))) {
+			self.record_wait( Duration::ZERO, false );
+			return Ok( self.hold( index, guard, dispatch_id ))
+		}
+		self.rejected.fetch_add( 1, Ordering::Relaxed );
+		self.report_long_holds( function );
+		Err( DispatchError::LockRejected )
+	}
+
+	/// Acquires an instance able to serve `function` for `dispatch_id`, waiting for the
+	/// first of them to finish its current dispatch if necessary.
+	///
+	/// # Errors
+	/// Fails with [`DispatchError::PluginDisabled`] if the plugin is disabled.
+	pub(crate) async fn lock( &self, function: &Function, dispatch_id: DispatchId ) -> Result<PluginGuard<'_, Instance>, DispatchError> {
+		self.ensure_enabled()?;
+		if let Some(( index, guard )) = self.candidates( function ).enumerate().find_map(|( index, mutex )| Some(( index, mutex.try_lock()? ))) {
+			self.record_wait( Duration::ZERO, false );
+			return Ok( self.hold( index, guard, dispatch_id ))
+		}
+		self.report_long_holds( function );
+		let started = Instant::now();
+		let ( guard, index, _ ) = futures::future::select_all( self.candidates( function ).map( Mutex::lock )).await;
+		self.record_wait( started.elapsed(), true );
+		Ok( self.hold( index, guard, dispatch_id ))
+	}
+
+	/// Waits for the plugin's own instance to be idle, bypassing statistics, the
+	/// enabled flag and long hold reporting, so the host can inspect its store.
+	pub(crate) async fn inspect( &self ) -> MutexGuard<'_, Instance> {
+		self.instance.lock().await
+	}
+
+	fn hold<'a>( &'a self, index: usize, guard: MutexGuard<'a, Instance>, dispatch_id: DispatchId ) -> PluginGuard<'a, Instance> {
+		let holder = &self.holders[index];
+		*holder.lock().unwrap_or_else( std::sync::PoisonError::into_inner ) = Some( Hold { dispatch_id, since: Instant::now(), reported: false });
+		PluginGuard { guard, holder }
+	}
+
+	/// Reports the long holds of the instances able to serve `function`.
+	fn report_long_holds( &self, function: &Function ) {
+		self.report_holds( &self.holders[..=self.candidate_replicas( function ).len()] );
+	}
+
+	/// Notifies the long hold observer of every instance among `holders` held for at
+	/// least its threshold that has not been reported yet.
+	fn report_holds( &self, holders: &[std::sync::Mutex<Option<Hold>>] ) {
+		let Some( long_hold ) = &self.wiring.long_hold else { return };
+		let holds = holders.iter().filter_map(| holder | {
+			let mut holder = holder.lock().unwrap_or_else( std::sync::PoisonError::into_inner );
+			let hold = holder.as_mut()?;
+			let held_for = hold.since.elapsed();
+			if hold.reported || held_for < long_hold.threshold { return None }
+			hold.reported = true ;
+			Some( LongHold { dispatch_id: hold.dispatch_id, held_for })
+		}).collect::<Vec<_>>();
+		// Notified after releasing the holders so a slow observer cannot delay releases
+		for hold in &holds { ( long_hold.observer )( hold ); }
+	}
+
+	pub(crate) fn set_enabled( &self, enabled: bool ) {
+		self.enabled.store( enabled, Ordering::Relaxed );
+	}
+
+	pub(crate) fn is_enabled( &self ) -> bool {
+		self.enabled.load( Ordering::Relaxed )
+	}
+
+	fn ensure_enabled( &self ) -> Result<(), DispatchError> {
+		match self.is_enabled() {
+			true => Ok(()),
+			false => Err( DispatchError::PluginDisabled ),
+		}
+	}
+
+	fn candidates( &self, function: &Function ) -> impl Iterator<Item = &Mutex<Instance>> {
+		std::iter::once( &self.instance ).chain( self.candidate_replicas( function ))
+	}
+
+	fn candidate_replicas( &self, function: &Function ) -> &[Mutex<Instance>] {
+		match function.allows_replicas() {
+			true => self.replicas.as_slice(),
+			false => &[],
+		}
+	}
+
+	pub(crate) fn component_info( &self ) -> &ComponentInfo {
+		&self.wiring.info
+	}
+
+	pub(crate) fn wiring( &self ) -> Arc<PluginWiring> {
+		Arc::clone( &self.wiring )
+	}
+
+	pub(crate) fn stats( &self ) -> LockStats {
+		let mut wait_histogram = [0; LockStats::BUCKETS];
+		wait_histogram.iter_mut()
+			.zip( &self.wait_histogram )
+			.for_each(|( count, bucket )| *count = bucket.load( Ordering::Relaxed ));
+		LockStats {
+			acquisitions: self.acquisitions.load( Ordering::Relaxed ),
+			contended: self.contended.load( Ordering::Relaxed ),
+			rejected: self.rejected.load( Ordering::Relaxed ),
+			total_wait: Duration::from_nanos( self.total_wait_nanos.load( Ordering::Relaxed )),
+			max_wait: Duration::from_nanos( self.max_wait_nanos.load( Ordering::Relaxed )),
+			wait_histogram,
+		}
+	}
+
+	fn record_wait( &self, wait: Duration, contended: bool ) {
+		let nanos = u64::try_from( wait.as_nanos() ).unwrap_or( u64::MAX );
+		self.acquisitions.fetch_add( 1, Ordering::Relaxed );
+		if contended { self.contended.fetch_add( 1, Ordering::Relaxed ); }
+		self.total_wait_nanos.fetch_add( nanos, Ordering::Relaxed );
+		self.max_wait_nanos.fetch_max( nanos, Ordering::Relaxed );
+		let bucket = LockStats::BUCKET_BOUNDS.iter()
+			.position(| bound | wait < *bound )
+			.unwrap_or( LockStats::BUCKETS - 1 );
+		self.wait_histogram[bucket].fetch_add( 1, Ordering::Relaxed );
+	}
+
+}
+
+#[cfg(test)]
+mod tests { include!( "plugin_lock_tests.rs" ); }
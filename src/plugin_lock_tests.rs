@@ -0,0 +1,163 @@
+use std::time::Duration ;
+use std::sync::{ Arc, Mutex };
+use crate::{ CallContext, DispatchError, DispatchId, Function, FunctionKind, LongHold, ReturnKind };
+use crate::plugin_lock::{ LongHoldObserver, PluginWiring };
+use super::{ LockStats, PluginLock, Replicated, Wired };
+
+
+
+#[derive( Default )]
+struct Instance { id: usize, replicas: Vec<Instance>, wiring: Arc<PluginWiring> }
+
+impl Replicated for Instance {
+	fn take_replicas( &mut self ) -> Vec<Self> { std::mem::take( &mut self.replicas ) }
+}
+
+impl Wired for Instance {
+	fn wiring( &self ) -> Arc<PluginWiring> { Arc::clone( &self.wiring ) }
+}
+
+fn exclusive() -> Function {
+	Function::new( FunctionKind::Freestanding, ReturnKind::AssumeNoResources )
+}
+
+fn with_replicas( count: usize ) -> Instance {
+	Instance { id: 0, replicas: ( 1..=count ).map(| id | Instance { id, ..Instance::default() }).collect(), ..Instance::default() }
+}
+
+fn reporting_long_holds( threshold: Duration ) -> ( Instance, Arc<Mutex<Vec<LongHold>>> ) {
+	let holds = Arc::new( Mutex::new( Vec::new() ));
+	let reported = Arc::clone( &holds );
+	let observer = LongHoldObserver { threshold, observer: Arc::new( move | hold | reported.lock().unwrap().push( *hold )) };
+	let wiring = Arc::new( PluginWiring { long_hold: Some( observer ), ..PluginWiring::default() });
+	( Instance { wiring, ..Instance::default() }, holds )
+}
+
+fn dispatch() -> DispatchId {
+	CallContext::new().dispatch_id()
+}
+
+#[test]
+fn uncontended_acquisitions_land_in_the_first_bucket() {
+	let lock = PluginLock::new( Instance::default() );
+	drop( lock.try_lock( &exclusive(), dispatch() ));
+	drop( futures::executor::block_on( lock.lock( &exclusive(), dispatch() )));
+	let stats = lock.stats();
+	assert_eq!( stats.acquisitions, 2 );
+	assert_eq!( stats.contended, 0 );
+	assert_eq!( stats.wait_histogram, [ 2, 0, 0, 0, 0, 0 ]);
+}
+
+#[test]
+fn try_lock_on_a_busy_lock_is_rejected() {
+	let lock = PluginLock::new( Instance::default() );
+	let guard = lock.try_lock( &exclusive(), dispatch() );
+	assert!( guard.is_ok() );
+	assert!( matches!( lock.try_lock( &exclusive(), dispatch() ), Err( DispatchError::LockRejected )));
+	let stats = lock.stats();
+	assert_eq!( stats.acquisitions, 1 );
+	assert_eq!( stats.rejected, 1 );
+}
+
+#[test]
+fn waits_are_recorded_in_their_bucket() {
+	let lock = PluginLock::new( Instance::default() );
+	lock.record_wait( Duration::from_micros( 50 ), true );
+	lock.record_wait( Duration::from_millis( 5 ), true );
+	lock.record_wait( Duration::from_secs( 1 ), true );
+	let stats = lock.stats();
+	assert_eq!( stats.contended, 3 );
+	assert_eq!( stats.max_wait, Duration::from_secs( 1 ));
+	assert_eq!( stats.total_wait, Duration::from_secs( 1 ) + Duration::from_millis( 5 ) + Duration::from_micros( 50 ));
+	assert_eq!( stats.wait_histogram, [ 0, 1, 0, 1, 0, 1 ]);
+	assert_eq!( LockStats::BUCKET_BOUNDS.len() + 1, LockStats::BUCKETS );
+}
+
+#[test]
+fn waiting_for_a_busy_lock_is_contended() {
+	let lock = Arc::new( PluginLock::new( Instance::default() ));
+	let guard = lock.try_lock( &exclusive(), dispatch() ).expect( "lock is idle" );
+	let waiter = {
+		let lock = Arc::clone( &lock );
+		std::thread::spawn( move || drop( futures::executor::block_on( lock.lock( &exclusive(), dispatch() )).ok()))
+	};
+	std::thread::sleep( Duration::from_millis( 20 ));
+	drop( guard );
+	waiter.join().unwrap();
+	let stats = lock.stats();
+	assert_eq!( stats.acquisitions, 2 );
+	assert_eq!( stats.contended, 1 );
+	assert!( stats.max_wait >= Duration::from_millis( 10 ));
+}
+
+#[test]
+fn concurrent_calls_use_idle_replicas() {
+	let lock = PluginLock::new( with_replicas( 2 ));
+	let concurrent = exclusive().concurrent();
+	let first = lock.try_lock( &concurrent, dispatch() ).expect( "instance is idle" );
+	let second = lock.try_lock( &concurrent, dispatch() ).expect( "first replica is idle" );
+	let third = lock.try_lock( &concurrent, dispatch() ).expect( "second replica is idle" );
+	assert_eq!([ first.id, second.id, third.id ], [ 0, 1, 2 ]);
+	assert!( matches!( lock.try_lock( &concurrent, dispatch() ), Err( DispatchError::LockRejected )));
+	drop( second );
+	assert_eq!( futures::executor::block_on( lock.lock( &concurrent, dispatch() )).expect( "plugin is enabled" ).id, 1 );
+}
+
+#[test]
+fn exclusive_calls_never_use_replicas() {
+	let lock = PluginLock::new( with_replicas( 2 ));
+	let _guard = lock.try_lock( &exclusive(), dispatch() ).expect( "instance is idle" );
+	assert!( matches!( lock.try_lock( &exclusive(), dispatch() ), Err( DispatchError::LockRejected )));
+	let method = Function::new( FunctionKind::Method, ReturnKind::AssumeNoResources ).concurrent();
+	assert!( matches!( lock.try_lock( &method, dispatch() ), Err( DispatchError::LockRejected )));
+	let returns_resources = Function::new( FunctionKind::Freestanding, ReturnKind::MayContainResources ).concurrent();
+	assert!( matches!( lock.try_lock( &returns_resources, dispatch() ), Err( DispatchError::LockRejected )));
+}
+
+#[test]
+fn disabled_plugins_cannot_be_locked() {
+	let lock = PluginLock::new( with_replicas( 1 ));
+	lock.set_enabled( false );
+	assert!( matches!( lock.try_lock( &exclusive(), dispatch() ), Err( DispatchError::PluginDisabled )));
+	assert!( matches!( futures::executor::block_on( lock.lock( &exclusive(), dispatch() )), Err( DispatchError::PluginDisabled )));
+	assert_eq!( lock.stats(), LockStats::default() );
+	lock.set_enabled( true );
+	assert!( lock.try_lock( &exclusive(), dispatch() ).is_ok() );
+}
+
+#[test]
+fn contention_reports_a_long_hold_once() {
+	let ( instance, holds ) = reporting_long_holds( Duration::from_millis( 10 ));
+	let lock = PluginLock::new( instance );
+	let holder = dispatch();
+	let guard = lock.try_lock( &exclusive(), holder ).expect( "lock is idle" );
+	assert!( matches!( lock.try_lock( &exclusive(), dispatch() ), Err( DispatchError::LockRejected )));
+	assert!( holds.lock().unwrap().is_empty() );
+	std::thread::sleep( Duration::from_millis( 20 ));
+	assert!( matches!( lock.try_lock( &exclusive(), dispatch() ), Err( DispatchError::LockRejected )));
+	assert!( matches!( lock.try_lock( &exclusive(), dispatch() ), Err( DispatchError::LockRejected )));
+	let reported = holds.lock().unwrap().clone();
+	assert_eq!( reported.len(), 1 );
+	assert_eq!( reported[0].dispatch_id(), holder );
+	assert!( reported[0].held_for() >= Duration::from_millis( 20 ));
+	drop( guard );
+	let _guard = lock.try_lock( &exclusive(), dispatch() ).expect( "lock is idle" );
+	assert!( matches!( lock.try_lock( &exclusive(), dispatch() ), Err( DispatchError::LockRejected )));
+	assert_eq!( holds.lock().unwrap().len(), 1 );
+}
+
+#[test]
+fn waiting_for_a_long_held_lock_reports_it() {
+	let ( instance, holds ) = reporting_long_holds( Duration::ZERO );
+	let lock = Arc::new( PluginLock::new( instance ));
+	let holder = dispatch();
+	let guard = lock.try_lock( &exclusive(), holder ).expect( "lock is idle" );
+	let waiter = {
+		let lock = Arc::clone( &lock );
+		std::thread::spawn( move || drop( futures::executor::block_on( lock.lock( &exclusive(), dispatch() )).ok()))
+	};
+	while holds.lock().unwrap().is_empty() { std::thread::yield_now(); }
+	drop( guard );
+	waiter.join().unwrap();
+	assert_eq!( holds.lock().unwrap()[0].dispatch_id(), holder );
+}
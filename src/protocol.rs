@@ -0,0 +1,52 @@
+//! The names guests and the runtime agree on.
+//!
+//! Plugins are components, so allocation and memory exports are the canonical ABI's
+//! business and the runtime relies on no exports besides those of the plugs. What it
+//! does rely on is the `dispatch-error` variant of the `wasm-link:runtime` WIT package,
+//! the error type of socket responses, and the [shapes]( crate#core-concepts ) of those
+//! responses. Guest SDKs in other languages can be written against these constants and
+//! the bundled [`WIT`] instead of the runtime's source.
+//!
+//! ```
+//! use wasm_link::DispatchError ;
+//! use wasm_link::protocol::{ DISPATCH_ERROR, DISPATCH_ERROR_CASES, ERRORS_INTERFACE, PACKAGE, WIT };
+//!
+//! assert!( WIT.starts_with( &format!( "package {PACKAGE};" )));
+//! assert!( WIT.contains( &format!( "interface {ERRORS_INTERFACE} {{\n\tvariant {DISPATCH_ERROR} {{" )));
+//! assert!( DISPATCH_ERROR_CASES.contains( &DispatchError::LockRejected.code() ));
+//! ```
+
+/// The runtime's WIT package, versioned.
+pub const PACKAGE: &str = "wasm-link:runtime@0.5.0" ;
+
+/// The interface of [`PACKAGE`] defining [`DISPATCH_ERROR`].
+pub const ERRORS_INTERFACE: &str = "errors" ;
+
+/// The variant type socket responses report failed dispatches with.
+pub const DISPATCH_ERROR: &str = "dispatch-error" ;
+
+/// The cases of [`DISPATCH_ERROR`] in declaration order, one per
+/// [`code`]( crate::DispatchError::code ) a dispatch error may have.
+///
+/// Later versions of [`PACKAGE`] only ever append cases, and bump the package version
+/// when they do, since the component model matches variants exactly.
+pub const DISPATCH_ERROR_CASES: [&str; 15] = [
+	"lock-rejected",
+	"invalid-interface-path",
+	"invalid-function",
+	"missing-response",
+	"runtime-exception",
+	"invalid-argument-list",
+	"unsupported-type",
+	"executor-unavailable",
+	"resource-table-full",
+	"resource-handle-conversion-failed",
+	"invalid-resource-handle",
+	"plugin-disabled",
+	"payload-too-large",
+	"all-implementations-failed",
+	"host-panic",
+];
+
+/// The source of the WIT package defining [`DISPATCH_ERROR`], for guests to vendor.
+pub const WIT: &str = include_str!( "../wit/wasm-link.wit" );
@@ -1,12 +1,59 @@
+use std::any::Any ;
 use std::sync::Arc ;
 use thiserror::Error ;
-use wasmtime::component::{ Resource, ResourceAny, Val };
+use wasmtime::component::{ Resource, ResourceAny, ResourceType, Val };
 use wasmtime::StoreContextMut ;
 
 use crate::PluginContext ;
 
 
 
+/// What happened to a resource passed between plugins, as reported to
+/// [`PluginContext::on_resource_event`]( crate::PluginContext::on_resource_event ).
+#[derive( Debug, Clone, Copy, PartialEq, Eq )]
+pub enum ResourceEventKind {
+	/// A plugin's resource was wrapped into the table of the plugin receiving it.
+	Wrapped,
+	/// A resource that another plugin received and passed on entered the table of the
+	/// plugin receiving it; the receiving half of a transfer.
+	Received,
+	/// A plugin passed on an owned resource it had received, removing it from its
+	/// table; the sending half of a transfer.
+	Forwarded,
+	/// The plugin holding a wrapped resource dropped it, removing it from its table.
+	Dropped,
+}
+
+/// A change to the wrapped resources in a plugin's resource table.
+///
+/// Reported to the context owning the table, so tracking how many wrapped resources a
+/// plugin holds per owning plugin is enough to find leaks or enforce handle quotas.
+/// The table itself remains available to the context, e.g. for its size.
+#[derive( Debug )]
+pub struct ResourceEvent<'a> {
+	pub(crate) kind: ResourceEventKind,
+	pub(crate) plugin_id: &'a ( dyn Any + Send + Sync ),
+	pub(crate) resource_type: ResourceType,
+}
+
+impl ResourceEvent<'_> {
+
+	/// Returns what happened to the resource.
+	pub fn kind( &self ) -> ResourceEventKind { self.kind }
+
+	/// Returns the id of the plugin the resource belongs to, as given to the binding it
+	/// is plugged into.
+	///
+	/// Returns `None` if `PluginId` is not the binding's plugin id type.
+	pub fn plugin_id<PluginId: 'static>( &self ) -> Option<&PluginId> {
+		self.plugin_id.downcast_ref()
+	}
+
+	/// Returns the type of the resource within the plugin it belongs to.
+	pub fn resource_type( &self ) -> ResourceType { self.resource_type }
+
+}
+
 #[derive( Debug )]
 pub(crate) struct ResourceWrapper<Id> {
 	pub plugin_id: Id,
@@ -24,6 +71,14 @@ pub enum ResourceCreationError {
 	/// Failed to convert a stored resource into a host handle.
 	#[error( "Resource Handle Conversion Failed" )] ResourceHandleConversionFailed,
 }
+impl ResourceCreationError {
+	/// Returns a stable identifier of the error's kind, as for [`DispatchError::code`]( crate::DispatchError::code ).
+	pub fn code( &self ) -> &'static str { match self {
+		Self::ResourceTableFull => "resource-table-full",
+		Self::ResourceHandleConversionFailed => "resource-handle-conversion-failed",
+	}}
+}
+
 impl From<ResourceCreationError> for Val {
 	fn from( error: ResourceCreationError ) -> Self { match error {
 		ResourceCreationError::ResourceTableFull => Val::Variant( "resource-table-full".to_string(), None ),
@@ -41,6 +96,13 @@ pub enum ResourceReceiveError {
 	/// The handle doesn't correspond to any known resource (possibly already dropped or invalid).
 	#[error( "Invalid Handle" )] InvalidHandle,
 }
+impl ResourceReceiveError {
+	/// Returns a stable identifier of the error's kind, as for [`DispatchError::code`]( crate::DispatchError::code ).
+	pub fn code( &self ) -> &'static str { match self {
+		Self::InvalidHandle => "invalid-resource-handle",
+	}}
+}
+
 impl From<ResourceReceiveError> for Val {
 	fn from( error: ResourceReceiveError ) -> Self { match error {
 		ResourceReceiveError::InvalidHandle => Val::Variant( "invalid-resource-handle".to_string(), None ),
@@ -54,17 +116,25 @@ impl<Id: 'static + Send + Sync> ResourceWrapper<Id> {
 		Self { plugin_id, resource_handle }
 	}
 
-	/// Stores the wrapped resource in the host table and returns a handle.
+	/// Stores the wrapped resource in the host table and returns a handle, reporting
+	/// it as `kind` to the plugin context.
 	pub(crate) fn attach<Ctx: PluginContext>(
 		self,
 		store: &mut StoreContextMut<Ctx>,
+		kind: ResourceEventKind,
 	) -> Result<ResourceAny, ResourceCreationError> {
+		let wrapper = Arc::new( self );
 		let table = store.data_mut().resource_table();
-		let resource = table.push( Arc::new( self )).map_err(|_| ResourceCreationError::ResourceTableFull )?;
+		let resource = table.push( Arc::clone( &wrapper )).map_err(|_| ResourceCreationError::ResourceTableFull )?;
+		store.data_mut().on_resource_event( &wrapper.event( kind ));
 		ResourceAny::try_from_resource( resource, store )
 			.map_err(|_| ResourceCreationError::ResourceHandleConversionFailed )
 	}
 
+	fn event( &self, kind: ResourceEventKind ) -> ResourceEvent<'_> {
+		ResourceEvent { kind, plugin_id: &self.plugin_id, resource_type: self.resource_handle.ty() }
+	}
+
 	/// Looks up a wrapped resource by handle in the host resource table.
 	pub(crate) fn from_handle<'a, Ctx: PluginContext>(
 		handle: ResourceAny,
@@ -76,11 +146,35 @@ impl<Id: 'static + Send + Sync> ResourceWrapper<Id> {
 		Ok( wrapped )
 	}
 
+	/// Recovers the wrapper behind a handle a plugin returned without owning it, i.e. a
+	/// resource it received from another plugin and passes on. Returns `None` for the
+	/// plugin's own resources.
+	///
+	/// Owned handles leave the forwarding plugin, so their entry is removed from its table.
+	pub(crate) fn forwarded<Ctx: PluginContext>(
+		handle: ResourceAny,
+		store: &mut StoreContextMut<Ctx>,
+	) -> Option<Self> where Id: Clone {
+		if handle.ty() != ResourceType::host::<Arc<Self>>() { return None }
+		let resource = Resource::<Arc<Self>>::try_from_resource_any( handle, &mut *store ).ok()?;
+		let table = store.data_mut().resource_table();
+		let wrapped = match resource.owned() {
+			true => {
+				let wrapped = table.delete( resource ).ok()?;
+				store.data_mut().on_resource_event( &wrapped.event( ResourceEventKind::Forwarded ));
+				wrapped
+			}
+			false => Arc::clone( table.get( &resource ).ok()? ),
+		};
+		Some( Self::new( wrapped.plugin_id.clone(), wrapped.resource_handle ))
+	}
+
 	/// Drops a wrapped resource by handle from the host resource table.
 	pub(crate) fn drop<Ctx: PluginContext>( mut ctx: StoreContextMut<Ctx>, handle: u32 ) -> Result<(), wasmtime::Error> {
 		let resource = Resource::<Arc<Self>>::new_own( handle );
 		let table = ctx.data_mut().resource_table();
-		table.delete( resource ).map_err(|_| wasmtime::Error::new( ResourceReceiveError::InvalidHandle ))?;
+		let wrapper = table.delete( resource ).map_err(|_| wasmtime::Error::new( ResourceReceiveError::InvalidHandle ))?;
+		ctx.data_mut().on_resource_event( &wrapper.event( ResourceEventKind::Dropped ));
 		Ok(())
 	}
 
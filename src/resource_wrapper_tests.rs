@@ -1,24 +1,29 @@
 use wasmtime::{ AsContextMut, Engine, Store };
 use wasmtime::component::{ Resource, ResourceAny, ResourceTable };
 
-use super::ResourceWrapper ;
+use super::{ ResourceEvent, ResourceEventKind, ResourceWrapper };
 use crate::PluginContext ;
 
 
 
-struct Context { table: ResourceTable }
+struct Context { table: ResourceTable, events: Vec<( ResourceEventKind, Option<String> )> }
 
 impl PluginContext for Context {
 	fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.table }
+	fn on_resource_event( &mut self, event: &ResourceEvent<'_> ) {
+		self.events.push(( event.kind(), event.plugin_id::<String>().cloned() ));
+	}
 }
 
+fn context() -> Context { Context { table: ResourceTable::new(), events: Vec::new() } }
+
 #[test]
 fn attached_wrapper_can_be_looked_up_and_dropped() -> Result<(), wasmtime::Error> {
-	let mut store = Store::new( &Engine::default(), Context { table: ResourceTable::new() });
+	let mut store = Store::new( &Engine::default(), context() );
 	let resource = Resource::<u32>::new_own( 7 );
 	let resource = ResourceAny::try_from_resource( resource, &mut store )?;
 	let wrapper = ResourceWrapper::new( "plugin".to_string(), resource );
-	let handle = wrapper.attach( &mut store.as_context_mut() )?;
+	let handle = wrapper.attach( &mut store.as_context_mut(), ResourceEventKind::Wrapped )?;
 
 	{
 		let mut context = store.as_context_mut();
@@ -34,3 +39,24 @@ fn attached_wrapper_can_be_looked_up_and_dropped() -> Result<(), wasmtime::Error
 	assert!( ResourceWrapper::<String>::drop( store.as_context_mut(), typed.rep() ).is_err() );
 	Ok(())
 }
+
+#[test]
+fn lifecycle_events_name_the_owning_plugin() -> Result<(), wasmtime::Error> {
+	let mut store = Store::new( &Engine::default(), context() );
+	let resource = ResourceAny::try_from_resource( Resource::<u32>::new_own( 7 ), &mut store )?;
+	let handle = ResourceWrapper::new( "owner".to_string(), resource ).attach( &mut store.as_context_mut(), ResourceEventKind::Wrapped )?;
+	let wrapper = ResourceWrapper::<String>::forwarded( handle, &mut store.as_context_mut() ).expect( "handle is a wrapped resource" );
+	assert!( ResourceWrapper::<String>::forwarded( resource, &mut store.as_context_mut() ).is_none() );
+	let handle = wrapper.attach( &mut store.as_context_mut(), ResourceEventKind::Received )?;
+	let rep = Resource::<std::sync::Arc<ResourceWrapper<String>>>::try_from_resource_any( handle, &mut store )?.rep();
+	ResourceWrapper::<String>::drop( store.as_context_mut(), rep )?;
+
+	let owner = Some( "owner".to_string() );
+	assert_eq!( store.data().events, [
+		( ResourceEventKind::Wrapped, owner.clone() ),
+		( ResourceEventKind::Forwarded, owner.clone() ),
+		( ResourceEventKind::Received, owner.clone() ),
+		( ResourceEventKind::Dropped, owner ),
+	]);
+	Ok(())
+}
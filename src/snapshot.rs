@@ -0,0 +1,690 @@
+//! Declarative snapshots of a linked plugin graph.
+//!
+//! A [`GraphSnapshot`] records the wiring of a graph: the bindings with their interfaces
+//! and cardinalities, the plugins plugged into each binding, the component each plugin
+//! was created from, and the bindings each plugin was linked against. Runtime state such
+//! as stores and resources is not recorded.
+//!
+//! Snapshots are captured from a constructed graph with [`Binding::snapshot`]( crate::Binding::snapshot )
+//! and turned back into a graph with [`GraphSnapshot::load`], which asks a resolver for
+//! the plugin behind each entry. With the `json` or `toml` features enabled, snapshots
+//! can be written to and read from files, enabling config-driven deployments.
+//! [`GraphSnapshot::catalog`] flattens a snapshot into a [`Catalog`] of every function
+//! with its signature, to document a graph or serve it from an admin endpoint.
+
+use std::collections::{ BTreeMap, HashMap };
+use std::sync::Arc ;
+use thiserror::Error ;
+use wasmtime::Engine ;
+use wasmtime::component::{ Linker, Val };
+
+use crate::{ Binding, BindingAny, Function, Interface, Plugin, PluginContext, PluginInstanceSync };
+use crate::cardinality::{ Any, AtLeastOne, AtMostOne, ExactlyOne };
+use crate::plugin_lock::PluginWiring ;
+
+
+
+/// The number of plugins a binding accepts, mirroring the wrappers in [`cardinality`]( crate::cardinality ).
+#[derive( Debug, Clone, Copy, PartialEq, Eq, Hash )]
+#[cfg_attr( feature = "serde", derive( serde::Serialize, serde::Deserialize ), serde( rename_all = "kebab-case" ))]
+pub enum CardinalityKind {
+	/// [`ExactlyOne`]
+	ExactlyOne,
+	/// [`AtMostOne`]
+	AtMostOne,
+	/// [`AtLeastOne`]
+	AtLeastOne,
+	/// [`Any`]
+	Any,
+}
+
+/// The wiring of a plugin graph.
+///
+/// Bindings are keyed by package name, so each package may appear in a graph only once;
+/// bindings shared by several plugins are recorded once and shared again when loaded.
+///
+/// ```
+/// # use std::collections::HashMap ;
+/// # use wasm_link::{ Binding, Component, Engine, Linker, Plugin, PluginContext, ResourceTable };
+/// # use wasm_link::cardinality::ExactlyOne ;
+/// # use wasm_link::snapshot::CardinalityKind ;
+/// # struct Context { table: ResourceTable }
+/// # impl PluginContext for Context { fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.table } }
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let engine = Engine::default();
+/// let linker = Linker::new( &engine );
+/// let component = Component::new( &engine, "(component)" )?;
+/// let plugin = Plugin::new( component.clone(), Context { table: ResourceTable::new() })
+/// 	.with_component_hash( "sha256:1234" )
+/// 	.instantiate( &engine, &linker )?;
+/// let binding: Binding<String, Context> = Binding::new( "my:package", HashMap::new(), ExactlyOne( "plugin".to_string(), plugin ));
+///
+/// let snapshot = binding.snapshot()?;
+/// assert_eq!( snapshot.binding( "my:package" ).unwrap().cardinality(), CardinalityKind::ExactlyOne );
+///
+/// let roots = snapshot.load( &engine, &linker, | _package, plugin_id, _hash | {
+/// 	Ok(( plugin_id.to_string(), Plugin::new( component.clone(), Context { table: ResourceTable::new() })))
+/// })?;
+/// assert_eq!( roots.len(), 1 );
+/// # Ok(()) }
+/// ```
+#[derive( Debug, Clone, PartialEq, Eq, Default )]
+#[cfg_attr( feature = "serde", derive( serde::Serialize, serde::Deserialize ), serde( rename_all = "kebab-case" ))]
+pub struct GraphSnapshot {
+	/// Package names of the bindings the host dispatches to
+	roots: Vec<String>,
+	/// Every binding of the graph, keyed by package name
+	#[cfg_attr( feature = "serde", serde( default ))]
+	bindings: BTreeMap<String, BindingSnapshot>,
+}
+
+/// The wiring of a single binding within a [`GraphSnapshot`].
+#[derive( Debug, Clone, PartialEq, Eq )]
+#[cfg_attr( feature = "serde", derive( serde::Serialize, serde::Deserialize ), serde( rename_all = "kebab-case" ))]
+pub struct BindingSnapshot {
+	/// Number of plugins the binding accepts
+	cardinality: CardinalityKind,
+	/// Interfaces of the binding, keyed by name
+	#[cfg_attr( feature = "serde", serde( default ))]
+	interfaces: BTreeMap<String, Interface>,
+	/// Plugins plugged into the binding, keyed by plugin id
+	#[cfg_attr( feature = "serde", serde( default ))]
+	plugins: BTreeMap<String, PluginSnapshot>,
+}
+
+/// The wiring of a single plugin within a [`BindingSnapshot`].
+#[derive( Debug, Clone, PartialEq, Eq, Default )]
+#[cfg_attr( feature = "serde", derive( serde::Serialize, serde::Deserialize ), serde( rename_all = "kebab-case" ))]
+pub struct PluginSnapshot {
+	/// Identity of the plugin's component, as set with [`Plugin::with_component_hash`]
+	#[cfg_attr( feature = "serde", serde( default, skip_serializing_if = "Option::is_none" ))]
+	component: Option<String>,
+	/// Package names of the bindings the plugin was linked against
+	#[cfg_attr( feature = "serde", serde( default, skip_serializing_if = "Vec::is_empty" ))]
+	sockets: Vec<String>,
+	/// Socket interfaces the host linker already implemented, bypassing the sockets
+	#[cfg_attr( feature = "serde", serde( default, skip_serializing_if = "Vec::is_empty" ))]
+	host_provided: Vec<String>,
+	/// WIT signatures of the functions the plugin exports for the binding, keyed by
+	/// `interface#function`
+	#[cfg_attr( feature = "serde", serde( default, skip_serializing_if = "BTreeMap::is_empty" ))]
+	signatures: BTreeMap<String, String>,
+}
+
+/// A flat listing of every function a plugin graph exposes, for documentation, admin
+/// endpoints or generating client code.
+///
+/// Created with [`GraphSnapshot::catalog`]. Entries are ordered by package, interface and
+/// function name.
+#[derive( Debug, Clone, PartialEq, Eq, Default )]
+#[cfg_attr( feature = "serde", derive( serde::Serialize, serde::Deserialize ), serde( rename_all = "kebab-case" ))]
+pub struct Catalog {
+	/// Every function of every binding
+	functions: Vec<CatalogEntry>,
+}
+
+/// A single function within a [`Catalog`].
+#[derive( Debug, Clone, PartialEq, Eq )]
+#[cfg_attr( feature = "serde", derive( serde::Serialize, serde::Deserialize ), serde( rename_all = "kebab-case" ))]
+pub struct CatalogEntry {
+	/// Package name of the binding declaring the function
+	package: String,
+	/// Name of the interface declaring the function
+	interface: String,
+	/// Name of the function
+	function: String,
+	/// Number of plugins the binding accepts
+	cardinality: CardinalityKind,
+	/// Kind, return kind and default limits of the function
+	#[cfg_attr( feature = "serde", serde( flatten ))]
+	spec: Function,
+	/// WIT signature exported by the plugins, if known
+	#[cfg_attr( feature = "serde", serde( default, skip_serializing_if = "Option::is_none" ))]
+	signature: Option<String>,
+	/// Ids of the plugins implementing the function
+	#[cfg_attr( feature = "serde", serde( default ))]
+	plugins: Vec<String>,
+}
+
+/// Errors that occur when capturing a [`GraphSnapshot`].
+#[derive( Error, Debug, Clone, PartialEq, Eq )]
+pub enum SnapshotError {
+	/// Only plugin ids converting to a string, integer, char or bool [`Val`] can be recorded.
+	#[error( "Unsupported Plugin Id: {0}" )] UnsupportedPluginId( String ),
+	/// Two different bindings use the same package name.
+	#[error( "Conflicting Binding: {0}" )] ConflictingBinding( String ),
+}
+
+/// A structural problem found by [`GraphSnapshot::diagnostics`].
+///
+/// None of these prevent [`GraphSnapshot::load`] from succeeding, but they usually point
+/// at a mistake in a hand-written or merged snapshot.
+#[derive( Debug, Clone, PartialEq, Eq, Hash )]
+pub enum GraphDiagnostic {
+	/// A binding that is neither a root nor a socket of any plugin.
+	UnusedBinding( String ),
+	/// A plugin that cannot be reached from any root, so loading never resolves it.
+	UnreachablePlugin {
+		/// Package name of the plugin's binding
+		package: String,
+		/// Id of the plugin within the snapshot
+		plugin: String,
+	},
+	/// A socket referring to a package that is not in the snapshot.
+	MissingSocket {
+		/// Package name of the plugin's binding
+		package: String,
+		/// Id of the plugin within the snapshot
+		plugin: String,
+		/// Package name of the missing socket
+		socket: String,
+	},
+}
+
+impl std::fmt::Display for GraphDiagnostic {
+	fn fmt( &self, f: &mut std::fmt::Formatter<'_> ) -> std::fmt::Result {
+		match self {
+			Self::UnusedBinding( package ) => write!( f, "Unused Binding: {package}" ),
+			Self::UnreachablePlugin { package, plugin } => write!( f, "Unreachable Plugin: {plugin} of {package}" ),
+			Self::MissingSocket { package, plugin, socket } => write!( f, "Missing Socket: {socket} of plugin {plugin} of {package}" ),
+		}
+	}
+}
+
+/// Errors that occur when loading a graph from a [`GraphSnapshot`].
+///
+/// Like [`DispatchError`]( crate::DispatchError ), each error has a stable
+/// [`code`](Self::code) and serializes as `{ "code": .., "message": .. }` with the
+/// `serde` feature enabled.
+#[derive( Error, Debug )]
+pub enum GraphLoadError {
+	/// A root or socket refers to a package that is not in the snapshot.
+	#[error( "Missing Binding: {0}" )] MissingBinding( String ),
+	/// A binding depends on itself through the sockets of its plugins.
+	#[error( "Cyclic Binding: {0}" )] CyclicBinding( String ),
+	/// The number of plugins does not match the binding's cardinality.
+	#[error( "Cardinality Mismatch: {package} is {cardinality:?} but has {plugins} plugins" )]
+	CardinalityMismatch {
+		/// Package name of the binding
+		package: String,
+		/// Cardinality of the binding
+		cardinality: CardinalityKind,
+		/// Number of plugins in the snapshot
+		plugins: usize,
+	},
+	/// The resolver failed to provide a plugin.
+	#[error( "Failed to resolve plugin {plugin} of {package}: {source}" )]
+	Resolve {
+		/// Package name of the binding
+		package: String,
+		/// Id of the plugin within the snapshot
+		plugin: String,
+		/// Error returned by the resolver
+		source: wasmtime::Error,
+	},
+	/// Linking a resolved plugin failed.
+	#[error( "Failed to link plugin {plugin} of {package}: {source}" )]
+	Link {
+		/// Package name of the binding
+		package: String,
+		/// Id of the plugin within the snapshot
+		plugin: String,
+		/// Error returned by [`Plugin::link`]
+		source: wasmtime::Error,
+	},
+}
+
+impl GraphLoadError {
+
+	/// Returns a stable identifier of the error's kind, e.g. `"missing-binding"`.
+	pub fn code( &self ) -> &'static str { match self {
+		Self::MissingBinding( _ ) => "missing-binding",
+		Self::CyclicBinding( _ ) => "cyclic-binding",
+		Self::CardinalityMismatch { .. } => "cardinality-mismatch",
+		Self::Resolve { .. } => "resolve-failed",
+		Self::Link { .. } => "link-failed",
+	}}
+
+}
+
+#[cfg( feature = "serde" )]
+impl serde::Serialize for GraphLoadError {
+	fn serialize<S: serde::Serializer>( &self, serializer: S ) -> Result<S::Ok, S::Error> {
+		serialize_error( "GraphLoadError", self.code(), self, serializer )
+	}
+}
+
+impl GraphSnapshot {
+
+	/// Returns the package names of the root bindings.
+	pub fn roots( &self ) -> &[String] { &self.roots }
+
+	/// Returns the binding with the given package name.
+	pub fn binding( &self, package: &str ) -> Option<&BindingSnapshot> { self.bindings.get( package ) }
+
+	/// Returns every binding of the graph, keyed by package name.
+	pub fn bindings( &self ) -> &BTreeMap<String, BindingSnapshot> { &self.bindings }
+
+	/// Checks the snapshot for bindings nothing uses, plugins no root reaches and sockets
+	/// referring to missing bindings.
+	///
+	/// Snapshots captured with [`Binding::snapshot`]( crate::Binding::snapshot ) are always
+	/// clean; diagnostics are meant for snapshots written by hand or assembled with
+	/// [`merge`](Self::merge). They are returned in package order.
+	///
+	/// ```
+	/// # use std::collections::HashMap ;
+	/// # use wasm_link::{ Binding, Component, Engine, Linker, Plugin, PluginContext, ResourceTable };
+	/// # use wasm_link::cardinality::ExactlyOne ;
+	/// # struct Context { table: ResourceTable }
+	/// # impl PluginContext for Context { fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.table } }
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// # let engine = Engine::default();
+	/// # let linker = Linker::new( &engine );
+	/// # let plugin = Plugin::new( Component::new( &engine, "(component)" )?, Context { table: ResourceTable::new() })
+	/// # 	.instantiate( &engine, &linker )?;
+	/// let app: Binding<String, Context> = Binding::new( "my:app", HashMap::new(), ExactlyOne( "app".to_string(), plugin ));
+	/// let snapshot = app.snapshot()?;
+	/// snapshot.diagnostics().iter().for_each(| diagnostic | eprintln!( "{diagnostic}" ));
+	/// assert!( snapshot.diagnostics().is_empty() );
+	/// # Ok(()) }
+	/// ```
+	pub fn diagnostics( &self ) -> Vec<GraphDiagnostic> {
+		let mut reachable = std::collections::HashSet::new();
+		let mut pending = self.roots.iter().map( String::as_str ).collect::<Vec<_>>();
+		while let Some( package ) = pending.pop() {
+			if !reachable.insert( package ) { continue }
+			let Some( binding ) = self.bindings.get( package ) else { continue };
+			pending.extend( binding.plugins.values().flat_map(| plugin | plugin.sockets.iter().map( String::as_str )));
+		}
+
+		let used = self.bindings.values()
+			.flat_map(| binding | binding.plugins.values())
+			.flat_map(| plugin | plugin.sockets.iter().map( String::as_str ))
+			.chain( self.roots.iter().map( String::as_str ))
+			.collect::<std::collections::HashSet<_>>();
+
+		self.bindings.iter().flat_map(|( package, binding )| {
+			let unused = ( !used.contains( package.as_str() )).then(|| GraphDiagnostic::UnusedBinding( package.clone() ));
+			let unreachable = binding.plugins.keys()
+				.filter(| _ | !reachable.contains( package.as_str() ))
+				.map(| plugin | GraphDiagnostic::UnreachablePlugin { package: package.clone(), plugin: plugin.clone() });
+			let missing = binding.plugins.iter().flat_map(|( plugin, snapshot )| snapshot.sockets.iter()
+				.filter(| socket | !self.bindings.contains_key( *socket ))
+				.map(| socket | GraphDiagnostic::MissingSocket { package: package.clone(), plugin: plugin.clone(), socket: socket.clone() })
+			);
+			unused.into_iter().chain( unreachable ).chain( missing ).collect::<Vec<_>>()
+		}).collect()
+	}
+
+	/// Adds the bindings and roots of `other` to this snapshot.
+	///
+	/// Use to capture a graph with several roots.
+	///
+	/// # Errors
+	/// Returns [`SnapshotError::ConflictingBinding`] if both snapshots contain different
+	/// bindings with the same package name.
+	pub fn merge( &mut self, other: GraphSnapshot ) -> Result<(), SnapshotError> {
+		self.merge_bindings( other.bindings )?;
+		other.roots.into_iter().for_each(| root | if !self.roots.contains( &root ) { self.roots.push( root ) });
+		Ok(())
+	}
+
+	fn merge_bindings( &mut self, bindings: BTreeMap<String, BindingSnapshot> ) -> Result<(), SnapshotError> {
+		bindings.into_iter().try_for_each(|( package, binding )| match self.bindings.get( &package ) {
+			Some( existing ) if *existing != binding => Err( SnapshotError::ConflictingBinding( package )),
+			Some( _ ) => Ok(()),
+			None => { self.bindings.insert( package, binding ); Ok(()) }
+		})
+	}
+
+	pub(crate) fn capture(
+		package: &str,
+		cardinality: CardinalityKind,
+		interfaces: &HashMap<String, Interface>,
+		plugins: Vec<( Val, Arc<PluginWiring> )>,
+	) -> Result<Self, SnapshotError> {
+		let mut graph = Self::default();
+		let prefix = format!( "{package}/" );
+		let plugins = plugins.into_iter().map(|( id, wiring )| {
+			let sockets = wiring.sockets.clone()?;
+			let plugin = PluginSnapshot {
+				component: wiring.component.clone(),
+				sockets: sockets.roots.clone(),
+				host_provided: wiring.host_provided.clone(),
+				signatures: wiring.info.signatures().iter()
+					.filter_map(|( key, signature )| Some(( key.strip_prefix( &prefix )?.to_string(), signature.clone() )))
+					.collect(),
+			};
+			graph.merge_bindings( sockets.bindings )?;
+			Ok(( plugin_key( id )?, plugin ))
+		}).collect::<Result<_, SnapshotError>>()?;
+		let binding = BindingSnapshot {
+			cardinality,
+			interfaces: interfaces.iter().map(|( name, interface )| ( name.clone(), interface.clone() )).collect(),
+			plugins,
+		};
+		graph.merge_bindings( BTreeMap::from([( package.to_string(), binding )]))?;
+		graph.roots.push( package.to_string() );
+		Ok( graph )
+	}
+
+	pub(crate) fn capture_sockets<PluginId, Ctx, Instance>(
+		sockets: &[BindingAny<PluginId, Ctx, Instance>],
+	) -> Result<Self, SnapshotError>
+	where
+		PluginId: std::hash::Hash + Eq + Clone + Send + Sync + Into<Val> + 'static,
+		Ctx: PluginContext + 'static,
+		Instance: Send + 'static,
+	{
+		sockets.iter().try_fold( Self::default(), | mut graph, socket | {
+			graph.merge( socket.snapshot()? )?;
+			Ok( graph )
+		})
+	}
+
+	/// Reconstructs the graph, returning the root bindings in the order of [`roots`](Self::roots).
+	///
+	/// `resolve` is called once per plugin with the package name of its binding, its id
+	/// within the snapshot and its component hash, and returns the plugin id to use along
+	/// with the plugin to link. Plugins are linked against their recorded sockets, dependencies
+	/// first, and bindings shared in the snapshot are shared in the loaded graph. The component
+	/// hash is carried over to plugins that do not set their own.
+	///
+	/// # Errors
+	/// Returns an error if the snapshot is inconsistent, or if resolving or linking a plugin fails.
+	pub fn load<PluginId, Ctx>(
+		&self,
+		engine: &Engine,
+		linker: &Linker<Ctx>,
+		mut resolve: impl FnMut( &str, &str, Option<&str> ) -> Result<( PluginId, Plugin<Ctx> ), wasmtime::Error>,
+	) -> Result<Vec<BindingAny<PluginId, Ctx>>, GraphLoadError>
+	where
+		PluginId: std::hash::Hash + Eq + Clone + std::fmt::Debug + Send + Sync + Into<Val> + 'static,
+		Ctx: PluginContext + 'static,
+	{
+		let mut loader = Loader { snapshot: self, engine, linker, resolve: &mut resolve, loaded: HashMap::new(), visiting: Vec::new() };
+		self.roots.iter().map(| root | loader.load( root )).collect()
+	}
+
+	/// Lists every function the graph's bindings declare, with its cardinality, default
+	/// limits and the WIT signature its plugins export.
+	///
+	/// Signatures are recorded when a plugin is instantiated, so they are missing for
+	/// bindings without plugins and for snapshots written by hand. If the plugins of a
+	/// binding disagree, the signature of the first one by id is listed.
+	///
+	/// ```
+	/// # use std::collections::HashMap ;
+	/// # use wasm_link::{ Binding, Component, Engine, Interface, Linker, Plugin, PluginContext, ResourceTable, ReturnKind };
+	/// # use wasm_link::cardinality::ExactlyOne ;
+	/// # struct Context { table: ResourceTable }
+	/// # impl PluginContext for Context { fn resource_table( &mut self ) -> &mut ResourceTable { &mut self.table } }
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// # let engine = Engine::default();
+	/// # let linker = Linker::new( &engine );
+	/// # let plugin = Plugin::new( Component::new( &engine, "(component)" )?, Context { table: ResourceTable::new() })
+	/// # 	.instantiate( &engine, &linker )?;
+	/// let binding: Binding<String, Context> = Binding::new(
+	/// 	"my:package",
+	/// 	HashMap::from([( "api".to_string(), Interface::builder().function( "get", ReturnKind::AssumeNoResources ).build())]),
+	/// 	ExactlyOne( "plugin".to_string(), plugin ),
+	/// );
+	/// let catalog = binding.snapshot()?.catalog();
+	/// assert_eq!( catalog.functions()[0].function(), "get" );
+	/// assert_eq!( catalog.functions()[0].plugins(), [ "plugin".to_string() ]);
+	/// # Ok(()) }
+	/// ```
+	pub fn catalog( &self ) -> Catalog {
+		let functions = self.bindings.iter().flat_map(|( package, binding )| {
+			binding.interfaces.iter().flat_map( move |( interface, spec )| {
+				let mut functions = spec.functions().collect::<Vec<_>>();
+				functions.sort_by_key(|( name, _ )| *name );
+				functions.into_iter().map( move |( function, spec )| {
+					let key = format!( "{interface}#{function}" );
+					CatalogEntry {
+						package: package.clone(),
+						interface: interface.clone(),
+						function: function.to_string(),
+						cardinality: binding.cardinality,
+						spec,
+						signature: binding.plugins.values().find_map(| plugin | plugin.signatures.get( &key )).cloned(),
+						plugins: binding.plugins.keys().cloned().collect(),
+					}
+				})
+			})
+		}).collect();
+		Catalog { functions }
+	}
+
+	/// Writes the snapshot as pretty-printed JSON.
+	///
+	/// # Errors
+	/// Returns an error if serialization fails.
+	#[cfg( feature = "json" )]
+	pub fn to_json( &self ) -> Result<String, serde_json::Error> {
+		serde_json::to_string_pretty( self )
+	}
+
+	/// Reads a snapshot written by [`to_json`](Self::to_json).
+	///
+	/// # Errors
+	/// Returns an error if `json` is not a valid snapshot.
+	#[cfg( feature = "json" )]
+	pub fn from_json( json: &str ) -> Result<Self, serde_json::Error> {
+		serde_json::from_str( json )
+	}
+
+	/// Writes the snapshot as TOML.
+	///
+	/// # Errors
+	/// Returns an error if serialization fails.
+	#[cfg( feature = "toml" )]
+	pub fn to_toml( &self ) -> Result<String, toml::ser::Error> {
+		toml::to_string( self )
+	}
+
+	/// Reads a snapshot written by [`to_toml`](Self::to_toml).
+	///
+	/// # Errors
+	/// Returns an error if `toml` is not a valid snapshot.
+	#[cfg( feature = "toml" )]
+	pub fn from_toml( toml: &str ) -> Result<Self, toml::de::Error> {
+		toml::from_str( toml )
+	}
+
+}
+
+impl BindingSnapshot {
+
+	/// Returns the number of plugins the binding accepts.
+	pub fn cardinality( &self ) -> CardinalityKind { self.cardinality }
+
+	/// Returns the interfaces of the binding, keyed by name.
+	pub fn interfaces( &self ) -> &BTreeMap<String, Interface> { &self.interfaces }
+
+	/// Returns the plugins plugged into the binding, keyed by plugin id.
+	pub fn plugins( &self ) -> &BTreeMap<String, PluginSnapshot> { &self.plugins }
+
+}
+
+impl PluginSnapshot {
+
+	/// Returns the identity of the plugin's component, if it was set.
+	pub fn component( &self ) -> Option<&str> { self.component.as_deref() }
+
+	/// Returns the package names of the bindings the plugin was linked against.
+	pub fn sockets( &self ) -> &[String] { &self.sockets }
+
+	/// Returns the WIT paths of the socket interfaces the host linker already implemented,
+	/// so the plugin called the host's functions instead of the plugged plugins.
+	pub fn host_provided( &self ) -> &[String] { &self.host_provided }
+
+	/// Returns the WIT signatures of the functions the plugin exports for the binding,
+	/// keyed by `interface#function`.
+	pub fn signatures( &self ) -> &BTreeMap<String, String> { &self.signatures }
+
+}
+
+impl Catalog {
+
+	/// Returns the functions of the graph.
+	pub fn functions( &self ) -> &[CatalogEntry] { &self.functions }
+
+	/// Writes the catalog as pretty-printed JSON.
+	///
+	/// # Errors
+	/// Returns an error if serialization fails.
+	#[cfg( feature = "json" )]
+	pub fn to_json( &self ) -> Result<String, serde_json::Error> {
+		serde_json::to_string_pretty( self )
+	}
+
+}
+
+impl CatalogEntry {
+
+	/// Returns the package name of the binding declaring the function.
+	pub fn package( &self ) -> &str { &self.package }
+
+	/// Returns the name of the interface declaring the function.
+	pub fn interface( &self ) -> &str { &self.interface }
+
+	/// Returns the name of the function.
+	pub fn function( &self ) -> &str { &self.function }
+
+	/// Returns the number of plugins the binding accepts.
+	pub fn cardinality( &self ) -> CardinalityKind { self.cardinality }
+
+	/// Returns the function's kind, return kind and default limits.
+	pub fn spec( &self ) -> &Function { &self.spec }
+
+	/// Returns the WIT signature the plugins export, e.g. `func(a: u32) -> u32`, if known.
+	pub fn signature( &self ) -> Option<&str> { self.signature.as_deref() }
+
+	/// Returns the ids of the plugins implementing the function.
+	pub fn plugins( &self ) -> &[String] { &self.plugins }
+
+}
+
+type Resolver<'a, PluginId, Ctx> = dyn FnMut( &str, &str, Option<&str> ) -> Result<( PluginId, Plugin<Ctx> ), wasmtime::Error> + 'a ;
+
+struct Loader<'a, PluginId, Ctx>
+where
+	PluginId: std::hash::Hash + Eq + Clone + Send + Sync + 'static,
+	Ctx: PluginContext + 'static,
+{
+	snapshot: &'a GraphSnapshot,
+	engine: &'a Engine,
+	linker: &'a Linker<Ctx>,
+	resolve: &'a mut Resolver<'a, PluginId, Ctx>,
+	loaded: HashMap<String, BindingAny<PluginId, Ctx>>,
+	visiting: Vec<String>,
+}
+
+impl<PluginId, Ctx> Loader<'_, PluginId, Ctx>
+where
+	PluginId: std::hash::Hash + Eq + Clone + std::fmt::Debug + Send + Sync + Into<Val> + 'static,
+	Ctx: PluginContext + 'static,
+{
+
+	fn load( &mut self, package: &str ) -> Result<BindingAny<PluginId, Ctx>, GraphLoadError> {
+		if let Some( binding ) = self.loaded.get( package ) { return Ok( binding.clone() ) }
+		if self.visiting.iter().any(| visiting | visiting == package ) {
+			return Err( GraphLoadError::CyclicBinding( package.to_string() ))
+		}
+		let snapshot = self.snapshot.bindings.get( package )
+			.ok_or_else(|| GraphLoadError::MissingBinding( package.to_string() ))?;
+
+		self.visiting.push( package.to_string() );
+		let plugins = snapshot.plugins.iter()
+			.map(|( key, plugin )| self.load_plugin( package, key, plugin ))
+			.collect::<Result<Vec<_>, _>>()?;
+		self.visiting.pop();
+
+		let interfaces = snapshot.interfaces.iter().map(|( name, interface )| ( name.clone(), interface.clone() )).collect();
+		let mismatch = || GraphLoadError::CardinalityMismatch {
+			package: package.to_string(),
+			cardinality: snapshot.cardinality,
+			plugins: snapshot.plugins.len(),
+		};
+		let mut plugins = plugins.into_iter();
+		let binding = match snapshot.cardinality {
+			CardinalityKind::ExactlyOne => match ( plugins.next(), plugins.next() ) {
+				( Some(( id, plugin )), None ) => Binding::new( package, interfaces, ExactlyOne( id, plugin )).into_any(),
+				_ => return Err( mismatch() ),
+			},
+			CardinalityKind::AtMostOne => match ( plugins.next(), plugins.next() ) {
+				( plugin, None ) => Binding::new( package, interfaces, AtMostOne( plugin )).into_any(),
+				_ => return Err( mismatch() ),
+			},
+			CardinalityKind::AtLeastOne => match crate::NEMap::try_from_map( plugins.collect() ) {
+				Some( plugins ) => Binding::new( package, interfaces, AtLeastOne( plugins )).into_any(),
+				None => return Err( mismatch() ),
+			},
+			CardinalityKind::Any => Binding::new( package, interfaces, Any( plugins.collect() )).into_any(),
+		};
+		self.loaded.insert( package.to_string(), binding.clone() );
+		Ok( binding )
+	}
+
+	fn load_plugin(
+		&mut self,
+		package: &str,
+		key: &str,
+		snapshot: &PluginSnapshot,
+	) -> Result<( PluginId, PluginInstanceSync<Ctx> ), GraphLoadError> {
+		let sockets = snapshot.sockets.iter()
+			.map(| socket | self.load( socket ))
+			.collect::<Result<Vec<_>, _>>()?;
+		let ( id, mut plugin ) = ( self.resolve )( package, key, snapshot.component.as_deref() )
+			.map_err(| source | GraphLoadError::Resolve { package: package.to_string(), plugin: key.to_string(), source })?;
+		if let ( None, Some( hash )) = ( plugin.component_hash(), &snapshot.component ) {
+			plugin = plugin.with_component_hash( hash.clone() );
+		}
+		let instance = plugin.link( self.engine, self.linker.clone(), sockets )
+			.map_err(| source | GraphLoadError::Link { package: package.to_string(), plugin: key.to_string(), source })?;
+		Ok(( id, instance ))
+	}
+
+}
+
+fn plugin_key( id: Val ) -> Result<String, SnapshotError> {
+	Ok( match id {
+		Val::String( id ) => id,
+		Val::Char( id ) => id.to_string(),
+		Val::Bool( id ) => id.to_string(),
+		Val::U8( id ) => id.to_string(),
+		Val::U16( id ) => id.to_string(),
+		Val::U32( id ) => id.to_string(),
+		Val::U64( id ) => id.to_string(),
+		Val::S8( id ) => id.to_string(),
+		Val::S16( id ) => id.to_string(),
+		Val::S32( id ) => id.to_string(),
+		Val::S64( id ) => id.to_string(),
+		id => return Err( SnapshotError::UnsupportedPluginId( format!( "{id:?}" ))),
+	})
+}
+
+#[cfg( feature = "serde" )]
+pub(crate) fn sorted_map<S: serde::Serializer, V: serde::Serialize>( map: &HashMap<String, V>, serializer: S ) -> Result<S::Ok, S::Error> {
+	serializer.collect_map( map.iter().collect::<BTreeMap<_, _>>() )
+}
+
+/// Serializes an error as its code and displayed message.
+#[cfg( feature = "serde" )]
+pub(crate) fn serialize_error<S: serde::Serializer>( name: &'static str, code: &'static str, error: &impl std::fmt::Display, serializer: S ) -> Result<S::Ok, S::Error> {
+	use serde::ser::SerializeStruct ;
+	let mut state = serializer.serialize_struct( name, 2 )?;
+	state.serialize_field( "code", code )?;
+	state.serialize_field( "message", &error.to_string() )?;
+	state.end()
+}
+
+#[cfg( feature = "serde" )]
+pub(crate) fn sorted_set<S: serde::Serializer>( set: &std::collections::HashSet<String>, serializer: S ) -> Result<S::Ok, S::Error> {
+	serializer.collect_seq( set.iter().collect::<std::collections::BTreeSet<_>>() )
+}
+
+#[cfg(test)]
+mod tests { include!( "snapshot_tests.rs" ); }
@@ -0,0 +1,71 @@
+use std::collections::HashMap ;
+use wasmtime::component::Val ;
+use super::{ BindingSnapshot, CardinalityKind, GraphDiagnostic, GraphSnapshot, PluginSnapshot, SnapshotError, plugin_key };
+
+
+
+fn graph( package: &str, plugin: &str ) -> GraphSnapshot {
+	GraphSnapshot::capture( package, CardinalityKind::ExactlyOne, &HashMap::new(), vec![
+		( Val::String( plugin.to_string() ), std::sync::Arc::default() ),
+	]).unwrap()
+}
+
+#[test]
+fn primitive_plugin_ids_are_recorded() {
+	assert_eq!( plugin_key( Val::String( "a".to_string() )), Ok( "a".to_string() ));
+	assert_eq!( plugin_key( Val::U32( 7 )), Ok( "7".to_string() ));
+	assert_eq!( plugin_key( Val::S8( -1 )), Ok( "-1".to_string() ));
+	assert!( matches!( plugin_key( Val::List( Vec::new() )), Err( SnapshotError::UnsupportedPluginId( _ ))));
+}
+
+#[test]
+fn merging_keeps_identical_bindings_once() {
+	let mut snapshot = graph( "a:pkg", "one" );
+	snapshot.merge( graph( "a:pkg", "one" )).unwrap();
+	snapshot.merge( graph( "b:pkg", "two" )).unwrap();
+	assert_eq!( snapshot.roots(), [ "a:pkg".to_string(), "b:pkg".to_string() ]);
+	assert_eq!( snapshot.bindings().len(), 2 );
+}
+
+#[test]
+fn merging_different_bindings_with_the_same_package_fails() {
+	let mut snapshot = graph( "a:pkg", "one" );
+	assert_eq!( snapshot.merge( graph( "a:pkg", "two" )), Err( SnapshotError::ConflictingBinding( "a:pkg".to_string() )));
+}
+
+#[test]
+fn diagnostics_flag_unused_unreachable_and_missing_bindings() {
+	let binding = | sockets: &[&str] | BindingSnapshot {
+		cardinality: CardinalityKind::ExactlyOne,
+		interfaces: std::collections::BTreeMap::new(),
+		plugins: std::collections::BTreeMap::from([( "p".to_string(), PluginSnapshot {
+			component: None,
+			sockets: sockets.iter().map( ToString::to_string ).collect(),
+			host_provided: Vec::new(),
+			signatures: std::collections::BTreeMap::new(),
+		})]),
+	};
+	let snapshot = GraphSnapshot {
+		roots: vec![ "app:pkg".to_string() ],
+		bindings: std::collections::BTreeMap::from([
+			( "app:pkg".to_string(), binding( &[ "dep:pkg" ])),
+			( "dep:pkg".to_string(), binding( &[] )),
+			( "orphan:pkg".to_string(), binding( &[ "stray:pkg", "gone:pkg" ])),
+			( "stray:pkg".to_string(), binding( &[] )),
+		]),
+	};
+	assert_eq!( snapshot.diagnostics(), vec![
+		GraphDiagnostic::UnusedBinding( "orphan:pkg".to_string() ),
+		GraphDiagnostic::UnreachablePlugin { package: "orphan:pkg".to_string(), plugin: "p".to_string() },
+		GraphDiagnostic::MissingSocket { package: "orphan:pkg".to_string(), plugin: "p".to_string(), socket: "gone:pkg".to_string() },
+		GraphDiagnostic::UnreachablePlugin { package: "stray:pkg".to_string(), plugin: "p".to_string() },
+	]);
+}
+
+#[cfg( feature = "json" )]
+#[test]
+fn load_errors_serialize_as_code_and_message() {
+	let error = super::GraphLoadError::MissingBinding( "a:pkg".to_string() );
+	assert_eq!( error.code(), "missing-binding" );
+	assert_eq!( serde_json::to_string( &error ).unwrap(), r#"{"code":"missing-binding","message":"Missing Binding: a:pkg"}"# );
+}
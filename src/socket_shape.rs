@@ -0,0 +1,172 @@
+use std::collections::{ HashMap, HashSet };
+use thiserror::Error ;
+use wasmtime::Engine ;
+use wasmtime::component::types::{ self, ComponentItem, Type };
+
+use crate::{ FunctionKind, Interface };
+use crate::export_path ;
+use crate::snapshot::CardinalityKind ;
+
+
+
+/// Why a component's import of a socket interface does not match what the socket provides.
+///
+/// Returned, wrapped in a [`wasmtime::Error`], by [`Plugin::link`]( crate::Plugin::link )
+/// and [`Plugin::link_async`]( crate::Plugin::link_async ) before instantiation, in place
+/// of Wasmtime's generic instantiation errors or traps on the first call.
+#[derive( Error, Debug, Clone, PartialEq, Eq )]
+pub enum SocketShapeError {
+	/// The component imports the interface as something other than an instance.
+	#[error( "Not An Instance: {0} must be imported as an instance" )]
+	NotAnInstance( String ),
+	/// The component imports an item the binding does not declare.
+	#[error( "Undeclared Item: {interface} does not declare {item}" )]
+	UndeclaredItem {
+		/// WIT path of the imported interface
+		interface: String,
+		/// Name of the imported item
+		item: String,
+	},
+	/// The component expects a different result than the socket produces.
+	///
+	/// Freestanding functions return one value per plugin wrapped according to the
+	/// binding's cardinality, or a plain `result` for bindings with
+	/// [direct results]( crate::Binding::with_direct_results ); methods and targeted
+	/// functions return a `result`.
+	#[error( "Result Shape Mismatch: {interface}#{function} must return {expected}, found {found}" )]
+	ResultShape {
+		/// WIT path of the imported interface
+		interface: String,
+		/// Name of the imported function
+		function: String,
+		/// Outermost type the socket produces
+		expected: &'static str,
+		/// Outermost type the component declares
+		found: String,
+	},
+}
+
+/// Why a component's exports do not match the interfaces it was declared to implement.
+///
+/// Returned, wrapped in a [`wasmtime::Error`], when instantiating a plugin restricted
+/// with [`Plugin::with_strict_exports`]( crate::Plugin::with_strict_exports ), in place
+/// of an [`InvalidInterfacePath`]( crate::DispatchError::InvalidInterfacePath ) on the
+/// first dispatch.
+#[derive( Error, Debug, Clone, PartialEq, Eq )]
+pub enum ExportError {
+	/// The component exports an interface that was not declared, e.g. because of a
+	/// typo in its package name.
+	#[error( "Undeclared Export: {0} is not a declared interface" )]
+	Undeclared( String ),
+}
+
+/// Checks that every interface `component` exports is `declared`, in any version.
+pub(crate) fn check_exports(
+	component: &types::Component,
+	engine: &Engine,
+	declared: &HashSet<String>,
+) -> Result<(), ExportError> {
+	component.exports( engine )
+		.filter(|( _, item )| matches!( item.ty, ComponentItem::ComponentInstance( _ )))
+		.find(|( ident, _ )| !declared.contains( *ident ) && !declared.contains( export_path::unversioned( ident )))
+		.map_or( Ok(()), |( ident, _ )| Err( ExportError::Undeclared( ident.to_string() )))
+}
+
+/// Names of the items a component imports from each interface it imports as an instance.
+pub(crate) type Imports = HashMap<String, HashSet<String>>;
+
+/// Collects the items `component` imports, so sockets only define what is used.
+pub(crate) fn imports( component: &types::Component, engine: &Engine ) -> Imports {
+	component.imports( engine ).filter_map(|( ident, item )| match item.ty {
+		ComponentItem::ComponentInstance( instance ) => Some((
+			ident.to_string(),
+			instance.exports( engine ).map(|( name, _ )| name.to_string() ).collect(),
+		)),
+		_ => None,
+	}).collect()
+}
+
+/// Checks the component's import of each interface of a socket binding, if any.
+/// Interfaces the host implements natively are not checked.
+pub(crate) fn check(
+	component: &types::Component,
+	engine: &Engine,
+	package: &str,
+	interfaces: &HashMap<String, Interface>,
+	cardinality: CardinalityKind,
+	direct: bool,
+	host_provided: &[String],
+) -> Result<(), SocketShapeError> {
+	interfaces.iter().try_for_each(|( name, interface )| {
+		let ident = format!( "{package}/{name}" );
+		if host_provided.contains( &ident ) { return Ok(()) }
+		let Some( import ) = component.get_import( engine, &ident ) else { return Ok(()) };
+		let ComponentItem::ComponentInstance( instance ) = import.ty else {
+			return Err( SocketShapeError::NotAnInstance( ident ))
+		};
+		// Bound first so that the iterator is dropped before `instance`
+		let mut exports = instance.exports( engine );
+		exports.try_for_each(|( item, export )| {
+			let undeclared = || SocketShapeError::UndeclaredItem { interface: ident.clone(), item: item.to_string() };
+			match export.ty {
+				ComponentItem::ComponentFunc( func ) => {
+					let function = interface.function( item ).ok_or_else( undeclared )?;
+					let expected = match function.kind() {
+						FunctionKind::Freestanding => match cardinality {
+							CardinalityKind::ExactlyOne if direct => "result",
+							CardinalityKind::ExactlyOne => "tuple",
+							CardinalityKind::AtMostOne => "option",
+							CardinalityKind::AtLeastOne | CardinalityKind::Any => "map",
+						},
+						FunctionKind::Method | FunctionKind::Targeted => "result",
+					};
+					let results = func.results().collect::<Vec<_>>();
+					match results.as_slice() {
+						[ result ] if outermost( result ) == expected => Ok(()),
+						[ result ] => Err( SocketShapeError::ResultShape {
+							interface: ident.clone(), function: item.to_string(), expected, found: outermost( result ).to_string(),
+						}),
+						_ => Err( SocketShapeError::ResultShape {
+							interface: ident.clone(), function: item.to_string(), expected, found: format!( "{} results", results.len() ),
+						}),
+					}
+				},
+				ComponentItem::Resource( _ ) if interface.has_resource( item ) => Ok(()),
+				ComponentItem::Resource( _ ) => Err( undeclared() ),
+				_ => Ok(()),
+			}
+		})
+	})
+}
+
+pub(crate) fn outermost( ty: &Type ) -> &'static str {
+	match ty {
+		Type::Bool => "bool",
+		Type::S8 => "s8",
+		Type::U8 => "u8",
+		Type::S16 => "s16",
+		Type::U16 => "u16",
+		Type::S32 => "s32",
+		Type::U32 => "u32",
+		Type::S64 => "s64",
+		Type::U64 => "u64",
+		Type::Float32 => "float32",
+		Type::Float64 => "float64",
+		Type::Char => "char",
+		Type::String => "string",
+		Type::List( _ ) => "list",
+		Type::Map( _ ) => "map",
+		Type::Record( _ ) => "record",
+		Type::Tuple( _ ) => "tuple",
+		Type::Variant( _ ) => "variant",
+		Type::Enum( _ ) => "enum",
+		Type::Option( _ ) => "option",
+		Type::Result( _ ) => "result",
+		Type::Flags( _ ) => "flags",
+		Type::Own( _ ) => "own",
+		Type::Borrow( _ ) => "borrow",
+		Type::Future( _ ) => "future",
+		Type::Stream( _ ) => "stream",
+		Type::ErrorContext => "error-context",
+	}
+}
@@ -0,0 +1,500 @@
+//! Readable rendering and comparison of [`Val`] trees.
+//!
+//! `{:?}` on a nested [`Val`] prints every constructor on one line, which makes failed
+//! assertions on dispatch results hard to read. [`pretty`] renders values in a
+//! [WAVE]( crate::wave )-like syntax, indented once they nest, and [`diff`] lists only
+//! the places where two values differ.
+//!
+//! ```
+//! use wasm_link::Val ;
+//! use wasm_link::val::{ diff, pretty };
+//!
+//! let expected = Val::Record( vec![( "size".to_string(), Val::U32( 3 ))]);
+//! let actual = Val::Record( vec![( "size".to_string(), Val::U32( 4 ))]);
+//! assert_eq!( pretty( &expected ), "{ size: 3 }" );
+//! assert_eq!( diff( &expected, &actual )[0].to_string(), ".size: expected 3, found 4" );
+//! ```
+//!
+//! [`IntoVal`] and [`FromVal`] convert Rust values to and from [`Val`], so arguments
+//! and results need no hand-written mapping. They are implemented for scalars,
+//! strings, lists, options, results and tuples; hosts implement them for their own
+//! enums and records on top of those, with [`take_field`] for record fields.
+
+use std::fmt::Write ;
+use thiserror::Error ;
+use wasmtime::component::Val ;
+
+const INDENT: &str = "  ";
+
+
+
+/// Renders `value` for humans.
+///
+/// Composite values holding only scalars are kept on one line; all others put each
+/// element on its own, indented line. Resources, futures and streams cannot be
+/// inspected without their store and are shown as placeholders such as `own<resource>`.
+pub fn pretty( value: &Val ) -> String {
+	let mut out = String::new();
+	write( value, Some( 0 ), &mut out );
+	out
+}
+
+/// A single place where two values differ, as found by [`diff`].
+#[derive( Debug, Clone, PartialEq, Eq )]
+pub struct ValDifference {
+	path: String,
+	expected: Option<String>,
+	actual: Option<String>,
+}
+
+impl ValDifference {
+
+	/// Returns the path to the differing value from the root, e.g. `.items[2].name`,
+	/// or `.` for the root itself.
+	pub fn path( &self ) -> &str { &self.path }
+
+	/// Returns the expected value, or `None` if only the actual value has an element here.
+	pub fn expected( &self ) -> Option<&str> { self.expected.as_deref() }
+
+	/// Returns the actual value, or `None` if only the expected value has an element here.
+	pub fn actual( &self ) -> Option<&str> { self.actual.as_deref() }
+
+}
+
+impl std::fmt::Display for ValDifference {
+	fn fmt( &self, f: &mut std::fmt::Formatter<'_> ) -> std::fmt::Result {
+		let path = if self.path.is_empty() { "." } else { &self.path };
+		let expected = self.expected.as_deref().unwrap_or( "<missing>" );
+		let actual = self.actual.as_deref().unwrap_or( "<missing>" );
+		write!( f, "{path}: expected {expected}, found {actual}" )
+	}
+}
+
+/// Lists the places where `actual` differs from `expected`, descending into composite
+/// values of the same shape. Returns an empty list if the values are equal.
+///
+/// Record fields are matched by name and map entries by key, so a single wrong field
+/// is reported as such rather than as a difference of the whole record.
+pub fn diff( expected: &Val, actual: &Val ) -> Vec<ValDifference> {
+	let mut differences = Vec::new();
+	diff_at( String::new(), Some( expected ), Some( actual ), &mut differences );
+	differences
+}
+
+fn diff_at( path: String, expected: Option<&Val>, actual: Option<&Val>, differences: &mut Vec<ValDifference> ) {
+	let ( expected, actual ) = match ( expected, actual ) {
+		( Some( expected ), Some( actual )) => ( expected, actual ),
+		( None, None ) => return,
+		( expected, actual ) => return differences.push( ValDifference {
+			path,
+			expected: expected.map( inline ),
+			actual: actual.map( inline ),
+		}),
+	};
+	match ( expected, actual ) {
+		( Val::List( expected ), Val::List( actual )) | ( Val::Tuple( expected ), Val::Tuple( actual )) => {
+			( 0..expected.len().max( actual.len() )).for_each(| index | diff_at(
+				format!( "{path}[{index}]" ), expected.get( index ), actual.get( index ), differences,
+			));
+		},
+		( Val::Record( expected ), Val::Record( actual )) => {
+			let names = expected.iter().map(|( name, _ )| name )
+				.chain( actual.iter().map(|( name, _ )| name ).filter(| name | field( expected, name ).is_none() ));
+			names.for_each(| name | diff_at(
+				format!( "{path}.{name}" ), field( expected, name ), field( actual, name ), differences,
+			));
+		},
+		( Val::Map( expected ), Val::Map( actual )) => {
+			let keys = expected.iter().map(|( key, _ )| key )
+				.chain( actual.iter().map(|( key, _ )| key ).filter(| key | entry( expected, key ).is_none() ));
+			let mut seen = Vec::new();
+			keys.filter(| key | if seen.contains( key ) { false } else { seen.push( *key ); true })
+				.for_each(| key | diff_at(
+					format!( "{path}[{}]", inline( key )), entry( expected, key ), entry( actual, key ), differences,
+				));
+		},
+		( Val::Option( Some( expected )), Val::Option( Some( actual ))) =>
+			diff_at( format!( "{path}.some" ), Some( expected ), Some( actual ), differences ),
+		( Val::Result( Ok( Some( expected ))), Val::Result( Ok( Some( actual )))) =>
+			diff_at( format!( "{path}.ok" ), Some( expected ), Some( actual ), differences ),
+		( Val::Result( Err( Some( expected ))), Val::Result( Err( Some( actual )))) =>
+			diff_at( format!( "{path}.err" ), Some( expected ), Some( actual ), differences ),
+		( Val::Variant( expected_case, Some( expected )), Val::Variant( actual_case, Some( actual ))) if expected_case == actual_case =>
+			diff_at( format!( "{path}.{expected_case}" ), Some( expected ), Some( actual ), differences ),
+		( expected, actual ) if expected != actual => differences.push( ValDifference {
+			path,
+			expected: Some( inline( expected )),
+			actual: Some( inline( actual )),
+		}),
+		_ => {},
+	}
+}
+
+fn field<'a>( fields: &'a [( String, Val )], name: &str ) -> Option<&'a Val> {
+	fields.iter().find(|( field, _ )| field == name ).map(|( _, value )| value )
+}
+
+/// Finds the value of `key`, the last one winning as in the Component Model.
+fn entry<'a>( entries: &'a [( Val, Val )], key: &Val ) -> Option<&'a Val> {
+	entries.iter().rev().find(|( entry, _ )| entry == key ).map(|( _, value )| value )
+}
+
+/// Renders `value` on a single line.
+fn inline( value: &Val ) -> String {
+	let mut out = String::new();
+	write( value, None, &mut out );
+	out
+}
+
+/// Writes `value` indented at `depth`, or on a single line if `depth` is `None`.
+fn write( value: &Val, depth: Option<usize>, out: &mut String ) {
+	match value {
+		Val::Bool( value ) => out.push_str( &value.to_string() ),
+		Val::S8( value ) => out.push_str( &value.to_string() ),
+		Val::U8( value ) => out.push_str( &value.to_string() ),
+		Val::S16( value ) => out.push_str( &value.to_string() ),
+		Val::U16( value ) => out.push_str( &value.to_string() ),
+		Val::S32( value ) => out.push_str( &value.to_string() ),
+		Val::U32( value ) => out.push_str( &value.to_string() ),
+		Val::S64( value ) => out.push_str( &value.to_string() ),
+		Val::U64( value ) => out.push_str( &value.to_string() ),
+		Val::Float32( value ) => out.push_str( &value.to_string() ),
+		Val::Float64( value ) => out.push_str( &value.to_string() ),
+		// Writing to a `String` cannot fail
+		Val::Char( value ) => { let _ = write!( out, "{value:?}" ); },
+		Val::String( value ) => { let _ = write!( out, "{value:?}" ); },
+		Val::Enum( case ) => out.push_str( case ),
+		Val::Flags( flags ) => { let _ = write!( out, "{{{}}}", flags.join( ", " )); },
+		Val::List( items ) => write_items( "[", "]", items.iter().map(| item | ( None, item )), depth, out ),
+		Val::Tuple( items ) => write_items( "(", ")", items.iter().map(| item | ( None, item )), depth, out ),
+		Val::Record( fields ) => write_items( "{ ", " }", fields.iter().map(|( name, value )| ( Some( name.clone() ), value )), depth, out ),
+		Val::Map( entries ) => write_items( "{ ", " }", entries.iter().map(|( key, value )| ( Some( format!( "{} =>", inline( key ))), value )), depth, out ),
+		Val::Option( None ) => out.push_str( "none" ),
+		Val::Option( Some( value )) => write_case( "some", Some( value ), depth, out ),
+		Val::Result( Ok( value )) => write_case( "ok", value.as_deref(), depth, out ),
+		Val::Result( Err( value )) => write_case( "err", value.as_deref(), depth, out ),
+		Val::Variant( case, value ) => write_case( case, value.as_deref(), depth, out ),
+		Val::Resource( resource ) if resource.owned() => out.push_str( "own<resource>" ),
+		Val::Resource( _ ) => out.push_str( "borrow<resource>" ),
+		Val::Future( _ ) => out.push_str( "<future>" ),
+		Val::Stream( _ ) => out.push_str( "<stream>" ),
+		Val::ErrorContext( _ ) => out.push_str( "<error-context>" ),
+	}
+}
+
+fn write_case( case: &str, value: Option<&Val>, depth: Option<usize>, out: &mut String ) {
+	out.push_str( case );
+	if let Some( value ) = value {
+		out.push( '(' );
+		write( value, depth, out );
+		out.push( ')' );
+	}
+}
+
+fn write_items<'a>(
+	open: &str,
+	close: &str,
+	items: impl ExactSizeIterator<Item = ( Option<String>, &'a Val )> + Clone,
+	depth: Option<usize>,
+	out: &mut String,
+) {
+	if items.len() == 0 {
+		out.push_str( open.trim_end() );
+		out.push_str( close.trim_start() );
+		return
+	}
+	let label = | name: Option<String> | name.map( | name | match name.ends_with( "=>" ) {
+		true => format!( "{name} " ),
+		false => format!( "{name}: " ),
+	}).unwrap_or_default();
+	match depth.filter(| _ | !items.clone().all(|( _, value )| is_scalar( value ))) {
+		None => {
+			out.push_str( open );
+			items.enumerate().for_each(| ( index, ( name, value )) | {
+				if index > 0 { out.push_str( ", " ); }
+				out.push_str( &label( name ));
+				write( value, depth, out );
+			});
+			out.push_str( close );
+		},
+		Some( depth ) => {
+			out.push_str( open.trim_end() );
+			out.push( '\n' );
+			items.for_each(|( name, value )| {
+				out.push_str( &INDENT.repeat( depth + 1 ));
+				out.push_str( &label( name ));
+				write( value, Some( depth + 1 ), out );
+				out.push_str( ",\n" );
+			});
+			out.push_str( &INDENT.repeat( depth ));
+			out.push_str( close.trim_start() );
+		},
+	}
+}
+
+fn is_scalar( value: &Val ) -> bool {
+	match value {
+		Val::List( items ) | Val::Tuple( items ) => items.is_empty(),
+		Val::Record( fields ) => fields.is_empty(),
+		Val::Map( entries ) => entries.is_empty(),
+		Val::Option( Some( value )) | Val::Result( Ok( Some( value )) | Err( Some( value ))) | Val::Variant( _, Some( value )) => is_scalar( value ),
+		_ => true,
+	}
+}
+
+/// Conversion of a Rust value into a [`Val`].
+///
+/// ```
+/// use wasm_link::Val ;
+/// use wasm_link::val::{ FromVal, FromValError, IntoVal, take_field };
+///
+/// enum Color { Red, Green }
+///
+/// impl IntoVal for Color {
+/// 	fn into_val( self ) -> Val {
+/// 		Val::Enum( match self { Self::Red => "red", Self::Green => "green" }.to_string() )
+/// 	}
+/// }
+///
+/// struct Point { x: u32, y: u32 }
+///
+/// impl FromVal for Point {
+/// 	fn from_val( value: Val ) -> Result<Self, FromValError> {
+/// 		let Val::Record( mut fields ) = value else { return Err( FromValError::mismatch( "record", &value )) };
+/// 		Ok( Self { x: take_field( &mut fields, "x" )?, y: take_field( &mut fields, "y" )? })
+/// 	}
+/// }
+///
+/// assert_eq!( Color::Green.into_val(), Val::Enum( "green".to_string() ));
+/// assert_eq!( vec![ 1u32, 2 ].into_val(), Val::List( vec![ Val::U32( 1 ), Val::U32( 2 )]));
+/// let point = Point::from_val( Val::Record( vec![
+/// 	( "x".to_string(), Val::U32( 1 )),
+/// 	( "y".to_string(), Val::U32( 2 )),
+/// ]))?;
+/// assert_eq!(( point.x, point.y ), ( 1, 2 ));
+/// # Ok::<(), FromValError>(())
+/// ```
+pub trait IntoVal {
+	/// Converts `self` into a [`Val`].
+	fn into_val( self ) -> Val ;
+}
+
+/// Conversion of a [`Val`] into a Rust value, failing if the value has another shape.
+///
+/// See [`IntoVal`] for an example.
+pub trait FromVal: Sized {
+	/// Converts `value` into `Self`.
+	///
+	/// # Errors
+	/// Fails with a [`FromValError`] if `value` does not have the shape of `Self`.
+	fn from_val( value: Val ) -> Result<Self, FromValError> ;
+}
+
+/// Errors that occur when converting a [`Val`] with [`FromVal`].
+#[derive( Debug, Clone, PartialEq, Eq, Error )]
+pub enum FromValError {
+	/// The value has another type than the one converted to.
+	#[error( "Type Mismatch: expected {expected}, found {found}" )] TypeMismatch {
+		/// The WIT type converted to, e.g. `u32` or `list`
+		expected: &'static str,
+		/// The WIT type of the value, in the same form
+		found: &'static str,
+	},
+	/// A tuple has another number of elements than the type converted to.
+	#[error( "Length Mismatch: expected {expected} elements, found {found}" )] LengthMismatch {
+		/// The number of elements of the type converted to
+		expected: usize,
+		/// The number of elements of the value
+		found: usize,
+	},
+	/// A record lacks a field of the type converted to.
+	#[error( "Missing Field: {0}" )] MissingField( String ),
+	/// An enum or variant has a case the type converted to does not know.
+	#[error( "Unknown Case: {0}" )] UnknownCase( String ),
+}
+
+impl FromValError {
+	/// Reports that `value` is not a value of the WIT type `expected`.
+	pub fn mismatch( expected: &'static str, value: &Val ) -> Self {
+		Self::TypeMismatch { expected, found: type_name( value ) }
+	}
+}
+
+/// Removes the field `name` from a record's `fields` and converts it.
+///
+/// # Errors
+/// Fails with [`FromValError::MissingField`] if the record has no such field and with
+/// the field's conversion error if it has another type.
+pub fn take_field<T: FromVal>( fields: &mut Vec<( String, Val )>, name: &str ) -> Result<T, FromValError> {
+	let index = fields.iter().position(|( field, _ )| field == name )
+		.ok_or_else(|| FromValError::MissingField( name.to_string() ))?;
+	T::from_val( fields.swap_remove( index ).1 )
+}
+
+impl IntoVal for Val {
+	fn into_val( self ) -> Val { self }
+}
+
+impl FromVal for Val {
+	fn from_val( value: Val ) -> Result<Self, FromValError> { Ok( value ) }
+}
+
+macro_rules! scalar_conversions {
+	( $( $ty:ty => $case:ident, $name:literal ; )* ) => { $(
+		impl IntoVal for $ty {
+			fn into_val( self ) -> Val { Val::$case( self.into() ) }
+		}
+		impl FromVal for $ty {
+			fn from_val( value: Val ) -> Result<Self, FromValError> { match value {
+				Val::$case( value ) => Ok( value.into() ),
+				value => Err( FromValError::mismatch( $name, &value )),
+			}}
+		}
+	)* };
+}
+
+scalar_conversions! {
+	bool => Bool, "bool" ;
+	i8 => S8, "s8" ;
+	i16 => S16, "s16" ;
+	i32 => S32, "s32" ;
+	i64 => S64, "s64" ;
+	u8 => U8, "u8" ;
+	u16 => U16, "u16" ;
+	u32 => U32, "u32" ;
+	u64 => U64, "u64" ;
+	f32 => Float32, "f32" ;
+	f64 => Float64, "f64" ;
+	char => Char, "char" ;
+	String => String, "string" ;
+}
+
+impl IntoVal for &str {
+	fn into_val( self ) -> Val { Val::String( self.to_string() ) }
+}
+
+impl<T: IntoVal> IntoVal for Vec<T> {
+	fn into_val( self ) -> Val { Val::List( self.into_iter().map( IntoVal::into_val ).collect() ) }
+}
+
+impl<T: FromVal> FromVal for Vec<T> {
+	fn from_val( value: Val ) -> Result<Self, FromValError> { match value {
+		Val::List( items ) => items.into_iter().map( T::from_val ).collect(),
+		value => Err( FromValError::mismatch( "list", &value )),
+	}}
+}
+
+impl<T: IntoVal> IntoVal for Option<T> {
+	fn into_val( self ) -> Val { Val::Option( self.map(| value | Box::new( value.into_val() ))) }
+}
+
+impl<T: FromVal> FromVal for Option<T> {
+	fn from_val( value: Val ) -> Result<Self, FromValError> { match value {
+		Val::Option( value ) => value.map(| value | T::from_val( *value )).transpose(),
+		value => Err( FromValError::mismatch( "option", &value )),
+	}}
+}
+
+/// Converts `()` to a result case without a payload, as in `result<_, E>`.
+impl<T: IntoVal, E: IntoVal> IntoVal for Result<T, E> {
+	fn into_val( self ) -> Val {
+		let payload = | value: Val | match value {
+			Val::Tuple( items ) if items.is_empty() => None,
+			value => Some( Box::new( value )),
+		};
+		Val::Result( match self {
+			Ok( value ) => Ok( payload( value.into_val() )),
+			Err( error ) => Err( payload( error.into_val() )),
+		})
+	}
+}
+
+/// Converts a result case without a payload as if it carried `()`.
+impl<T: FromVal, E: FromVal> FromVal for Result<T, E> {
+	fn from_val( value: Val ) -> Result<Self, FromValError> {
+		let payload = | value: Option<Box<Val>> | value.map_or( Val::Tuple( Vec::new() ), | value | *value );
+		match value {
+			Val::Result( Ok( value )) => Ok( Ok( T::from_val( payload( value ))? )),
+			Val::Result( Err( error )) => Ok( Err( E::from_val( payload( error ))? )),
+			value => Err( FromValError::mismatch( "result", &value )),
+		}
+	}
+}
+
+macro_rules! tuple_conversions {
+	( $( ( $( $ty:ident ),* ); )* ) => { $(
+		#[allow( non_snake_case )]
+		impl<$( $ty: IntoVal ),*> IntoVal for ( $( $ty, )* ) {
+			fn into_val( self ) -> Val {
+				let ( $( $ty, )* ) = self ;
+				Val::Tuple( vec![ $( $ty.into_val() ),* ])
+			}
+		}
+		#[allow( non_snake_case )]
+		impl<$( $ty: FromVal ),*> FromVal for ( $( $ty, )* ) {
+			fn from_val( value: Val ) -> Result<Self, FromValError> {
+				let Val::Tuple( items ) = value else { return Err( FromValError::mismatch( "tuple", &value )) };
+				let expected = <[&str]>::len( &[ $( stringify!( $ty ) ),* ]);
+				if items.len() != expected {
+					return Err( FromValError::LengthMismatch { expected, found: items.len() })
+				}
+				let mut items = items.into_iter();
+				Ok(( $( $ty::from_val( items.next().unwrap_or( Val::Tuple( Vec::new() )))?, )* ))
+			}
+		}
+	)* };
+}
+
+impl IntoVal for () {
+	fn into_val( self ) -> Val { Val::Tuple( Vec::new() ) }
+}
+
+impl FromVal for () {
+	fn from_val( value: Val ) -> Result<Self, FromValError> { match value {
+		Val::Tuple( items ) if items.is_empty() => Ok(()),
+		Val::Tuple( items ) => Err( FromValError::LengthMismatch { expected: 0, found: items.len() }),
+		value => Err( FromValError::mismatch( "tuple", &value )),
+	}}
+}
+
+tuple_conversions! {
+	( A );
+	( A, B );
+	( A, B, C );
+	( A, B, C, D );
+}
+
+/// Returns the name of the WIT type of `value`, e.g. `u32` or `list`.
+fn type_name( value: &Val ) -> &'static str {
+	match value {
+		Val::Bool( _ ) => "bool",
+		Val::S8( _ ) => "s8",
+		Val::U8( _ ) => "u8",
+		Val::S16( _ ) => "s16",
+		Val::U16( _ ) => "u16",
+		Val::S32( _ ) => "s32",
+		Val::U32( _ ) => "u32",
+		Val::S64( _ ) => "s64",
+		Val::U64( _ ) => "u64",
+		Val::Float32( _ ) => "f32",
+		Val::Float64( _ ) => "f64",
+		Val::Char( _ ) => "char",
+		Val::String( _ ) => "string",
+		Val::List( _ ) => "list",
+		Val::Map( _ ) => "map",
+		Val::Record( _ ) => "record",
+		Val::Tuple( _ ) => "tuple",
+		Val::Variant( _, _ ) => "variant",
+		Val::Enum( _ ) => "enum",
+		Val::Option( _ ) => "option",
+		Val::Result( _ ) => "result",
+		Val::Flags( _ ) => "flags",
+		Val::Resource( _ ) => "resource",
+		Val::Future( _ ) => "future",
+		Val::Stream( _ ) => "stream",
+		Val::ErrorContext( _ ) => "error-context",
+	}
+}
+
+#[cfg(test)]
+mod tests { include!( "val_tests.rs" ); }
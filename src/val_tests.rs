@@ -0,0 +1,81 @@
+use wasmtime::component::Val ;
+use super::{ FromVal, FromValError, IntoVal, diff, pretty };
+
+
+
+fn record( fields: &[( &str, Val )]) -> Val {
+	Val::Record( fields.iter().map(|( name, value )| ( name.to_string(), value.clone() )).collect() )
+}
+
+#[test]
+fn pretty_keeps_flat_values_on_one_line() {
+	assert_eq!( pretty( &Val::List( vec![ Val::U32( 1 ), Val::U32( 2 )])), "[1, 2]" );
+	assert_eq!( pretty( &Val::Option( Some( Box::new( Val::String( "x".to_string() ))))), r#"some("x")"# );
+	assert_eq!( pretty( &Val::Result( Ok( None ))), "ok" );
+	assert_eq!( pretty( &Val::List( Vec::new() )), "[]" );
+	assert_eq!( pretty( &record( &[])), "{}" );
+}
+
+#[test]
+fn pretty_indents_nested_values() {
+	let value = record( &[
+		( "name", Val::String( "x".to_string() )),
+		( "items", Val::List( vec![ record( &[( "size", Val::U8( 3 ))]) ])),
+	]);
+	assert_eq!( pretty( &value ), "{\n  name: \"x\",\n  items: [\n    { size: 3 },\n  ],\n}" );
+}
+
+#[test]
+fn diff_reports_only_differing_leaves() {
+	let expected = record( &[
+		( "name", Val::String( "x".to_string() )),
+		( "items", Val::List( vec![ Val::U32( 1 ), Val::U32( 2 )])),
+	]);
+	let actual = record( &[
+		( "name", Val::String( "x".to_string() )),
+		( "items", Val::List( vec![ Val::U32( 1 ), Val::U32( 3 ), Val::U32( 4 )])),
+	]);
+	let differences = diff( &expected, &actual ).iter().map( ToString::to_string ).collect::<Vec<_>>();
+	assert_eq!( differences, [
+		".items[1]: expected 2, found 3",
+		".items[2]: expected <missing>, found 4",
+	]);
+}
+
+#[test]
+fn diff_of_equal_values_is_empty() {
+	let value = Val::Tuple( vec![ Val::Bool( true ), Val::Char( 'c' )]);
+	assert!( diff( &value, &value ).is_empty() );
+}
+
+#[test]
+fn diff_compares_different_cases_as_a_whole() {
+	let expected = Val::Result( Ok( Some( Box::new( Val::U32( 1 )))));
+	let actual = Val::Result( Err( Some( Box::new( Val::String( "boom".to_string() )))));
+	assert_eq!( diff( &expected, &actual )[0].to_string(), r#".: expected ok(1), found err("boom")"# );
+}
+
+#[test]
+fn conversions_round_trip() {
+	let value = ( 7u32, "seven".to_string(), vec![ Some( true ), None ]).into_val();
+	assert_eq!( value, Val::Tuple( vec![
+		Val::U32( 7 ),
+		Val::String( "seven".to_string() ),
+		Val::List( vec![ Val::Option( Some( Box::new( Val::Bool( true )))), Val::Option( None )]),
+	]));
+	assert_eq!( <( u32, String, Vec<Option<bool>> )>::from_val( value ), Ok(( 7, "seven".to_string(), vec![ Some( true ), None ])));
+}
+
+#[test]
+fn results_without_payload_convert_as_unit() {
+	let ok: Result<(), String> = Ok(());
+	assert_eq!( ok.into_val(), Val::Result( Ok( None )));
+	assert_eq!( Result::<(), String>::from_val( Val::Result( Err( Some( Box::new( Val::String( "boom".to_string() )))))), Ok( Err( "boom".to_string() )));
+}
+
+#[test]
+fn conversions_report_the_mismatch() {
+	assert_eq!( u32::from_val( Val::S32( 1 )), Err( FromValError::TypeMismatch { expected: "u32", found: "s32" }));
+	assert_eq!( <( u8, u8 )>::from_val( Val::Tuple( vec![ Val::U8( 1 )])), Err( FromValError::LengthMismatch { expected: 2, found: 1 }));
+	assert_eq!( Vec::<u8>::from_val( Val::List( vec![ Val::U8( 1 ), Val::U16( 2 )])), Err( FromValError::TypeMismatch { expected: "u8", found: "u16" }));
+}
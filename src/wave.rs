@@ -0,0 +1,62 @@
+//! [WAVE](https://github.com/bytecodealliance/wasm-tools/tree/main/crates/wasm-wave)
+//! text encoding of arguments and results.
+//!
+//! WAVE is the human-readable syntax of Component Model values used by wasmtime and
+//! wasm-tools, e.g. `42`, `"text"`, `some([1, 2])` or `{ name: "x", size: 3 }`. Using
+//! it for command-line calls, test vectors and golden files gives them all one shared
+//! representation of [`Val`]. Requires the `wave` feature.
+//!
+//! Parsing needs the types of the values, which only the plugins know, so
+//! [`Binding::dispatch_wave`]( crate::Binding::dispatch_wave ) parses the arguments
+//! against each plugin's export separately.
+
+use thiserror::Error ;
+use wasmtime::component::{ Type, Val };
+use wasmtime::component::wasm_wave::untyped::UntypedFuncCall ;
+
+use crate::DispatchError ;
+
+
+
+/// Errors that occur when dispatching with WAVE-encoded arguments.
+#[derive( Error, Debug )]
+pub enum WaveError {
+	/// The arguments are not valid WAVE or do not match the function's parameters.
+	#[error( "Invalid WAVE Arguments: {0}" )] InvalidArguments( String ),
+	/// The result contains a value WAVE cannot encode, such as a resource.
+	#[error( "Unencodable Result: {0}" )] UnencodableResult( String ),
+	/// The dispatch itself failed.
+	#[error( transparent )] Dispatch( #[from] DispatchError ),
+}
+
+/// Parses comma-separated WAVE `args` as values of the given parameter types.
+///
+/// Trailing `option` parameters may be omitted and are passed as `none`.
+///
+/// # Errors
+/// Returns [`WaveError::InvalidArguments`] if `args` is not valid WAVE or does not
+/// match `params`.
+pub fn parse_args( params: &[Type], args: &str ) -> Result<Vec<Val>, WaveError> {
+	let call = format!( "call({args})" );
+	UntypedFuncCall::parse( &call )
+		.and_then(| call | call.to_wasm_params::<Val>( params ))
+		.map_err(| err | WaveError::InvalidArguments( err.to_string() ))
+}
+
+/// Encodes a value as WAVE.
+///
+/// ```
+/// use wasm_link::Val ;
+///
+/// let value = Val::List( vec![ Val::U32( 1 ), Val::U32( 2 )]);
+/// assert_eq!( wasm_link::wave::format( &value ).unwrap(), "[1, 2]" );
+/// ```
+///
+/// # Errors
+/// Returns [`WaveError::UnencodableResult`] if the value contains a resource.
+pub fn format( value: &Val ) -> Result<String, WaveError> {
+	value.to_wave().map_err(| err | WaveError::UnencodableResult( err.to_string() ))
+}
+
+#[cfg(test)]
+mod tests { include!( "wave_tests.rs" ); }
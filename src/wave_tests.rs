@@ -0,0 +1,18 @@
+use wasmtime::component::Val ;
+use super::{ WaveError, format, parse_args };
+
+
+
+#[test]
+fn values_are_formatted_as_wave() {
+	assert_eq!( format( &Val::String( "hi".to_string() )).unwrap(), r#""hi""# );
+	assert_eq!( format( &Val::Option( Some( Box::new( Val::S32( -1 ))))).unwrap(), "some(-1)" );
+	assert_eq!( format( &Val::Tuple( vec![ Val::Bool( true ), Val::Char( 'x' )])).unwrap(), "(true, 'x')" );
+}
+
+#[test]
+fn arguments_without_parameters_parse_to_nothing() {
+	assert!( parse_args( &[], "" ).unwrap().is_empty() );
+	assert!( matches!( parse_args( &[], "1" ), Err( WaveError::InvalidArguments( _ ))));
+	assert!( matches!( parse_args( &[], "[unclosed" ), Err( WaveError::InvalidArguments( _ ))));
+}
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::sync::Barrier;
+use wasm_link::{ Binding, DispatchError, Engine, Linker, Val };
+use wasm_link::cardinality::ExactlyOne ;
+
+fixtures! {
+	bindings = { root: "root", binding_b: "binding-b", binding_c: "binding-c", binding_d: "binding-d" };
+	plugins  = { plugin_a: "plugin-a", plugin_b: "plugin-b", plugin_c: "plugin-c", plugin_d: "plugin-d" };
+}
+
+const THREADS: usize = 8 ;
+const ITERATIONS: usize = 200 ;
+
+// Bindings are shared between host threads and the plugins that depend on them
+const _: fn() = || {
+	fn assert_send_sync<T: Send + Sync + Clone>() {}
+	assert_send_sync::<Binding<String, crate::fixture_linking::TestContext>>();
+};
+
+#[test]
+fn concurrent_dispatches_into_a_shared_dependency_never_block_or_corrupt_state() {
+
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+
+	let binding_d = Binding::new(
+		bindings.binding_d.package,
+		HashMap::from([( bindings.binding_d.name, bindings.binding_d.spec )]),
+		ExactlyOne( "_".to_string(), plugins.plugin_d.plugin
+			.instantiate( &engine, &linker )
+			.expect( "Failed to instantiate plugin-d" )),
+	);
+
+	let binding_b = Binding::new(
+		bindings.binding_b.package,
+		HashMap::from([( bindings.binding_b.name, bindings.binding_b.spec )]),
+		ExactlyOne( "_".to_string(), plugins.plugin_b.plugin
+			.link( &engine, linker.clone(), vec![ binding_d.clone() ])
+			.expect( "Failed to link plugin-b" )),
+	);
+
+	let binding_c = Binding::new(
+		bindings.binding_c.package,
+		HashMap::from([( bindings.binding_c.name, bindings.binding_c.spec )]),
+		ExactlyOne( "_".to_string(), plugins.plugin_c.plugin
+			.link( &engine, linker.clone(), vec![ binding_d.clone() ])
+			.expect( "Failed to link plugin-c" )),
+	);
+
+	let binding_root = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		ExactlyOne( "_".to_string(), plugins.plugin_a.plugin
+			.link( &engine, linker.clone(), vec![ binding_b.clone(), binding_c.clone() ])
+			.expect( "Failed to link plugin-a" )),
+	);
+
+	// Every thread targets a different level of the graph, so dispatches overlap on
+	// the plugins they share. Contention must surface as `LockRejected`, either to the
+	// host or to the consuming plugin, which then reads a smaller value.
+	let targets = [
+		( binding_root.clone(), "get-value", 2 ),
+		( binding_b.clone(), "get-b", 1 ),
+		( binding_c.clone(), "get-c", 1 ),
+		( binding_d.clone(), "get-d", 1 ),
+	];
+	let barrier = Barrier::new( THREADS );
+	let succeeded = std::thread::scope(| scope | {
+		let handles = ( 0..THREADS ).map(| thread | {
+			let ( target, function, expected ) = targets[ thread % targets.len() ].clone();
+			let barrier = &barrier ;
+			scope.spawn( move || {
+				barrier.wait();
+				( 0..ITERATIONS ).filter(| _ | match target.dispatch( "root", function, &[] ) {
+					Ok( ExactlyOne( _, Ok( Val::U32( value )))) if value == expected => true,
+					Ok( ExactlyOne( _, Ok( Val::U32( value )))) if value < expected => false,
+					Ok( ExactlyOne( _, Err( DispatchError::LockRejected ))) => false,
+					value => panic!( "Unexpected result of {function}: {:#?}", value ),
+				}).count()
+			})
+		}).collect::<Vec<_>>();
+		handles.into_iter().map(| handle | handle.join().expect( "Dispatching thread panicked" )).sum::<usize>()
+	});
+	assert!( succeeded > 0, "No dispatch succeeded" );
+
+	match binding_root.dispatch( "root", "get-value", &[] ) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 2 )))) => {}
+		value => panic!( "Expected Ok( ExactlyOne( Ok( U32( 2 )))), found: {:#?}", value ),
+	}
+
+}
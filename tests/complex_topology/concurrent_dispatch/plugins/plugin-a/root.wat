@@ -0,0 +1,95 @@
+(component
+	;; A rejected dispatch reads as 0, the `lock-rejected` case
+	(type $interface_b-type (instance
+		(type $dispatch-error' (variant
+			(case "lock-rejected")
+			(case "plugin-disabled")
+			(case "invalid-interface-path" string)
+			(case "invalid-function" string)
+			(case "missing-response")
+			(case "runtime-exception" string)
+			(case "invalid-argument-list")
+			(case "unsupported-type" string)
+			(case "executor-unavailable")
+			(case "payload-too-large" string)
+			(case "all-implementations-failed")
+			(case "resource-table-full")
+			(case "resource-handle-conversion-failed")
+			(case "invalid-resource-handle")
+		))
+		(export "dispatch-error" (type (eq $dispatch-error')))
+		(type $dispatch-result (result u32 (error 1)))
+		(type $wrapped-result (tuple string $dispatch-result))
+		(export "get-b" (func (result $wrapped-result)))
+	))
+	(import "test:binding-b/root" (instance $interface_b (type $interface_b-type)))
+	(type $interface_c-type (instance
+		(type $dispatch-error' (variant
+			(case "lock-rejected")
+			(case "plugin-disabled")
+			(case "invalid-interface-path" string)
+			(case "invalid-function" string)
+			(case "missing-response")
+			(case "runtime-exception" string)
+			(case "invalid-argument-list")
+			(case "unsupported-type" string)
+			(case "executor-unavailable")
+			(case "payload-too-large" string)
+			(case "all-implementations-failed")
+			(case "resource-table-full")
+			(case "resource-handle-conversion-failed")
+			(case "invalid-resource-handle")
+		))
+		(export "dispatch-error" (type (eq $dispatch-error')))
+		(type $dispatch-result (result u32 (error 1)))
+		(type $wrapped-result (tuple string $dispatch-result))
+		(export "get-c" (func (result $wrapped-result)))
+	))
+	(import "test:binding-c/root" (instance $interface_c (type $interface_c-type)))
+
+	(alias export $interface_b "get-b" (func $get_b))
+	(alias export $interface_c "get-c" (func $get_c))
+
+	;; Memory for lowering
+	(core module $mem_module
+		(memory (export "memory") 1)
+		(func (export "realloc") (param i32 i32 i32 i32) (result i32)
+			i32.const 256
+		)
+	)
+	(core instance $mem_inst (instantiate $mem_module))
+	(alias core export $mem_inst "memory" (core memory $shared_mem))
+	(alias core export $mem_inst "realloc" (core func $shared_realloc))
+
+	(core func $lowered_get_b (canon lower (func $get_b) (memory $shared_mem) (realloc $shared_realloc)))
+	(core func $lowered_get_c (canon lower (func $get_c) (memory $shared_mem) (realloc $shared_realloc)))
+	(core instance $imports_b (export "get-b" (func $lowered_get_b)))
+	(core instance $imports_c (export "get-c" (func $lowered_get_c)))
+	(core instance $mem_imports (export "memory" (memory $shared_mem)))
+
+	(core module $main_impl
+		(import "binding-b" "get-b" (func $get_b (param i32)))
+		(import "binding-c" "get-c" (func $get_c (param i32)))
+		(import "mem" "memory" (memory 1))
+
+		(func (export "get-value") (result i32)
+			(call $get_b (i32.const 0))
+			(call $get_c (i32.const 32))
+			(i32.add
+				(i32.load (i32.const 12))
+				(i32.load (i32.const 44))
+			)
+		)
+	)
+
+	(core instance $main_inst (instantiate $main_impl
+		(with "binding-b" (instance $imports_b))
+		(with "binding-c" (instance $imports_c))
+		(with "mem" (instance $mem_imports))
+	))
+
+	(alias core export $main_inst "get-value" (core func $core_get_value))
+	(func $lifted_get_value (result u32) (canon lift (core func $core_get_value)))
+	(instance $inst (export "get-value" (func $lifted_get_value)))
+	(export "test:shared/root" (instance $inst))
+)
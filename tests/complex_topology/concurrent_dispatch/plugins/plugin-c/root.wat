@@ -0,0 +1,63 @@
+(component
+	;; Import binding-d dependency. A rejected dispatch reads as 0, the `lock-rejected` case
+	(type $interface_d-type (instance
+		(type $dispatch-error' (variant
+			(case "lock-rejected")
+			(case "plugin-disabled")
+			(case "invalid-interface-path" string)
+			(case "invalid-function" string)
+			(case "missing-response")
+			(case "runtime-exception" string)
+			(case "invalid-argument-list")
+			(case "unsupported-type" string)
+			(case "executor-unavailable")
+			(case "payload-too-large" string)
+			(case "all-implementations-failed")
+			(case "resource-table-full")
+			(case "resource-handle-conversion-failed")
+			(case "invalid-resource-handle")
+		))
+		(export "dispatch-error" (type (eq $dispatch-error')))
+		(type $dispatch-result (result u32 (error 1)))
+		(type $wrapped-result (tuple string $dispatch-result))
+		(export "get-d" (func (result $wrapped-result)))
+	))
+	(import "test:binding-d/root" (instance $interface_d (type $interface_d-type)))
+
+	(alias export $interface_d "get-d" (func $get_d))
+
+	;; Memory for lowering
+	(core module $mem_module
+		(memory (export "memory") 1)
+		(func (export "realloc") (param i32 i32 i32 i32) (result i32)
+			i32.const 256
+		)
+	)
+	(core instance $mem_inst (instantiate $mem_module))
+	(alias core export $mem_inst "memory" (core memory $shared_mem))
+	(alias core export $mem_inst "realloc" (core func $shared_realloc))
+
+	(core func $lowered_get_d (canon lower (func $get_d) (memory $shared_mem) (realloc $shared_realloc)))
+	(core instance $imports_d (export "get-d" (func $lowered_get_d)))
+	(core instance $mem_imports (export "memory" (memory $shared_mem)))
+
+	(core module $main_impl
+		(import "binding-d" "get-d" (func $get_d (param i32)))
+		(import "mem" "memory" (memory 1))
+
+		(func (export "get-c") (result i32)
+			(call $get_d (i32.const 0))
+			(i32.load (i32.const 12))
+		)
+	)
+
+	(core instance $main_inst (instantiate $main_impl
+		(with "binding-d" (instance $imports_d))
+		(with "mem" (instance $mem_imports))
+	))
+
+	(alias core export $main_inst "get-c" (core func $core_get_c))
+	(func $lifted_get_c (result u32) (canon lift (core func $core_get_c)))
+	(instance $inst (export "get-c" (func $lifted_get_c)))
+	(export "test:binding-c/root" (instance $inst))
+)
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use wasm_link::{ Binding, Engine, Linker, Plugin, Val };
+use wasm_link::cardinality::ExactlyOne ;
+use wasm_link::snapshot::{ CardinalityKind, GraphSnapshot };
+use crate::fixture_linking::TestContext ;
+
+fixtures! {
+	bindings = { root: "root", binding_b: "binding-b", binding_c: "binding-c", binding_d: "binding-d" };
+	plugins  = { plugin_a: "plugin-a", plugin_b: "plugin-b", plugin_c: "plugin-c", plugin_d: "plugin-d" };
+}
+
+fn build_graph( engine: &Engine, linker: &Linker<TestContext> ) -> Binding<String, TestContext> {
+
+	let plugins = fixtures::plugins( engine );
+	let bindings = fixtures::bindings();
+
+	let instance_d = plugins.plugin_d.plugin
+		.with_component_hash( "plugin-d" )
+		.instantiate( engine, linker )
+		.expect( "Failed to instantiate plugin-d" );
+	let binding_d = Binding::new(
+		bindings.binding_d.package,
+		HashMap::from([( bindings.binding_d.name, bindings.binding_d.spec )]),
+		ExactlyOne( "d".to_string(), instance_d ),
+	);
+
+	let instance_b = plugins.plugin_b.plugin
+		.with_component_hash( "plugin-b" )
+		.link( engine, linker.clone(), vec![ binding_d.clone() ])
+		.expect( "Failed to link plugin-b" );
+	let binding_b = Binding::new(
+		bindings.binding_b.package,
+		HashMap::from([( bindings.binding_b.name, bindings.binding_b.spec )]),
+		ExactlyOne( "b".to_string(), instance_b ),
+	);
+
+	let instance_c = plugins.plugin_c.plugin
+		.with_component_hash( "plugin-c" )
+		.link( engine, linker.clone(), vec![ binding_d ])
+		.expect( "Failed to link plugin-c" );
+	let binding_c = Binding::new(
+		bindings.binding_c.package,
+		HashMap::from([( bindings.binding_c.name, bindings.binding_c.spec )]),
+		ExactlyOne( "c".to_string(), instance_c ),
+	);
+
+	let instance_a = plugins.plugin_a.plugin
+		.with_component_hash( "plugin-a" )
+		.link( engine, linker.clone(), vec![ binding_b, binding_c ])
+		.expect( "Failed to link plugin-a" );
+	Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		ExactlyOne( "a".to_string(), instance_a ),
+	)
+
+}
+
+fn load_graph( snapshot: &GraphSnapshot, engine: &Engine, linker: &Linker<TestContext> ) -> Binding<String, TestContext> {
+	let plugins = fixtures::plugins( engine );
+	let mut components = HashMap::from([
+		( "plugin-a", plugins.plugin_a.plugin ),
+		( "plugin-b", plugins.plugin_b.plugin ),
+		( "plugin-c", plugins.plugin_c.plugin ),
+		( "plugin-d", plugins.plugin_d.plugin ),
+	]);
+	let mut roots = snapshot.load( engine, linker, | _package, plugin_id, hash | {
+		let plugin: Plugin<TestContext> = hash.and_then(| hash | components.remove( hash ))
+			.ok_or_else(|| wasmtime::Error::msg( format!( "no component for {plugin_id}" )))?;
+		Ok(( plugin_id.to_string(), plugin ))
+	}).expect( "Failed to load graph snapshot" );
+	match roots.pop() {
+		Some( wasm_link::BindingAny::ExactlyOne( binding )) if roots.is_empty() => binding,
+		roots => panic!( "Expected a single ExactlyOne root, found: {:#?}", roots ),
+	}
+}
+
+#[test]
+fn snapshot_records_graph_wiring() {
+
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let snapshot = build_graph( &engine, &linker ).snapshot().expect( "Failed to capture snapshot" );
+
+	assert_eq!( snapshot.roots(), [ "test:shared".to_string() ]);
+	assert_eq!( snapshot.bindings().len(), 4 );
+	let root = snapshot.binding( "test:shared" ).expect( "root binding missing" );
+	assert_eq!( root.cardinality(), CardinalityKind::ExactlyOne );
+	assert!( root.interfaces().contains_key( "root" ));
+	let plugin_a = &root.plugins()["a"];
+	assert_eq!( plugin_a.component(), Some( "plugin-a" ));
+	assert_eq!( plugin_a.sockets(), [ "test:binding-b".to_string(), "test:binding-c".to_string() ]);
+	let binding_d = snapshot.binding( "test:binding-d" ).expect( "shared binding missing" );
+	assert!( binding_d.plugins()["d"].sockets().is_empty() );
+
+}
+
+#[test]
+fn snapshot_loads_into_an_equivalent_graph() {
+
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let snapshot = build_graph( &engine, &linker ).snapshot().expect( "Failed to capture snapshot" );
+
+	let loaded = load_graph( &snapshot, &engine, &linker );
+	match loaded.dispatch( "root", "get-value", &[] ) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 2 )))) => {}
+		value => panic!( "Expected Ok( ExactlyOne( Ok( U32( 2 )))), found: {:#?}", value ),
+	}
+	assert_eq!( loaded.snapshot().expect( "Failed to capture snapshot" ), snapshot );
+
+}
+
+#[cfg( feature = "json" )]
+#[test]
+fn snapshot_round_trips_through_json() {
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let snapshot = build_graph( &engine, &linker ).snapshot().expect( "Failed to capture snapshot" );
+	let json = snapshot.to_json().expect( "Failed to write JSON" );
+	assert_eq!( GraphSnapshot::from_json( &json ).expect( "Failed to read JSON" ), snapshot );
+}
+
+#[cfg( feature = "toml" )]
+#[test]
+fn snapshot_round_trips_through_toml() {
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let snapshot = build_graph( &engine, &linker ).snapshot().expect( "Failed to capture snapshot" );
+	let toml = snapshot.to_toml().expect( "Failed to write TOML" );
+	let loaded = load_graph( &GraphSnapshot::from_toml( &toml ).expect( "Failed to read TOML" ), &engine, &linker );
+	match loaded.dispatch( "root", "get-value", &[] ) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 2 )))) => {}
+		value => panic!( "Expected Ok( ExactlyOne( Ok( U32( 2 )))), found: {:#?}", value ),
+	}
+}
+
+#[test]
+fn catalog_lists_every_function_of_the_graph() {
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let catalog = build_graph( &engine, &linker ).snapshot().expect( "Failed to capture snapshot" ).catalog();
+	let functions = catalog.functions().iter()
+		.map(| entry | ( entry.package(), entry.function(), entry.signature(), entry.plugins().to_vec() ))
+		.collect::<Vec<_>>();
+	assert_eq!( functions, vec![
+		( "test:binding-b", "get-b", Some( "func() -> u32" ), vec![ "b".to_string() ]),
+		( "test:binding-c", "get-c", Some( "func() -> u32" ), vec![ "c".to_string() ]),
+		( "test:binding-d", "get-d", Some( "func() -> u32" ), vec![ "d".to_string() ]),
+		( "test:shared", "get-value", Some( "func() -> u32" ), vec![ "a".to_string() ]),
+	]);
+	assert!( catalog.functions().iter().all(| entry | entry.cardinality() == CardinalityKind::ExactlyOne ));
+}
+
+#[cfg( feature = "json" )]
+#[test]
+fn catalog_serializes_to_json() {
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let catalog = build_graph( &engine, &linker ).snapshot().expect( "Failed to capture snapshot" ).catalog();
+	let json: serde_json::Value = serde_json::from_str( &catalog.to_json().expect( "Failed to write JSON" ))
+		.expect( "Failed to read JSON" );
+	assert_eq!( json["functions"][0]["package"], "test:binding-b" );
+	assert_eq!( json["functions"][0]["kind"], "freestanding" );
+	assert_eq!( json["functions"][0]["signature"], "func() -> u32" );
+	assert_eq!( json["functions"][0]["cardinality"], "exactly-one" );
+}
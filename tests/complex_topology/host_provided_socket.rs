@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use wasm_link::{ Binding, Engine, Linker, Val };
+use wasm_link::cardinality::ExactlyOne ;
+
+fixtures! {
+	bindings = { dependency: "dependency", consumer: "consumer" };
+	plugins  = { child: "child", consumer: "consumer" };
+}
+
+#[test]
+fn host_implemented_socket_interfaces_bypass_the_socket() {
+
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+
+	let child_instance = plugins.child.plugin
+		.instantiate( &engine, &linker )
+		.expect( "Failed to instantiate child plugin" );
+	let binding_child = Binding::new(
+		bindings.dependency.package,
+		HashMap::from([( bindings.dependency.name, bindings.dependency.spec )]),
+		ExactlyOne( "_".to_string(), child_instance ),
+	);
+
+	let mut host_linker = linker.clone();
+	host_linker.root().instance( "test:child/root" )
+		.and_then(| mut instance | instance.func_wrap( "get-value", | _, (): () | Ok(( 7_u32, )) ))
+		.expect( "Failed to define host interface" );
+
+	let consumer_instance = plugins.consumer.plugin
+		.link( &engine, host_linker, vec![ binding_child ])
+		.expect( "Failed to link consumer plugin" );
+	let binding_consumer = Binding::new(
+		bindings.consumer.package,
+		HashMap::from([( bindings.consumer.name, bindings.consumer.spec )]),
+		ExactlyOne( "_".to_string(), consumer_instance ),
+	);
+
+	match binding_consumer.dispatch( "root", "get-value", &[] ) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 7 )))) => {}
+		value => panic!( "Expected Ok( ExactlyOne( Ok( U32( 7 )))), found: {:#?}", value ),
+	}
+
+	let snapshot = binding_consumer.snapshot().expect( "Failed to capture snapshot" );
+	let consumer = &snapshot.binding( "test:consumer" ).expect( "consumer binding missing" ).plugins()["_"];
+	assert_eq!( consumer.host_provided(), [ "test:child/root".to_string() ]);
+
+}
+
+#[test]
+fn host_implemented_socket_interfaces_are_not_shadowed() {
+
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+
+	let child_instance = plugins.child.plugin
+		.instantiate( &engine, &linker )
+		.expect( "Failed to instantiate child plugin" );
+	let binding_child = Binding::new(
+		bindings.dependency.package,
+		HashMap::from([( bindings.dependency.name, bindings.dependency.spec )]),
+		ExactlyOne( "_".to_string(), child_instance ),
+	);
+
+	let mut host_linker = linker.clone();
+	host_linker.allow_shadowing( true );
+	host_linker.root().instance( "test:child/root" )
+		.and_then(| mut instance | instance.func_wrap( "get-value", | _, (): () | Ok(( 7_u32, )) ))
+		.expect( "Failed to define host interface" );
+
+	let consumer_instance = plugins.consumer.plugin
+		.link( &engine, host_linker, vec![ binding_child ])
+		.expect( "Failed to link consumer plugin" );
+	let binding_consumer = Binding::new(
+		bindings.consumer.package,
+		HashMap::from([( bindings.consumer.name, bindings.consumer.spec )]),
+		ExactlyOne( "_".to_string(), consumer_instance ),
+	);
+
+	match binding_consumer.dispatch( "root", "get-value", &[] ) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 7 )))) => {}
+		value => panic!( "Expected Ok( ExactlyOne( Ok( U32( 7 )))), found: {:#?}", value ),
+	}
+
+}
@@ -0,0 +1,22 @@
+(component
+	;; Imports the plain result the host provides rather than the socket's wrapped one
+	(import "test:child/root" (instance $child
+		(export "get-value" (func (result u32)))
+	))
+
+	(alias export $child "get-value" (func $get_child))
+	(core func $lowered_get_child (canon lower (func $get_child)))
+	(core instance $imports (export "get-value" (func $lowered_get_child)))
+
+	(core module $m
+		(import "child" "get-value" (func $get_child (result i32)))
+		(func (export "get-value") (result i32)
+			call $get_child
+		)
+	)
+	(core instance $i (instantiate $m (with "child" (instance $imports))))
+
+	(func $f (result u32) (canon lift (core func $i "get-value")))
+	(instance $inst (export "get-value" (func $f)))
+	(export "test:consumer/root" (instance $inst))
+)
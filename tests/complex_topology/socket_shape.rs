@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use wasm_link::{ Binding, BindingAny, Engine, InterfaceBuilder, Linker, PluginInstanceSync, ReturnKind, SocketShapeError };
+use wasm_link::cardinality::{ AtMostOne, ExactlyOne };
+use crate::fixture_linking::{ PluginData, TestContext };
+
+fixtures! {
+	bindings = { dependency: "dependency" };
+	plugins  = { child: "child", unwrapped: "unwrapped", undeclared: "undeclared", partial: "partial", void: "void" };
+}
+
+fn link_error(
+	engine: &Engine,
+	plugin: PluginData,
+	socket: impl Fn( PluginInstanceSync<TestContext> ) -> BindingAny<String, TestContext>,
+) -> Option<SocketShapeError> {
+	let linker = Linker::new( engine );
+	let child_instance = fixtures::plugins( engine ).child.plugin
+		.instantiate( engine, &linker )
+		.expect( "Failed to instantiate child plugin" );
+	let err = plugin.plugin.link( engine, linker, vec![ socket( child_instance )])
+		.expect_err( "Linking should fail" );
+	err.downcast_ref::<SocketShapeError>().cloned()
+}
+
+#[test]
+fn linking_rejects_imports_missing_the_cardinality_wrapper() {
+	let engine = Engine::default();
+	let bindings = fixtures::bindings();
+	let error = link_error( &engine, fixtures::plugins( &engine ).unwrapped, | child | Binding::new(
+		bindings.dependency.package.clone(),
+		HashMap::from([( bindings.dependency.name.clone(), bindings.dependency.spec.clone() )]),
+		ExactlyOne( "_".to_string(), child ),
+	).into_any());
+	assert_eq!( error, Some( SocketShapeError::ResultShape {
+		interface: "test:child/root".to_string(),
+		function: "get-value".to_string(),
+		expected: "tuple",
+		found: "u32".to_string(),
+	}));
+}
+
+#[test]
+fn linking_expects_the_wrapper_of_the_socket_cardinality() {
+	let engine = Engine::default();
+	let bindings = fixtures::bindings();
+	let error = link_error( &engine, fixtures::plugins( &engine ).unwrapped, | child | Binding::new(
+		bindings.dependency.package.clone(),
+		HashMap::from([( bindings.dependency.name.clone(), bindings.dependency.spec.clone() )]),
+		AtMostOne( Some(( "_".to_string(), child ))),
+	).into_any());
+	assert!( matches!( error, Some( SocketShapeError::ResultShape { expected: "option", .. })));
+}
+
+#[test]
+fn linking_rejects_imports_without_a_result() {
+	let engine = Engine::default();
+	let bindings = fixtures::bindings();
+	let error = link_error( &engine, fixtures::plugins( &engine ).void, | child | Binding::new(
+		bindings.dependency.package.clone(),
+		HashMap::from([( bindings.dependency.name.clone(), bindings.dependency.spec.clone() )]),
+		ExactlyOne( "_".to_string(), child ),
+	).into_any());
+	assert_eq!( error, Some( SocketShapeError::ResultShape {
+		interface: "test:child/root".to_string(),
+		function: "get-value".to_string(),
+		expected: "tuple",
+		found: "0 results".to_string(),
+	}));
+}
+
+#[test]
+fn linking_rejects_imports_of_undeclared_functions() {
+	let engine = Engine::default();
+	let bindings = fixtures::bindings();
+	let error = link_error( &engine, fixtures::plugins( &engine ).undeclared, | child | Binding::new(
+		bindings.dependency.package.clone(),
+		HashMap::from([( bindings.dependency.name.clone(), bindings.dependency.spec.clone() )]),
+		ExactlyOne( "_".to_string(), child ),
+	).into_any());
+	assert_eq!( error, Some( SocketShapeError::UndeclaredItem {
+		interface: "test:child/root".to_string(),
+		item: "get-other".to_string(),
+	}));
+}
+
+#[test]
+fn linking_tolerates_members_the_component_does_not_import() {
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let child_instance = plugins.child.plugin
+		.instantiate( &engine, &linker )
+		.expect( "Failed to instantiate child plugin" );
+	let binding = Binding::new(
+		bindings.dependency.package,
+		HashMap::from([( bindings.dependency.name, InterfaceBuilder::from( bindings.dependency.spec ).function( "get-other", ReturnKind::AssumeNoResources ).build())]),
+		ExactlyOne( "_".to_string(), child_instance ),
+	);
+	plugins.partial.plugin.link( &engine, linker, vec![ binding ])
+		.expect( "Failed to link plugin importing a subset of the interface" );
+}
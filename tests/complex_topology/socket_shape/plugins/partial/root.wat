@@ -0,0 +1,6 @@
+(component
+	;; Imports only some of the functions the binding declares
+	(import "test:child/root" (instance
+		(export "get-value" (func (result (tuple string (result u32)))))
+	))
+)
@@ -0,0 +1,7 @@
+(component
+	;; Imports a function the binding does not declare
+	(import "test:child/root" (instance
+		(export "get-value" (func (result (tuple string (result u32)))))
+		(export "get-other" (func (result (tuple string (result u32)))))
+	))
+)
@@ -0,0 +1,7 @@
+(component
+	;; Expects the plain result of the single plugin instead of the `tuple<plugin-id, result<u32>>`
+	;; an exactly-one socket provides
+	(import "test:child/root" (instance
+		(export "get-value" (func (result u32)))
+	))
+)
@@ -0,0 +1,6 @@
+(component
+	;; Imports the function without any result, as if it were declared void
+	(import "test:child/root" (instance
+		(export "get-value" (func))
+	))
+)
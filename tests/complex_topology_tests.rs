@@ -4,5 +4,9 @@ include!( "test_utils/fixture_linking.rs" );
 #[path = "complex_topology"] mod complex_topology {
 	mod deep_nesting ;
 	mod shared_dependency ;
+	mod concurrent_dispatch ;
 	mod multiple_sockets ;
+	mod graph_snapshot ;
+	mod socket_shape ;
+	mod host_provided_socket ;
 }
@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+use std::sync::{ Arc, Mutex };
+use wasm_link::{ Binding, DispatchError, Engine, Linker, Val, nem };
+use wasm_link::cardinality::AtLeastOne ;
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { healthy: "healthy", broken: "broken" };
+}
+
+#[test]
+fn dispatches_where_every_plugin_failed_are_reported_once_hooked() {
+
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let instantiate = | data: crate::fixture_linking::PluginData | data.plugin
+		.instantiate( &engine, &linker )
+		.expect( "Failed to instantiate plugin" );
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		AtLeastOne( nem! {
+			"broken".to_string() => instantiate( plugins.broken ),
+			"healthy".to_string() => instantiate( plugins.healthy ),
+		}),
+	);
+	let args = [ Val::U32( 40 ), Val::U32( 2 )];
+	assert!( binding.set_enabled( &"healthy".to_string(), false ));
+
+	let AtLeastOne( results ) = binding.dispatch( "root", "add", &args ).expect( "Dispatch failed" );
+	assert!( matches!( results.get( "broken" ), Some( Err( DispatchError::RuntimeException( _ )))), "{results:#?}" );
+	assert!( matches!( results.get( "healthy" ), Some( Err( DispatchError::PluginDisabled ))), "{results:#?}" );
+
+	let reported = Arc::new( Mutex::new( Vec::new() ));
+	let hook_reported = Arc::clone( &reported );
+	binding.on_all_failed( move | interface, function | hook_reported.lock().unwrap().push( format!( "{interface}#{function}" )));
+	let error = binding.dispatch( "root", "add", &args ).expect_err( "Every plugin failed" );
+	assert!( matches!( error, DispatchError::AllImplementationsFailed ), "{error:?}" );
+	assert_eq!( *reported.lock().unwrap(), vec![ "test:dispatch-error/root#add".to_string() ]);
+
+	assert!( binding.set_enabled( &"healthy".to_string(), true ));
+	let AtLeastOne( results ) = binding.dispatch( "root", "add", &args ).expect( "Dispatch failed" );
+	assert!( matches!( results.get( "healthy" ), Some( Ok( Val::U32( 42 )))), "{results:#?}" );
+	assert_eq!( reported.lock().unwrap().len(), 1 );
+
+}
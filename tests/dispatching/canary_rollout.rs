@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use std::sync::{ Arc, Mutex };
+use wasm_link::{ Binding, CallContext, CanaryRollout, Engine, Linker, Val };
+use wasm_link::cardinality::ExactlyOne ;
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { stable: "stable", canary: "canary" };
+}
+
+fn binding( engine: &Engine, rollout: CanaryRollout ) -> Binding<String, crate::fixture_linking::TestContext> {
+	let linker = Linker::new( engine );
+	let plugins = fixtures::plugins( engine );
+	let bindings = fixtures::bindings();
+	let stable = plugins.stable.plugin.instantiate( engine, &linker ).expect( "Failed to instantiate stable plugin" );
+	let canary = plugins.canary.plugin.instantiate( engine, &linker ).expect( "Failed to instantiate canary plugin" );
+	Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		ExactlyOne( "_".to_string(), stable.with_canary( canary, rollout )),
+	)
+}
+
+fn tenant( name: &str ) -> CallContext {
+	CallContext::new().with_metadata( HashMap::from([( "tenant".to_string(), Val::String( name.to_string() ))]))
+}
+
+#[test]
+fn keyed_rollout_routes_selected_dispatches_to_canary() {
+	let engine = Engine::default();
+	let binding = binding( &engine, CanaryRollout::keyed(| context | {
+		context.metadata().get( "tenant" ) == Some( &Val::String( "acme".to_string() ))
+	}));
+	let args = [ Val::U32( 40 ), Val::U32( 2 )];
+	match binding.dispatch_with( &tenant( "acme" ), "root", "add", &args ) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 43 )))) => {}
+		value => panic!( "Expected the canary's result, found: {:#?}", value ),
+	}
+	match binding.dispatch_with( &tenant( "other" ), "root", "add", &args ) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+		value => panic!( "Expected the stable result, found: {:#?}", value ),
+	}
+}
+
+#[test]
+fn comparison_invokes_both_versions() {
+	let engine = Engine::default();
+	let seen = Arc::new( Mutex::new( Vec::new() ));
+	let seen_clone = Arc::clone( &seen );
+	let binding = binding( &engine, CanaryRollout::percentage( 0 ).with_comparison( move | comparison | {
+		seen_clone.lock().unwrap().push((
+			comparison.matches(),
+			comparison.served_by_canary(),
+			comparison.stable().as_ref().ok().cloned(),
+			comparison.canary().as_ref().ok().cloned(),
+		));
+	}));
+	match binding.dispatch( "root", "add", &[ Val::U32( 40 ), Val::U32( 2 )]) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+		value => panic!( "Expected the stable result, found: {:#?}", value ),
+	}
+	assert_eq!( *seen.lock().unwrap(), vec![( false, false, Some( Val::U32( 42 )), Some( Val::U32( 43 )))]);
+}
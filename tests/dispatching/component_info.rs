@@ -0,0 +1,27 @@
+use wasm_link::{ Engine, Linker };
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { adder: "adder" };
+}
+
+#[test]
+fn component_info_describes_the_compiled_component() {
+
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let plugin_instance = plugins.adder.plugin
+		.instantiate( &engine, &linker )
+		.expect( "Failed to instantiate plugin" );
+
+	let info = plugin_instance.component_info();
+	assert!( info.compiled_size() > 0 );
+	assert!( info.functions() > 0 );
+	assert_eq!( info.memories(), Some( 0 ));
+	assert!( info.imports().is_empty() );
+	assert_eq!( info.exports(), [ "test:dispatch-error/root", "add" ]);
+	assert_eq!( info.signature( "test:dispatch-error/root", "add" ), Some( "func(a: u32, b: u32) -> u32" ));
+	assert_eq!( info.signature( "test:dispatch-error/root", "missing" ), None );
+
+}
@@ -0,0 +1,60 @@
+#![cfg( feature = "wave" )]
+
+use std::collections::HashMap;
+use wasm_link::{ Binding, Engine, Linker };
+use wasm_link::cardinality::ExactlyOne ;
+use wasm_link::conformance::{ ConformanceFailure, TestVector };
+use wasm_link::wave::WaveError ;
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { test_plugin: "test-plugin" };
+}
+
+fn binding( engine: &Engine, vectors: Vec<TestVector> ) -> Binding<String, crate::fixture_linking::TestContext> {
+	let linker = Linker::new( engine );
+	let plugins = fixtures::plugins( engine );
+	let bindings = fixtures::bindings();
+	let plugin_instance = plugins.test_plugin.plugin
+		.instantiate( engine, &linker )
+		.expect( "Failed to instantiate plugin" );
+	Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec.with_test_vectors( vectors ))]),
+		ExactlyOne( "_".to_string(), plugin_instance ),
+	)
+}
+
+#[test]
+fn conforming_plugin_passes() {
+	let engine = Engine::default();
+	let binding = binding( &engine, vec![
+		TestVector::new( "add", "40, 2" ).expecting( "42" ),
+		TestVector::new( "add", "1, 1" ),
+	]);
+	let ExactlyOne( _, report ) = binding.run_conformance();
+	assert_eq!( report.outcomes().len(), 2 );
+	assert!( report.passed(), "Expected all vectors to pass, found: {report:#?}" );
+}
+
+#[test]
+fn violations_are_reported_per_vector() {
+	let engine = Engine::default();
+	let binding = binding( &engine, vec![
+		TestVector::new( "add", "40, 2" ).expecting( "43" ),
+		TestVector::new( "add", "40" ),
+		TestVector::new( "add", "40, 2" ).expecting( "42" ),
+	]);
+	let ExactlyOne( _, report ) = binding.run_conformance();
+	assert!( !report.passed() );
+	assert_eq!( report.failures().count(), 2 );
+	match report.outcomes()[0].result() {
+		Err( ConformanceFailure::Mismatch { actual, .. }) if actual == "42" => {}
+		value => panic!( "Expected Mismatch, found: {value:#?}" ),
+	}
+	match report.outcomes()[1].result() {
+		Err( ConformanceFailure::Call( WaveError::InvalidArguments( _ ))) => {}
+		value => panic!( "Expected InvalidArguments, found: {value:#?}" ),
+	}
+	assert!( report.outcomes()[2].passed() );
+}
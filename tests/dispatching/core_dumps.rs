@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+use wasm_link::{ Binding, DispatchError, Engine, Linker, Val };
+use wasm_link::cardinality::ExactlyOne ;
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { broken: "broken" };
+}
+
+#[test]
+fn trapping_calls_write_a_core_dump() {
+
+	let engine = Engine::new( &wasm_link::debug::config() ).expect( "Invalid configuration" );
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let dir = std::env::temp_dir().join( format!( "wasm-link-core-dumps-{}", std::process::id() ));
+	let _ = std::fs::remove_dir_all( &dir );
+	let plugin = plugins.broken.plugin
+		.with_core_dumps( &dir )
+		.instantiate( &engine, &linker )
+		.expect( "Failed to instantiate plugin" );
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		ExactlyOne( "broken".to_string(), plugin ),
+	);
+	let args = [ Val::U32( 40 ), Val::U32( 2 )];
+
+	let ExactlyOne( _, result ) = binding.dispatch( "root", "add", &args ).expect( "Dispatch failed" );
+	assert!( matches!( result, Err( DispatchError::RuntimeException( _ ))), "{result:?}" );
+	let dump = std::fs::read( dir.join( "0-add.coredump" )).expect( "Missing core dump" );
+	assert!( dump.starts_with( b"\0asm" ));
+	std::fs::remove_dir_all( &dir ).expect( "Failed to remove core dumps" );
+
+}
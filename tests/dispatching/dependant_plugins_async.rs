@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::{ Arc, Mutex };
 use wasm_link::{ Binding, Engine, Linker, Val };
 use wasm_link::cardinality::ExactlyOne ;
 
@@ -65,6 +66,70 @@ fn links_and_dispatches_wit_async_across_plugin_stores_on_one_worker() {
 	});
 }
 
+/// Runs every task on `pool`, recording the name of the thread each one ran on.
+struct RecordingExecutor {
+	pool: futures::executor::ThreadPool,
+	threads: Arc<Mutex<Vec<String>>>,
+}
+
+impl RecordingExecutor {
+	fn pinned( name: &str, threads: &Arc<Mutex<Vec<String>>> ) -> Self {
+		Self {
+			pool: futures::executor::ThreadPool::builder()
+				.pool_size( 1 )
+				.name_prefix( name )
+				.create()
+				.expect( "Failed to create async executor" ),
+			threads: Arc::clone( threads ),
+		}
+	}
+}
+
+impl futures::task::Spawn for RecordingExecutor {
+	fn spawn_obj( &self, future: futures::task::FutureObj<'static, ()> ) -> Result<(), futures::task::SpawnError> {
+		let threads = Arc::clone( &self.threads );
+		self.pool.spawn_obj( futures::task::FutureObj::new( Box::pin( async move {
+			let name = std::thread::current().name().unwrap_or_default().to_string();
+			threads.lock().expect( "Thread log poisoned" ).push( name );
+			future.await ;
+		})))
+	}
+}
+
+#[test]
+fn pins_each_plugin_to_its_own_worker_for_synchronous_callers() {
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let threads = Arc::new( Mutex::new( Vec::new() ));
+
+	let child_instance = futures::executor::block_on( plugins.child.plugin
+		.instantiate_async( &engine, &linker, RecordingExecutor::pinned( "child-", &threads )))
+		.expect( "Failed to instantiate child plugin asynchronously" );
+	let dependency_binding = Binding::new(
+		bindings.dependency.package,
+		HashMap::from([( bindings.dependency.name, bindings.dependency.spec )]),
+		ExactlyOne( "_".to_string(), child_instance ),
+	);
+	let startup_instance = futures::executor::block_on( plugins.startup.plugin
+		.link_async( &engine, linker, vec![ dependency_binding ], RecordingExecutor::pinned( "startup-", &threads )))
+		.expect( "Failed to link startup plugin asynchronously" );
+	let root_binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		ExactlyOne( "_".to_string(), startup_instance ),
+	);
+
+	( 0..2 ).for_each(| _ | match futures::executor::block_on( root_binding.dispatch_async( "root", "get-primitive", &[] )) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+		value => panic!( "Expected Ok( ExactlyOne( Ok( U32( 42 )))), found: {:#?}", value ),
+	});
+
+	let threads = threads.lock().expect( "Thread log poisoned" ).clone();
+	assert_eq!( threads, [ "startup-0", "child-0", "startup-0", "child-0" ]);
+}
+
 #[test]
 fn reports_when_the_supplied_executor_rejects_dispatch() {
 	futures::executor::block_on( async {
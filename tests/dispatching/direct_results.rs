@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use wasm_link::{ Binding, Engine, Linker, SocketShapeError, Val };
+use wasm_link::cardinality::ExactlyOne ;
+
+fixtures! {
+	bindings = { child: "child", root: "root" };
+	plugins  = { child: "child", consumer: "consumer", wrapped: "wrapped" };
+}
+
+#[test]
+fn direct_results_reach_consumers_without_the_plugin_id() {
+
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+
+	let child = Binding::new(
+		bindings.child.package,
+		HashMap::from([( bindings.child.name, bindings.child.spec )]),
+		ExactlyOne( "_".to_string(), plugins.child.plugin
+			.instantiate( &engine, &linker )
+			.expect( "Failed to instantiate child plugin" )),
+	).with_direct_results();
+
+	let consumer = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		ExactlyOne( "_".to_string(), plugins.consumer.plugin
+			.link( &engine, linker.clone(), vec![ child.clone() ])
+			.expect( "Failed to link consumer plugin" )),
+	);
+
+	match consumer.dispatch( "root", "get-value", &[] ) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 43 )))) => {}
+		value => panic!( "Expected Ok( ExactlyOne( Ok( U32( 43 )))), found: {:#?}", value ),
+	}
+
+	// The host still receives the plugin id
+	match child.dispatch( "root", "get-value", &[] ) {
+		Ok( ExactlyOne( id, Ok( Val::U32( 42 )))) if id == "_" => {}
+		value => panic!( "Expected Ok( ExactlyOne( \"_\", Ok( U32( 42 )))), found: {:#?}", value ),
+	}
+
+}
+
+#[test]
+fn linking_expects_plain_results_from_bindings_with_direct_results() {
+
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+
+	let child = Binding::new(
+		bindings.child.package,
+		HashMap::from([( bindings.child.name, bindings.child.spec )]),
+		ExactlyOne( "_".to_string(), plugins.child.plugin
+			.instantiate( &engine, &linker )
+			.expect( "Failed to instantiate child plugin" )),
+	).with_direct_results();
+
+	let error = plugins.wrapped.plugin.link( &engine, linker, vec![ child ])
+		.expect_err( "Linking should fail" );
+	assert_eq!( error.downcast_ref::<SocketShapeError>(), Some( &SocketShapeError::ResultShape {
+		interface: "test:child/root".to_string(),
+		function: "get-value".to_string(),
+		expected: "result",
+		found: "tuple".to_string(),
+	}));
+
+}
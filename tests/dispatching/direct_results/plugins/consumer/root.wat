@@ -0,0 +1,38 @@
+(component
+	;; Expects the plain result of the single plugin
+	(import "test:child/root" (instance $child
+		(export "get-value" (func (result (result u32))))
+	))
+
+	(alias export $child "get-value" (func $get_child))
+
+	(core module $mem_module
+		(memory (export "memory") 1)
+	)
+	(core instance $mem_inst (instantiate $mem_module))
+	(alias core export $mem_inst "memory" (core memory $shared_mem))
+
+	(core func $lowered_get_child (canon lower (func $get_child) (memory $shared_mem)))
+	(core instance $imports_child (export "get-value" (func $lowered_get_child)))
+	(core instance $mem_imports (export "memory" (memory $shared_mem)))
+
+	(core module $main_impl
+		(import "child" "get-value" (func $get_child (param i32)))
+		(import "mem" "memory" (memory 1))
+
+		(func (export "get-value") (result i32)
+			(call $get_child (i32.const 0))
+			(i32.add (i32.load (i32.const 4)) (i32.const 1))
+		)
+	)
+
+	(core instance $main_inst (instantiate $main_impl
+		(with "child" (instance $imports_child))
+		(with "mem" (instance $mem_imports))
+	))
+
+	(alias core export $main_inst "get-value" (core func $core_get_value))
+	(func $lifted_get_value (result u32) (canon lift (core func $core_get_value)))
+	(instance $inst (export "get-value" (func $lifted_get_value)))
+	(export "test:consumer/root" (instance $inst))
+)
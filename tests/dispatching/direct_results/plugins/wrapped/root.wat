@@ -0,0 +1,6 @@
+(component
+	;; Expects the `tuple<plugin-id, result<u32>>` of an exactly-one socket without direct results
+	(import "test:child/root" (instance
+		(export "get-value" (func (result (tuple string (result u32)))))
+	))
+)
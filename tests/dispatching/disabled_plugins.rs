@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use std::sync::Arc ;
+use std::sync::atomic::{ AtomicBool, Ordering };
+use wasm_link::{ Binding, DispatchError, Engine, Linker, Val };
+use wasm_link::cardinality::Any ;
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { healthy: "healthy", misbehaving: "misbehaving" };
+}
+
+#[test]
+fn disabled_plugins_are_skipped_until_reenabled() {
+
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let instantiate = | data: crate::fixture_linking::PluginData | data.plugin
+		.instantiate( &engine, &linker )
+		.expect( "Failed to instantiate plugin" );
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		Any( HashMap::from([
+			( "healthy".to_string(), instantiate( plugins.healthy )),
+			( "misbehaving".to_string(), instantiate( plugins.misbehaving )),
+		])),
+	);
+	let args = [ Val::U32( 40 ), Val::U32( 2 )];
+
+	assert!( binding.set_enabled( &"misbehaving".to_string(), false ));
+	assert!( !binding.set_enabled( &"missing".to_string(), false ));
+	let Any( results ) = binding.dispatch( "root", "add", &args ).expect( "Dispatch failed" );
+	assert!( matches!( results.get( "healthy" ), Some( Ok( Val::U32( 42 )))), "{results:#?}" );
+	assert!( matches!( results.get( "misbehaving" ), Some( Err( DispatchError::PluginDisabled ))), "{results:#?}" );
+	assert_eq!( binding.lock_stats().0["misbehaving"].acquisitions, 0 );
+
+	assert!( binding.set_enabled( &"misbehaving".to_string(), true ));
+	assert_eq!( binding.is_enabled( &"misbehaving".to_string() ), Some( true ));
+	let Any( results ) = binding.dispatch( "root", "add", &args ).expect( "Dispatch failed" );
+	assert!( matches!( results.get( "misbehaving" ), Some( Ok( Val::U32( 43 )))), "{results:#?}" );
+
+}
+
+#[test]
+fn disabled_plugins_do_not_count_as_failed() {
+
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let instantiate = | data: crate::fixture_linking::PluginData | data.plugin
+		.instantiate( &engine, &linker )
+		.expect( "Failed to instantiate plugin" );
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		Any( HashMap::from([
+			( "healthy".to_string(), instantiate( plugins.healthy )),
+			( "misbehaving".to_string(), instantiate( plugins.misbehaving )),
+		])),
+	);
+	let all_failed = Arc::new( AtomicBool::new( false ));
+	let flag = Arc::clone( &all_failed );
+	binding.on_all_failed( move | _, _ | flag.store( true, Ordering::Relaxed ));
+
+	assert!( binding.set_enabled( &"healthy".to_string(), false ));
+	assert!( binding.set_enabled( &"misbehaving".to_string(), false ));
+	let Any( results ) = binding.dispatch( "root", "add", &[ Val::U32( 40 ), Val::U32( 2 )]).expect( "Dispatch failed" );
+	assert!( results.values().all(| result | matches!( result, Err( DispatchError::PluginDisabled ))), "{results:#?}" );
+	assert!( !all_failed.load( Ordering::Relaxed ));
+
+}
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+use std::sync::{ Arc, Mutex };
+use wasm_link::{ Binding, CallContext, DispatchId, Engine, Linker, Val };
+use wasm_link::cardinality::ExactlyOne ;
+
+fixtures! {
+	bindings = { root: "root", dependency: "dependency" };
+	plugins  = { startup: "startup", child: "child" };
+}
+
+type Seen = Arc<Mutex<Vec<( &'static str, Option<DispatchId> )>>>;
+
+fn recording_plugin( data: crate::fixture_linking::PluginData, name: &'static str, seen: &Seen ) -> wasm_link::Plugin<crate::fixture_linking::TestContext> {
+	let seen = Arc::clone( seen );
+	data.plugin.with_epoch_limiter( move | store, _, _, _ | {
+		let id = store.data().call_context.as_ref().map( CallContext::dispatch_id );
+		seen.lock().unwrap().push(( name, id ));
+		u64::MAX
+	})
+}
+
+#[test]
+fn dispatch_id_is_shared_by_nested_calls() {
+
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let seen = Seen::default();
+
+	let child_instance = recording_plugin( plugins.child, "child", &seen )
+		.instantiate( &engine, &linker )
+		.expect( "Failed to instantiate child plugin" );
+	let dependency_binding = Binding::new(
+		bindings.dependency.package,
+		HashMap::from([( bindings.dependency.name, bindings.dependency.spec )]),
+		ExactlyOne( "_".to_string(), child_instance ),
+	);
+	let startup_instance = recording_plugin( plugins.startup, "startup", &seen )
+		.link( &engine, linker.clone(), vec![ dependency_binding ])
+		.expect( "Failed to link startup plugin" );
+	let root_binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		ExactlyOne( "_".to_string(), startup_instance ),
+	);
+
+	let context = CallContext::new();
+	match root_binding.dispatch_with( &context, "root", "get-primitive", &[] ) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+		value => panic!( "Expected Ok( ExactlyOne( Ok( U32( 42 )))), found: {:#?}", value ),
+	}
+	assert_eq!( *seen.lock().unwrap(), vec![
+		( "startup", Some( context.dispatch_id() )),
+		( "child", Some( context.dispatch_id() )),
+	]);
+
+	seen.lock().unwrap().clear();
+	let _ = root_binding.dispatch( "root", "get-primitive", &[] );
+	let seen = seen.lock().unwrap();
+	assert_eq!( seen.len(), 2 );
+	assert_eq!( seen[0].1, seen[1].1 );
+	assert_ne!( seen[0].1, Some( context.dispatch_id() ));
+
+}
+
+#[test]
+fn dispatch_id_is_shared_by_nested_async_calls() {
+
+	futures::executor::block_on( async {
+		let engine = Engine::default();
+		let linker = Linker::new( &engine );
+		let executor = futures::executor::ThreadPool::new()
+			.expect( "Failed to create async executor" );
+		let plugins = fixtures::plugins( &engine );
+		let bindings = fixtures::bindings();
+		let seen = Seen::default();
+
+		let child_instance = recording_plugin( plugins.child, "child", &seen )
+			.instantiate_async( &engine, &linker, executor.clone() )
+			.await
+			.expect( "Failed to instantiate child plugin asynchronously" );
+		let dependency_binding = Binding::new(
+			bindings.dependency.package,
+			HashMap::from([( bindings.dependency.name, bindings.dependency.spec )]),
+			ExactlyOne( "_".to_string(), child_instance ),
+		);
+		let startup_instance = recording_plugin( plugins.startup, "startup", &seen )
+			.link_async( &engine, linker.clone(), vec![ dependency_binding ], executor )
+			.await
+			.expect( "Failed to link startup plugin asynchronously" );
+		let root_binding = Binding::new(
+			bindings.root.package,
+			HashMap::from([( bindings.root.name, bindings.root.spec )]),
+			ExactlyOne( "_".to_string(), startup_instance ),
+		);
+
+		let context = CallContext::new();
+		match root_binding.dispatch_async_with( &context, "root", "get-primitive", &[] ).await {
+			Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+			value => panic!( "Expected Ok( ExactlyOne( Ok( U32( 42 )))), found: {:#?}", value ),
+		}
+		assert_eq!( *seen.lock().unwrap(), vec![
+			( "startup", Some( context.dispatch_id() )),
+			( "child", Some( context.dispatch_id() )),
+		]);
+	});
+
+}
+
+#[test]
+fn call_metadata_reaches_nested_calls() {
+
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let seen = Arc::new( Mutex::new( Vec::new() ));
+
+	let recorded = Arc::clone( &seen );
+	let child_instance = plugins.child.plugin
+		.with_epoch_limiter( move | store, _, _, _ | {
+			let tenant = store.data().call_context.as_ref().and_then(| context | context.metadata().get( "tenant" ).cloned() );
+			recorded.lock().unwrap().push( tenant );
+			u64::MAX
+		})
+		.instantiate( &engine, &linker )
+		.expect( "Failed to instantiate child plugin" );
+	let dependency_binding = Binding::new(
+		bindings.dependency.package,
+		HashMap::from([( bindings.dependency.name, bindings.dependency.spec )]),
+		ExactlyOne( "_".to_string(), child_instance ),
+	);
+	let startup_instance = plugins.startup.plugin
+		.link( &engine, linker.clone(), vec![ dependency_binding ])
+		.expect( "Failed to link startup plugin" );
+	let root_binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		ExactlyOne( "_".to_string(), startup_instance ),
+	);
+
+	for tenant in [ "acme", "globex" ] {
+		let context = CallContext::new().with_metadata( HashMap::from([
+			( "tenant".to_string(), Val::String( tenant.to_string() )),
+		]));
+		let _ = root_binding.dispatch_with( &context, "root", "get-primitive", &[] );
+	}
+	let _ = root_binding.dispatch( "root", "get-primitive", &[] );
+	assert_eq!( *seen.lock().unwrap(), vec![
+		Some( Val::String( "acme".to_string() )),
+		Some( Val::String( "globex".to_string() )),
+		None,
+	]);
+
+}
+
+#[test]
+fn traced_dispatch_returns_the_dispatch_id() {
+
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let seen = Seen::default();
+
+	let child_instance = recording_plugin( plugins.child, "child", &seen )
+		.instantiate( &engine, &linker )
+		.expect( "Failed to instantiate child plugin" );
+	let dependency_binding = Binding::new(
+		bindings.dependency.package,
+		HashMap::from([( bindings.dependency.name, bindings.dependency.spec )]),
+		ExactlyOne( "_".to_string(), child_instance ),
+	);
+
+	let ( id, result ) = dependency_binding.dispatch_traced( "root", "get-value", &[] );
+	match result {
+		Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+		value => panic!( "Expected Ok( ExactlyOne( Ok( U32( 42 )))), found: {:#?}", value ),
+	}
+	assert_eq!( *seen.lock().unwrap(), vec![( "child", Some( id ))]);
+
+	let ( failed_id, result ) = dependency_binding.dispatch_traced( "root", "missing", &[] );
+	assert!( matches!( result, Err( wasm_link::DispatchError::InvalidFunction( _ ))));
+	assert_ne!( failed_id, id );
+
+}
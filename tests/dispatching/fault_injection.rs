@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+use std::time::{ Duration, Instant };
+use wasm_link::{ Binding, DispatchError, Engine, Fault, FaultInjector, Linker, Val };
+use wasm_link::cardinality::ExactlyOne ;
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { get_value: "get-value" };
+}
+
+fn dispatch_with_fault( fault: Fault ) -> Result<ExactlyOne<String, Result<Val, DispatchError>>, DispatchError> {
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let plugin_instance = plugins.get_value.plugin
+		.with_fault_injector( FaultInjector::new( 0, 1.0, vec![ fault ]))
+		.instantiate( &engine, &linker )
+		.expect( "Failed to instantiate plugin" );
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		ExactlyOne( "_".to_string(), plugin_instance ),
+	);
+	binding.dispatch( "root", "get-primitive", &[] )
+}
+
+#[test]
+fn injected_lock_rejection_skips_the_plugin() {
+	match dispatch_with_fault( Fault::LockRejected ) {
+		Ok( ExactlyOne( _, Err( DispatchError::LockRejected ))) => {}
+		value => panic!( "Expected injected LockRejected, found: {:#?}", value ),
+	}
+}
+
+#[test]
+fn injected_trap_is_reported_as_runtime_exception() {
+	match dispatch_with_fault( Fault::Trap( "injected".to_string() )) {
+		Ok( ExactlyOne( _, Err( DispatchError::RuntimeException( err )))) => assert_eq!( err.to_string(), "injected" ),
+		value => panic!( "Expected injected RuntimeException, found: {:#?}", value ),
+	}
+}
+
+#[test]
+fn injected_latency_delays_a_successful_call() {
+	let start = Instant::now();
+	match dispatch_with_fault( Fault::Latency( Duration::from_millis( 20 ))) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+		value => panic!( "Expected delayed U32( 42 ), found: {:#?}", value ),
+	}
+	assert!( start.elapsed() >= Duration::from_millis( 20 ));
+}
+
+#[test]
+fn injected_corruption_replaces_the_result() {
+	match dispatch_with_fault( Fault::corrupt(| call | ( call.function_name() == "get-primitive" ).then_some( Val::U32( 7 )))) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 7 )))) => {}
+		value => panic!( "Expected corrupted U32( 7 ), found: {:#?}", value ),
+	}
+}
+
+#[test]
+fn injected_corruption_leaves_results_it_has_no_value_for() {
+	match dispatch_with_fault( Fault::corrupt(| call | ( call.function_name() == "other" ).then_some( Val::String( "x".to_string() )))) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+		value => panic!( "Expected intact U32( 42 ), found: {:#?}", value ),
+	}
+}
+
+#[test]
+fn injected_latency_delays_an_async_call() {
+	futures::executor::block_on( async {
+		let engine = Engine::default();
+		let linker = Linker::new( &engine );
+		let executor = futures::executor::ThreadPool::builder().pool_size( 1 ).create()
+			.expect( "Failed to create async executor" );
+		let plugins = fixtures::plugins( &engine );
+		let bindings = fixtures::bindings();
+		let plugin_instance = plugins.get_value.plugin
+			.with_fault_injector( FaultInjector::new( 0, 1.0, vec![ Fault::Latency( Duration::from_millis( 20 ))]))
+			.instantiate_async( &engine, &linker, executor )
+			.await
+			.expect( "Failed to instantiate plugin" );
+		let binding = Binding::new(
+			bindings.root.package,
+			HashMap::from([( bindings.root.name, bindings.root.spec )]),
+			ExactlyOne( "_".to_string(), plugin_instance ),
+		);
+		let start = Instant::now();
+		match binding.dispatch_async( "root", "get-primitive", &[] ).await {
+			Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+			value => panic!( "Expected delayed U32( 42 ), found: {:#?}", value ),
+		}
+		assert!( start.elapsed() >= Duration::from_millis( 20 ));
+	});
+}
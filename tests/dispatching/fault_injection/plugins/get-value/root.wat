@@ -0,0 +1,13 @@
+(component
+	(core module $m
+		(func $get_value (export "get-primitive") (result i32)
+			i32.const 42
+		)
+	)
+	(core instance $i (instantiate $m))
+	(func $f (export "get-primitive") (result u32) (canon lift (core func $i "get-primitive")))
+	(instance $inst
+		(export "get-primitive" (func $f))
+	)
+	(export "test:primitive/root" (instance $inst))
+)
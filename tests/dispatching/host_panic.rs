@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+use std::sync::Arc ;
+use std::sync::atomic::{ AtomicUsize, Ordering };
+use wasm_link::{ Binding, DispatchError, Engine, Linker, PanicPolicy, Val };
+use wasm_link::cardinality::ExactlyOne ;
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { get_value: "get-value" };
+}
+
+/// Dispatches twice to a plugin whose dispatch observer panics on the first call only.
+fn dispatch_twice( policy: PanicPolicy ) -> [Result<Val, DispatchError>; 2] {
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let calls = Arc::new( AtomicUsize::new( 0 ));
+	let plugin_instance = plugins.get_value.plugin
+		.with_dispatch_observer( move | _ | assert!( calls.fetch_add( 1, Ordering::Relaxed ) > 0, "observer bug" ))
+		.with_panic_policy( policy )
+		.instantiate( &engine, &linker )
+		.expect( "Failed to instantiate plugin" );
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		ExactlyOne( "_".to_string(), plugin_instance ),
+	);
+	let dispatch = || match binding.dispatch( "root", "get-primitive", &[] ) {
+		Ok( ExactlyOne( _, result )) => result,
+		Err( err ) => panic!( "Expected the dispatch to reach the plugin, found: {err:#?}" ),
+	};
+	[ dispatch(), dispatch() ]
+}
+
+#[test]
+fn host_panics_disable_the_plugin_by_default() {
+	match dispatch_twice( PanicPolicy::default() ) {
+		[ Err( DispatchError::HostPanic( first )), Err( DispatchError::HostPanic( second ))] => {
+			assert_eq!( first, "observer bug" );
+			assert_eq!( second, "observer bug" );
+		}
+		results => panic!( "Expected both calls to fail with the panic, found: {results:#?}" ),
+	}
+}
+
+#[test]
+fn host_panics_can_leave_the_plugin_in_service() {
+	match dispatch_twice( PanicPolicy::Continue ) {
+		[ Err( DispatchError::HostPanic( _ )), Ok( Val::U32( 42 ))] => {}
+		results => panic!( "Expected only the first call to fail, found: {results:#?}" ),
+	}
+}
+
+#[test]
+fn host_panics_can_be_propagated() {
+	let panic = std::panic::catch_unwind(|| dispatch_twice( PanicPolicy::Propagate )).expect_err( "the panic was caught" );
+	assert_eq!( panic.downcast_ref::<&str>(), Some( &"observer bug" ));
+}
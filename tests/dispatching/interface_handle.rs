@@ -0,0 +1,38 @@
+use std::collections::HashMap;
+use wasm_link::{ Binding, DispatchError, Engine, Linker, Val };
+use wasm_link::cardinality::ExactlyOne ;
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { adder: "adder" };
+}
+
+#[test]
+fn interface_handles_dispatch_to_their_interface() {
+
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let plugin_instance = plugins.adder.plugin
+		.instantiate( &engine, &linker )
+		.expect( "Failed to instantiate plugin" );
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		ExactlyOne( "_".to_string(), plugin_instance ),
+	);
+
+	assert!( binding.interface( "missing" ).is_none() );
+	let root = binding.interface( "root" ).expect( "Interface not found" );
+	assert_eq!( root.name(), "root" );
+	assert_eq!( root.path(), "test:dispatch-error/root" );
+	assert_eq!( root.functions().map(|( name, _ )| name ).collect::<Vec<_>>(), [ "add" ]);
+
+	match root.call( "add", &[ Val::U32( 40 ), Val::U32( 2 )]) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+		value => panic!( "Expected Ok( ExactlyOne( Ok( U32( 42 )))), found: {:#?}", value ),
+	}
+	assert!( matches!( root.call( "missing", &[] ), Err( DispatchError::InvalidFunction( _ ))));
+
+}
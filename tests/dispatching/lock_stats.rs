@@ -0,0 +1,130 @@
+use std::collections::{ HashMap, HashSet };
+use std::sync::{ Arc, Mutex, OnceLock };
+use wasm_link::{ Binding, DispatchError, Engine, Function, FunctionKind, Interface, Linker, LockStats, ReturnKind, Val };
+use wasm_link::cardinality::ExactlyOne ;
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { plugin: "plugin" };
+}
+
+#[test]
+fn lock_stats_count_sync_dispatches() {
+
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+
+	let instance = plugins.plugin.plugin
+		.instantiate( &engine, &linker )
+		.expect( "Failed to instantiate plugin" );
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		ExactlyOne( "_".to_string(), instance ),
+	);
+
+	let ExactlyOne( _, stats ) = binding.lock_stats();
+	assert_eq!( stats, LockStats::default() );
+
+	for _ in 0..3 {
+		match binding.dispatch( "root", "get-value", &[] ) {
+			Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+			value => panic!( "Expected Ok( ExactlyOne( Ok( U32( 42 )))), found: {:#?}", value ),
+		}
+	}
+
+	let ExactlyOne( _, stats ) = binding.lock_stats();
+	assert_eq!( stats.acquisitions, 3 );
+	assert_eq!( stats.contended, 0 );
+	assert_eq!( stats.rejected, 0 );
+	assert_eq!( stats.wait_histogram[0], 3 );
+
+}
+
+#[test]
+fn try_dispatch_async_dispatches_to_idle_plugin() {
+	futures::executor::block_on( async {
+		let engine = Engine::default();
+		let linker = Linker::new( &engine );
+		let executor = futures::executor::ThreadPool::new()
+			.expect( "Failed to create async executor" );
+		let plugins = fixtures::plugins( &engine );
+		let bindings = fixtures::bindings();
+
+		let instance = plugins.plugin.plugin
+			.instantiate_async( &engine, &linker, executor )
+			.await
+			.expect( "Failed to instantiate plugin asynchronously" );
+		let binding = Binding::new(
+			bindings.root.package,
+			HashMap::from([( bindings.root.name, bindings.root.spec )]),
+			ExactlyOne( "_".to_string(), instance ),
+		);
+
+		match binding.try_dispatch_async( "root", "get-value", &[] ).await {
+			Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+			value => panic!( "Expected Ok( ExactlyOne( Ok( U32( 42 )))), found: {:#?}", value ),
+		}
+		let _ = binding.dispatch_async( "root", "get-value", &[] ).await;
+
+		let ExactlyOne( _, stats ) = binding.lock_stats();
+		assert_eq!( stats.acquisitions, 2 );
+		assert_eq!( stats.rejected, 0 );
+	});
+}
+
+/// Dispatches to the plugin again while its first dispatch still holds the lock.
+fn nested_dispatch( function: Function ) -> Result<Val, DispatchError> {
+
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let bindings = fixtures::bindings();
+	let binding = Arc::new( OnceLock::<Binding<String, crate::fixture_linking::TestContext>>::new() );
+	let nested = Arc::new( Mutex::new( None ));
+
+	let ( reentrant_binding, nested_result ) = ( Arc::clone( &binding ), Arc::clone( &nested ));
+	let instance = fixtures::plugins( &engine ).plugin.plugin
+		.with_epoch_limiter( move | _, _, _, _ | {
+			let mut nested_result = nested_result.lock().unwrap();
+			if let ( Some( binding ), None ) = ( reentrant_binding.get(), &*nested_result ) {
+				*nested_result = Some( binding.dispatch( "root", "get-value", &[] ).map(| ExactlyOne( _, result ) | result ));
+			}
+			u64::MAX
+		})
+		.instantiate( &engine, &linker )
+		.expect( "Failed to instantiate plugin" );
+	let replica = fixtures::plugins( &engine ).plugin.plugin
+		.instantiate( &engine, &linker )
+		.expect( "Failed to instantiate replica" );
+	binding.set( Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, Interface::new(
+			HashMap::from([( "get-value".to_string(), function )]),
+			HashSet::new(),
+		))]),
+		ExactlyOne( "_".to_string(), instance.with_replicas([ replica ])),
+	)).unwrap_or_else(| _ | unreachable!() );
+
+	match binding.get().unwrap().dispatch( "root", "get-value", &[] ) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+		value => panic!( "Expected Ok( ExactlyOne( Ok( U32( 42 )))), found: {:#?}", value ),
+	}
+	let result = nested.lock().unwrap().take().expect( "limiter did not run" ).expect( "nested dispatch failed" );
+	result
+
+}
+
+#[test]
+fn concurrent_functions_are_served_by_idle_replicas() {
+	let function = Function::new_async( FunctionKind::Freestanding, ReturnKind::AssumeNoResources );
+	match nested_dispatch( function.clone().concurrent() ) {
+		Ok( Val::U32( 42 )) => {}
+		value => panic!( "Expected Ok( U32( 42 )), found: {:#?}", value ),
+	}
+	match nested_dispatch( function ) {
+		Err( DispatchError::LockRejected ) => {}
+		value => panic!( "Expected Err( LockRejected ), found: {:#?}", value ),
+	}
+}
@@ -0,0 +1,47 @@
+use std::collections::HashMap ;
+use wasm_link::{ Engine, ExportError, Linker, Remap };
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { root: "root", typo: "typo", remapped: "remapped" };
+}
+
+fn declared() -> String {
+	let bindings = fixtures::bindings();
+	format!( "{}/{}", bindings.root.package, bindings.root.name )
+}
+
+#[test]
+fn strict_exports_accept_declared_interfaces() {
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	fixtures::plugins( &engine ).root.plugin
+		.with_strict_exports([ declared() ])
+		.instantiate( &engine, &linker )
+		.expect( "Failed to instantiate plugin" );
+}
+
+#[test]
+fn strict_exports_reject_undeclared_interfaces() {
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let err = fixtures::plugins( &engine ).typo.plugin
+		.with_strict_exports([ declared() ])
+		.instantiate( &engine, &linker )
+		.expect_err( "Instantiation should fail" );
+	assert_eq!(
+		err.downcast_ref::<ExportError>(),
+		Some( &ExportError::Undeclared( "test:strict-export/root".to_string() )),
+	);
+}
+
+#[test]
+fn strict_exports_accept_remapped_interfaces() {
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	fixtures::plugins( &engine ).remapped.plugin
+		.remap_interfaces( HashMap::from([( "root".to_string(), Remap::found_as( "legacy-root" ))]))
+		.with_strict_exports([ declared() ])
+		.instantiate( &engine, &linker )
+		.expect( "Failed to instantiate plugin" );
+}
@@ -0,0 +1,13 @@
+(component
+	(core module $m
+		(func $get_value (export "get-value") (result i32)
+			i32.const 7
+		)
+	)
+	(core instance $i (instantiate $m))
+	(func $f (export "get-value") (result u32) (canon lift (core func $i "get-value")))
+	(instance $inst
+		(export "get-value" (func $f))
+	)
+	(export "test:strict-exports/legacy-root" (instance $inst))
+)
@@ -0,0 +1,50 @@
+use std::collections::{ HashMap, HashSet };
+use wasm_link::{ Binding, Engine, Function, FunctionKind, Interface, Linker, ReturnKind, Val };
+use wasm_link::cardinality::{ Any, ExactlyOne };
+
+fixtures! {
+	bindings = { root: "root", consumer: "consumer" };
+	plugins  = { a: "a", b: "b", consumer: "consumer" };
+}
+
+#[test]
+fn targeted_functions_route_to_the_named_plugin() {
+
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let instantiate = | data: crate::fixture_linking::PluginData | data.plugin
+		.instantiate( &engine, &linker )
+		.expect( "Failed to instantiate plugin" );
+
+	let provider_binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, Interface::new(
+			HashMap::from([( "add".to_string(), Function::new( FunctionKind::Targeted, ReturnKind::AssumeNoResources ))]),
+			HashSet::new(),
+		))]),
+		Any( HashMap::from([
+			( "a".to_string(), instantiate( plugins.a )),
+			( "b".to_string(), instantiate( plugins.b )),
+		])),
+	);
+	let consumer_instance = plugins.consumer.plugin
+		.link( &engine, linker.clone(), vec![ provider_binding ])
+		.expect( "Failed to link consumer plugin" );
+	let consumer_binding = Binding::new(
+		bindings.consumer.package,
+		HashMap::from([( bindings.consumer.name, bindings.consumer.spec )]),
+		ExactlyOne( "_".to_string(), consumer_instance ),
+	);
+
+	match consumer_binding.dispatch( "root", "via-a", &[] ) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+		value => panic!( "Expected plugin a's result, found: {:#?}", value ),
+	}
+	match consumer_binding.dispatch( "root", "via-b", &[] ) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 43 )))) => {}
+		value => panic!( "Expected plugin b's result, found: {:#?}", value ),
+	}
+
+}
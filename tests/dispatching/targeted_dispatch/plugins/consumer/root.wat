@@ -0,0 +1,45 @@
+(component
+	(import "test:targeted/root" (instance $provider
+		(export "add" (func (param "plugin" string) (param "a" u32) (param "b" u32) (result (result u32))))
+	))
+
+	(alias export $provider "add" (func $add))
+
+	(core module $mem_module
+		(memory (export "memory") 1)
+		(data (i32.const 0) "ab")
+	)
+	(core instance $mem_inst (instantiate $mem_module))
+	(alias core export $mem_inst "memory" (core memory $shared_mem))
+
+	(core func $lowered_add (canon lower (func $add) (memory $shared_mem)))
+	(core instance $imports_provider (export "add" (func $lowered_add)))
+	(core instance $mem_imports (export "memory" (memory $shared_mem)))
+
+	(core module $main_impl
+		(import "provider" "add" (func $add (param i32 i32 i32 i32 i32)))
+		(import "mem" "memory" (memory 1))
+
+		(func (export "via-a") (result i32)
+			(call $add (i32.const 0) (i32.const 1) (i32.const 40) (i32.const 2) (i32.const 16))
+			(i32.load (i32.const 20))
+		)
+		(func (export "via-b") (result i32)
+			(call $add (i32.const 1) (i32.const 1) (i32.const 40) (i32.const 2) (i32.const 16))
+			(i32.load (i32.const 20))
+		)
+	)
+
+	(core instance $main_inst (instantiate $main_impl
+		(with "provider" (instance $imports_provider))
+		(with "mem" (instance $mem_imports))
+	))
+
+	(func $via_a (result u32) (canon lift (core func $main_inst "via-a")))
+	(func $via_b (result u32) (canon lift (core func $main_inst "via-b")))
+	(instance $inst
+		(export "via-a" (func $via_a))
+		(export "via-b" (func $via_b))
+	)
+	(export "test:consumer/root" (instance $inst))
+)
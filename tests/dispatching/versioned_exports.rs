@@ -0,0 +1,37 @@
+use std::collections::HashMap ;
+use wasm_link::{ Binding, Engine, Linker, Val };
+use wasm_link::cardinality::ExactlyOne ;
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { versioned: "versioned", nested: "nested" };
+}
+
+fn dispatch( engine: &Engine, plugin: crate::fixture_linking::PluginData ) -> Val {
+	let linker = Linker::new( engine );
+	let bindings = fixtures::bindings();
+	let plugin = plugin.plugin
+		.instantiate( engine, &linker )
+		.expect( "Failed to instantiate plugin" );
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		ExactlyOne( "_".to_string(), plugin ),
+	);
+	match binding.dispatch( "root", "get-value", &[] ) {
+		Ok( ExactlyOne( _, Ok( value ))) => value,
+		value => panic!( "Expected Ok( ExactlyOne( Ok( _ ))), found: {:#?}", value ),
+	}
+}
+
+#[test]
+fn dispatch_resolves_the_highest_versioned_export() {
+	let engine = Engine::default();
+	assert_eq!( dispatch( &engine, fixtures::plugins( &engine ).versioned ), Val::U32( 2 ));
+}
+
+#[test]
+fn dispatch_resolves_nested_exports() {
+	let engine = Engine::default();
+	assert_eq!( dispatch( &engine, fixtures::plugins( &engine ).nested ), Val::U32( 3 ));
+}
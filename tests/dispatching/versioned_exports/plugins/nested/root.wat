@@ -0,0 +1,10 @@
+(component
+	(core module $m
+		(func (export "get-value") (result i32) i32.const 3)
+	)
+	(core instance $i (instantiate $m))
+	(func $f (result u32) (canon lift (core func $i "get-value")))
+	(instance $inst (export "get-value" (func $f)))
+	(instance $outer (export "test:versioned-exports/root" (instance $inst)))
+	(export "outer" (instance $outer))
+)
@@ -0,0 +1,13 @@
+(component
+	(core module $m
+		(func (export "old") (result i32) i32.const 1)
+		(func (export "new") (result i32) i32.const 2)
+	)
+	(core instance $i (instantiate $m))
+	(func $old (result u32) (canon lift (core func $i "old")))
+	(func $new (result u32) (canon lift (core func $i "new")))
+	(instance $old_inst (export "get-value" (func $old)))
+	(instance $new_inst (export "get-value" (func $new)))
+	(export "test:versioned-exports/root@1.2.0" (instance $old_inst))
+	(export "test:versioned-exports/root@1.10.0" (instance $new_inst))
+)
@@ -0,0 +1,46 @@
+#![cfg( feature = "wave" )]
+
+use std::collections::HashMap;
+use wasm_link::{ Binding, Engine, Linker };
+use wasm_link::cardinality::ExactlyOne ;
+use wasm_link::wave::WaveError ;
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { test_plugin: "test-plugin" };
+}
+
+fn binding( engine: &Engine ) -> Binding<String, crate::fixture_linking::TestContext> {
+	let linker = Linker::new( engine );
+	let plugins = fixtures::plugins( engine );
+	let bindings = fixtures::bindings();
+	let plugin_instance = plugins.test_plugin.plugin
+		.instantiate( engine, &linker )
+		.expect( "Failed to instantiate plugin" );
+	Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		ExactlyOne( "_".to_string(), plugin_instance ),
+	)
+}
+
+#[test]
+fn dispatch_wave_parses_arguments_and_formats_result() {
+	let engine = Engine::default();
+	match binding( &engine ).dispatch_wave( "root", "add", "40, 2" ) {
+		Ok( ExactlyOne( _, Ok( result ))) if result == "42" => {}
+		value => panic!( "Expected Ok( ExactlyOne( Ok( \"42\" ))), found: {:#?}", value ),
+	}
+}
+
+#[test]
+fn dispatch_wave_rejects_mistyped_arguments() {
+	let engine = Engine::default();
+	let binding = binding( &engine );
+	for args in [ "40", "40, \"2\"", "40, 2, 1", "40 2" ] {
+		match binding.dispatch_wave( "root", "add", args ) {
+			Ok( ExactlyOne( _, Err( WaveError::InvalidArguments( _ )))) => {}
+			value => panic!( "Expected InvalidArguments for {args:?}, found: {:#?}", value ),
+		}
+	}
+}
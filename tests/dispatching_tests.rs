@@ -16,6 +16,22 @@ include!( "test_utils/assert_no_warnings.rs" );
 	mod single_plugin_expect_primitive ;
 	mod single_plugin_void ;
 	mod debug_output ;
+	mod fault_injection ;
+	mod dispatch_id ;
+	mod lock_stats ;
+	mod wave_arguments ;
+	mod conformance ;
+	mod canary_rollout ;
+	mod disabled_plugins ;
+	mod all_implementations_failed ;
+	mod core_dumps ;
+	mod component_info ;
+	mod interface_handle ;
+	mod direct_results ;
+	mod host_panic ;
+	mod targeted_dispatch ;
+	mod versioned_exports ;
+	mod strict_exports ;
 	mod remap_interface_name ;
 	mod remap_single_item_name ;
 	mod remap_multiple_item_names ;
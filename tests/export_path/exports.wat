@@ -0,0 +1,8 @@
+(component
+	(instance $empty)
+	(instance $outer (export "my:pkg/nested" (instance $empty)) (export "my:pkg/api" (instance $empty)))
+	(export "my:pkg/api" (instance $empty))
+	(export "my:pkg/versioned@1.2.0" (instance $empty))
+	(export "my:pkg/versioned@1.10.0" (instance $empty))
+	(export "outer" (instance $outer))
+)
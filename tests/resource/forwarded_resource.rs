@@ -0,0 +1,105 @@
+use std::collections::{ HashMap, HashSet };
+use wasm_link::{ Binding, Engine, Function, FunctionKind, Interface, Linker, ReturnKind, Val };
+use wasm_link::cardinality::ExactlyOne ;
+
+fixtures! {
+	bindings = { root: "root", dependency: "dependency" };
+	plugins  = { consumer: "consumer", forwarder: "forwarder", counter: "counter" };
+}
+
+// `use test:myresource/root.{ counter }` cannot be resolved from a standalone fixture
+fn forwarder_interface() -> Interface {
+	Interface::new(
+		HashMap::from([( "forward".to_string(), Function::new( FunctionKind::Freestanding, ReturnKind::MayContainResources ))]),
+		HashSet::new(),
+	)
+}
+
+#[test]
+fn forwarded_resources_keep_their_owner() {
+
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+
+	let counter_instance = plugins.counter.plugin
+		.instantiate( &engine, &linker )
+		.expect( "Failed to instantiate counter plugin" );
+	let dependency_binding = Binding::new(
+		bindings.dependency.package,
+		HashMap::from([( bindings.dependency.name, bindings.dependency.spec )]),
+		ExactlyOne( "counter".to_string(), counter_instance ),
+	);
+
+	let forwarder_instance = plugins.forwarder.plugin
+		.link( &engine, linker.clone(), vec![ dependency_binding.clone() ])
+		.expect( "Failed to link forwarder plugin" );
+	let forwarder_binding = Binding::new(
+		"test:forwarder",
+		HashMap::from([( "root".to_string(), forwarder_interface() )]),
+		ExactlyOne( "forwarder".to_string(), forwarder_instance ),
+	);
+
+	let consumer_instance = plugins.consumer.plugin
+		.link( &engine, linker, vec![ dependency_binding, forwarder_binding ])
+		.expect( "Failed to link consumer plugin" );
+	let root_binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		ExactlyOne( "_".to_string(), consumer_instance ),
+	);
+
+	match root_binding.dispatch( "root", "get-value", &[] ) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+		value => panic!( "Expected the counter plugin to serve the forwarded counter, got: {:#?}", value ),
+	}
+
+}
+
+#[test]
+fn forwarded_resources_keep_their_owner_async() {
+	futures::executor::block_on( async {
+		let engine = Engine::default();
+		let linker = Linker::new( &engine );
+		let executor = futures::executor::ThreadPool::new()
+			.expect( "Failed to create async executor" );
+		let plugins = fixtures::plugins( &engine );
+		let bindings = fixtures::bindings();
+
+		let counter_instance = plugins.counter.plugin
+			.instantiate_async( &engine, &linker, executor.clone() )
+			.await
+			.expect( "Failed to instantiate counter plugin asynchronously" );
+		let dependency_binding = Binding::new(
+			bindings.dependency.package,
+			HashMap::from([( bindings.dependency.name, bindings.dependency.spec )]),
+			ExactlyOne( "counter".to_string(), counter_instance ),
+		);
+
+		let forwarder_instance = plugins.forwarder.plugin
+			.link_async( &engine, linker.clone(), vec![ dependency_binding.clone() ], executor.clone() )
+			.await
+			.expect( "Failed to link forwarder plugin asynchronously" );
+		let forwarder_binding = Binding::new(
+			"test:forwarder",
+			HashMap::from([( "root".to_string(), forwarder_interface() )]),
+			ExactlyOne( "forwarder".to_string(), forwarder_instance ),
+		);
+
+		let consumer_instance = plugins.consumer.plugin
+			.link_async( &engine, linker, vec![ dependency_binding, forwarder_binding ], executor )
+			.await
+			.expect( "Failed to link consumer plugin asynchronously" );
+		let root_binding = Binding::new(
+			bindings.root.package,
+			HashMap::from([( bindings.root.name, bindings.root.spec )]),
+			ExactlyOne( "_".to_string(), consumer_instance ),
+		);
+
+		match root_binding.dispatch_async( "root", "get-value", &[] ).await {
+			Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+			value => panic!( "Expected the counter plugin to serve the forwarded counter, got: {:#?}", value ),
+		}
+	});
+}
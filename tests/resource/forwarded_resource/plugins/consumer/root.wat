@@ -0,0 +1,69 @@
+(component
+	;; Import the resource interface from the counter plugin
+	(import "test:myresource/root" (instance $resource_inst
+		(export "counter" (type $counter (sub resource)))
+		(export "[method]counter.get-value" (func (param "self" (borrow $counter)) (result (result u32))))
+	))
+	(alias export $resource_inst "counter" (type $counter))
+	(alias export $resource_inst "[method]counter.get-value" (func $get_wrapped))
+
+	;; Import the forwarder, which hands out counters it does not own
+	(import "test:forwarder/root" (instance $forwarder_inst
+		(alias outer 1 $counter (type $counter))
+		(export "forward" (func (result (tuple string (result (own $counter))))))
+	))
+	(alias export $forwarder_inst "forward" (func $forward_wrapped))
+
+	;; Memory provider module
+	(core module $mem_module
+		(memory (export "memory") 1)
+		(func (export "realloc") (param i32 i32 i32 i32) (result i32)
+			i32.const 256
+		)
+	)
+	(core instance $mem_inst (instantiate $mem_module))
+	(alias core export $mem_inst "memory" (core memory $shared_mem))
+	(alias core export $mem_inst "realloc" (core func $shared_realloc))
+
+	(core func $lowered_forward (canon lower (func $forward_wrapped) (memory $shared_mem) (realloc $shared_realloc)))
+	(core func $lowered_get (canon lower (func $get_wrapped) (memory $shared_mem) (realloc $shared_realloc)))
+
+	(core module $main_impl
+		(import "imports" "forward" (func $forward (param i32)))
+		(import "imports" "get" (func $get (param i32 i32)))
+		(import "mem" "memory" (memory 1))
+
+		(func (export "get-value") (result i32)
+			;; Call forward with retptr = 0
+			i32.const 0
+			call $forward
+
+			;; Call get-value on the forwarded handle with retptr = 16
+			(call $get
+				(i32.load (i32.const 12))
+				(i32.const 16)
+			)
+
+			;; Return the value at offset 20
+			(i32.load (i32.const 20))
+		)
+	)
+
+	(core instance $main_inst (instantiate $main_impl
+		(with "imports" (instance
+			(export "forward" (func $lowered_forward))
+			(export "get" (func $lowered_get))
+		))
+		(with "mem" (instance (export "memory" (memory $shared_mem))))
+	))
+
+	(alias core export $main_inst "get-value" (core func $core_get_value))
+	(func $lifted_get_value (result u32)
+		(canon lift (core func $core_get_value))
+	)
+
+	(instance $consumer_inst
+		(export "get-value" (func $lifted_get_value))
+	)
+	(export "test:consumer/root" (instance $consumer_inst))
+)
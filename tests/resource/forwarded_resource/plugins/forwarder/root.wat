@@ -0,0 +1,52 @@
+(component
+	;; Import the resource interface from the counter plugin
+	;; When calling across plugin boundaries, results are wrapped in tuple<plugin-id, result<T>>
+	(import "test:myresource/root" (instance $resource_inst
+		(export "counter" (type $counter (sub resource)))
+		(export "make-counter" (func (result (tuple string (result (own $counter))))))
+	))
+
+	(alias export $resource_inst "counter" (type $counter))
+	(alias export $resource_inst "make-counter" (func $make_counter_wrapped))
+
+	;; Memory provider module
+	(core module $mem_module
+		(memory (export "memory") 1)
+		(func (export "realloc") (param i32 i32 i32 i32) (result i32)
+			i32.const 256
+		)
+	)
+	(core instance $mem_inst (instantiate $mem_module))
+	(alias core export $mem_inst "memory" (core memory $shared_mem))
+	(alias core export $mem_inst "realloc" (core func $shared_realloc))
+
+	(core func $lowered_make_counter (canon lower (func $make_counter_wrapped) (memory $shared_mem) (realloc $shared_realloc)))
+
+	(core module $main_impl
+		(import "resource" "make-counter" (func $make_counter (param i32)))
+		(import "mem" "memory" (memory 1))
+
+		;; Passes on the counter created by the counter plugin
+		(func (export "forward") (result i32)
+			i32.const 0
+			call $make_counter
+			;; The handle sits behind the plugin id and the result discriminant
+			(i32.load (i32.const 12))
+		)
+	)
+
+	(core instance $main_inst (instantiate $main_impl
+		(with "resource" (instance (export "make-counter" (func $lowered_make_counter))))
+		(with "mem" (instance (export "memory" (memory $shared_mem))))
+	))
+
+	(alias core export $main_inst "forward" (core func $core_forward))
+	(func $lifted_forward (result (own $counter))
+		(canon lift (core func $core_forward))
+	)
+
+	(instance $forwarder_inst
+		(export "forward" (func $lifted_forward))
+	)
+	(export "test:forwarder/root" (instance $forwarder_inst))
+)
@@ -0,0 +1,83 @@
+use std::collections::{ HashMap, HashSet };
+use std::sync::{ Arc, Mutex };
+use std::sync::atomic::{ AtomicUsize, Ordering };
+use wasm_link::{ Binding, CallContext, Engine, Function, FunctionKind, Interface, Linker, ReturnKind };
+use wasm_link::cardinality::ExactlyOne ;
+use wasmtime::Config;
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { burn_fuel: "burn-fuel" };
+}
+
+#[test]
+fn call_context_overrides_limits_for_a_single_dispatch() {
+	let mut config = Config::new();
+	config.consume_fuel( true );
+	config.epoch_interruption( true );
+	let engine = Engine::new( &config ).expect( "failed to create engine" );
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let reports = Arc::new( Mutex::new( Vec::new() ));
+	let epoch_limits = Arc::new( AtomicUsize::new( 0 ));
+
+	let recorded = Arc::clone( &reports );
+	let limited = Arc::clone( &epoch_limits );
+	let plugin_instance = plugins.burn_fuel.plugin
+		.with_fuel_limiter(| _store, _interface, _function, _metadata | 100_000 )
+		.with_epoch_limiter( move | _store, _interface, _function, _metadata | {
+			limited.fetch_add( 1, Ordering::Relaxed );
+			1_000
+		})
+		.with_dispatch_observer( move | report | recorded.lock().unwrap().push(( report.fuel(), report.succeeded() )))
+		.instantiate( &engine, &linker )
+		.expect( "failed to instantiate plugin" );
+
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, Interface::new(
+			HashMap::from([( "burn".into(), Function::new( FunctionKind::Freestanding, ReturnKind::AssumeNoResources ))]),
+			HashSet::new(),
+		))]),
+		ExactlyOne( "_".to_string(), plugin_instance ),
+	);
+
+	let context = CallContext::new().with_fuel( 1 ).with_epoch_deadline( 1_000 );
+	assert_eq!(( context.fuel(), context.epoch_deadline() ), ( Some( 1 ), Some( 1_000 )));
+	let _ = binding.dispatch( "root", "burn", &[] );
+	let _ = binding.dispatch_with( &context, "root", "burn", &[] );
+	assert_eq!( *reports.lock().unwrap(), [( Some( 100_000 ), true ), ( Some( 1 ), false )]);
+	assert_eq!( epoch_limits.load( Ordering::Relaxed ), 1, "the epoch limiter is bypassed by the override" );
+}
+
+#[test]
+fn unobserved_dispatches_are_not_reported() {
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let reports = Arc::new( AtomicUsize::new( 0 ));
+
+	let recorded = Arc::clone( &reports );
+	let plugin_instance = plugins.burn_fuel.plugin
+		.with_dispatch_observer( move | _ | { recorded.fetch_add( 1, Ordering::Relaxed ); })
+		.instantiate( &engine, &linker )
+		.expect( "failed to instantiate plugin" );
+
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, Interface::new(
+			HashMap::from([( "burn".into(), Function::new( FunctionKind::Freestanding, ReturnKind::AssumeNoResources ))]),
+			HashSet::new(),
+		))]),
+		ExactlyOne( "_".to_string(), plugin_instance ),
+	);
+
+	let context = CallContext::new().unobserved();
+	assert!( !context.is_observed() );
+	assert!( binding.dispatch_with( &context, "root", "burn", &[] ).is_ok() );
+	assert_eq!( reports.load( Ordering::Relaxed ), 0 );
+	assert!( binding.dispatch( "root", "burn", &[] ).is_ok() );
+	assert_eq!( reports.load( Ordering::Relaxed ), 1 );
+}
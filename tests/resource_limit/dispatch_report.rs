@@ -0,0 +1,120 @@
+use std::collections::{ HashMap, HashSet };
+use std::sync::{ Arc, Mutex };
+use std::time::Duration ;
+use wasm_link::{ Binding, Engine, Function, FunctionKind, Interface, Linker, ReturnKind };
+use wasm_link::cardinality::ExactlyOne ;
+use wasmtime::Config;
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { burn_fuel: "burn-fuel" };
+}
+
+#[derive( Debug, PartialEq )]
+struct Report {
+	function: String,
+	fuel: Option<u64>,
+	fuel_consumed: Option<u64>,
+	succeeded: bool,
+}
+
+fn report_with_fuel( fuel: Option<u64> ) -> Report {
+	let mut config = Config::new();
+	config.consume_fuel( fuel.is_some() );
+	let engine = Engine::new( &config ).expect( "failed to create engine" );
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let reports = Arc::new( Mutex::new( Vec::new() ));
+
+	let recorded = Arc::clone( &reports );
+	let mut plugin = plugins.burn_fuel.plugin
+		.with_dispatch_observer( move | report | recorded.lock().unwrap().push( Report {
+			function: report.call().function_name().to_string(),
+			fuel: report.fuel(),
+			fuel_consumed: report.fuel_consumed(),
+			succeeded: report.succeeded(),
+		}));
+	if let Some( fuel ) = fuel {
+		plugin = plugin.with_fuel_limiter( move | _store, _interface, _function, _metadata | fuel );
+	}
+	let plugin_instance = plugin
+		.instantiate( &engine, &linker )
+		.expect( "failed to instantiate plugin" );
+
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, Interface::new(
+			HashMap::from([( "burn".into(), Function::new( FunctionKind::Freestanding, ReturnKind::AssumeNoResources ))]),
+			HashSet::new(),
+		))]),
+		ExactlyOne( "_".to_string(), plugin_instance ),
+	);
+
+	let _ = binding.dispatch( "root", "burn", &[] );
+	let _ = binding.dispatch( "root", "missing", &[] );
+	let mut reports = reports.lock().unwrap();
+	assert_eq!( reports.len(), 1, "only calls entering the plugin are reported" );
+	reports.remove( 0 )
+}
+
+#[test]
+fn report_contains_consumed_fuel() {
+	let report = report_with_fuel( Some( 100_000 ));
+	assert_eq!( report.function, "burn" );
+	assert_eq!( report.fuel, Some( 100_000 ));
+	assert!( report.succeeded );
+	let consumed = report.fuel_consumed.expect( "fuel consumption was not reported" );
+	assert!( consumed > 0 && consumed < 100_000, "unexpected fuel consumption: {consumed}" );
+}
+
+#[test]
+fn exhausted_fuel_is_fully_consumed() {
+	assert_eq!( report_with_fuel( Some( 1 )), Report {
+		function: "burn".to_string(),
+		fuel: Some( 1 ),
+		fuel_consumed: Some( 1 ),
+		succeeded: false,
+	});
+}
+
+#[test]
+fn report_without_fuel_has_no_fuel_figures() {
+	let report = report_with_fuel( None );
+	assert_eq!(( report.fuel, report.fuel_consumed, report.succeeded ), ( None, None, true ));
+}
+
+#[test]
+fn slow_call_observer_only_sees_calls_over_the_threshold() {
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let observed = Arc::new( Mutex::new( Vec::new() ));
+
+	let ( all, slow, never ) = ( Arc::clone( &observed ), Arc::clone( &observed ), Arc::clone( &observed ));
+	let plugin_instance = plugins.burn_fuel.plugin
+		.with_dispatch_observer( move | report | all.lock().unwrap().push( format!( "all:{}", report.call().function_name() )))
+		.with_slow_call_observer( Duration::ZERO, move | report | slow.lock().unwrap().push( format!(
+			"slow:{}:{}",
+			report.call().plugin_id::<String>().expect( "plugin id is a String" ),
+			report.call().function_name(),
+		)))
+		.instantiate( &engine, &linker )
+		.expect( "failed to instantiate plugin" );
+	let fast_instance = fixtures::plugins( &engine ).burn_fuel.plugin
+		.with_slow_call_observer( Duration::from_hours( 1 ), move | _ | never.lock().unwrap().push( "never".to_string() ))
+		.instantiate( &engine, &linker )
+		.expect( "failed to instantiate plugin" );
+
+	let interface = || HashMap::from([( bindings.root.name.clone(), Interface::new(
+		HashMap::from([( "burn".into(), Function::new( FunctionKind::Freestanding, ReturnKind::AssumeNoResources ))]),
+		HashSet::new(),
+	))]);
+	let binding = Binding::new( bindings.root.package.clone(), interface(), ExactlyOne( "slow".to_string(), plugin_instance ));
+	let fast_binding = Binding::new( bindings.root.package.clone(), interface(), ExactlyOne( "fast".to_string(), fast_instance ));
+
+	let _ = binding.dispatch( "root", "burn", &[] );
+	let _ = fast_binding.dispatch( "root", "burn", &[] );
+	assert_eq!( *observed.lock().unwrap(), [ "all:burn", "slow:slow:burn" ]);
+}
@@ -0,0 +1,115 @@
+use std::collections::{ HashMap, HashSet };
+use std::sync::{ Arc, atomic::{ AtomicBool, AtomicU64, Ordering }};
+use std::thread;
+use wasm_link::{ Binding, DispatchError, Engine, EpochBehavior, Function, FunctionKind, Interface, Linker, ReturnKind, Val };
+use wasm_link::cardinality::ExactlyOne ;
+use wasmtime::{ Config, UpdateDeadline };
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { burn_fuel: "burn-fuel" };
+}
+
+fn engine() -> Engine {
+	let mut config = Config::new();
+	config.epoch_interruption( true );
+	Engine::new( &config ).expect( "failed to create engine" )
+}
+
+fn interface() -> HashMap<String, Interface> {
+	HashMap::from([( "root".to_string(), Interface::new(
+		HashMap::from([( "burn".into(), Function::new( FunctionKind::Freestanding, ReturnKind::AssumeNoResources ))]),
+		HashSet::new(),
+	))])
+}
+
+/// Runs `dispatch` while another thread keeps incrementing the epoch.
+fn with_ticker<T>( engine: &Engine, dispatch: impl FnOnce() -> T ) -> T {
+	let stop = Arc::new( AtomicBool::new( false ));
+	let stop_clone = Arc::clone( &stop );
+	let engine_clone = engine.clone();
+	let handle = thread::spawn( move || while !stop_clone.load( Ordering::Acquire ) {
+		engine_clone.increment_epoch();
+		thread::yield_now();
+	});
+	let result = dispatch();
+	stop.store( true, Ordering::Release );
+	let _ = handle.join();
+	result
+}
+
+#[test]
+fn yielding_plugins_complete_past_their_deadline() {
+	let engine = engine();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let executor = futures::executor::ThreadPool::new().expect( "Failed to create async executor" );
+	let plugin_instance = futures::executor::block_on( plugins.burn_fuel.plugin
+		.with_epoch_limiter(| _, _, _, _ | 1 )
+		.with_epoch_behavior( EpochBehavior::YieldAndContinue( 1 ))
+		.instantiate_async( &engine, &linker, executor )
+	).expect( "failed to instantiate plugin" );
+	let binding = Binding::new( bindings.root.package, interface(), ExactlyOne( "_".to_string(), plugin_instance ));
+
+	match with_ticker( &engine, || futures::executor::block_on( binding.dispatch_async( "root", "burn", &[] ))) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+		other => panic!( "Expected Ok( U32( 42 )), got: {:#?}", other ),
+	}
+}
+
+#[test]
+fn yielding_requires_an_async_instance() {
+	let engine = engine();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	assert!( plugins.burn_fuel.plugin
+		.with_epoch_behavior( EpochBehavior::YieldAndContinue( 1 ))
+		.instantiate( &engine, &linker )
+		.is_err()
+	);
+}
+
+#[test]
+fn callback_decides_whether_to_continue() {
+	let engine = engine();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let extensions = Arc::new( AtomicU64::new( 0 ));
+	let extensions_clone = Arc::clone( &extensions );
+	let plugin_instance = plugins.burn_fuel.plugin
+		.with_epoch_limiter(| _, _, _, _ | 1 )
+		.with_epoch_behavior( EpochBehavior::Callback( Box::new( move | _ | {
+			extensions_clone.fetch_add( 1, Ordering::Relaxed );
+			Ok( UpdateDeadline::Continue( 1 ))
+		})))
+		.instantiate( &engine, &linker )
+		.expect( "failed to instantiate plugin" );
+	let binding = Binding::new( bindings.root.package, interface(), ExactlyOne( "_".to_string(), plugin_instance ));
+
+	match with_ticker( &engine, || binding.dispatch( "root", "burn", &[] )) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+		other => panic!( "Expected Ok( U32( 42 )), got: {:#?}", other ),
+	}
+	assert!( extensions.load( Ordering::Relaxed ) > 0 );
+}
+
+#[test]
+fn callback_errors_fail_the_call() {
+	let engine = engine();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let plugin_instance = plugins.burn_fuel.plugin
+		.with_epoch_limiter(| _, _, _, _ | 1 )
+		.with_epoch_behavior( EpochBehavior::Callback( Box::new(| _ | Err( wasmtime::Error::msg( "out of time" )))))
+		.instantiate( &engine, &linker )
+		.expect( "failed to instantiate plugin" );
+	let binding = Binding::new( bindings.root.package, interface(), ExactlyOne( "_".to_string(), plugin_instance ));
+
+	match with_ticker( &engine, || binding.dispatch( "root", "burn", &[] )) {
+		Ok( ExactlyOne( _, Err( DispatchError::RuntimeException( _ )))) => {}
+		other => panic!( "Expected RuntimeException, got: {:#?}", other ),
+	}
+}
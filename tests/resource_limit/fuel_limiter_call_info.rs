@@ -0,0 +1,51 @@
+use std::collections::{ HashMap, HashSet };
+use std::sync::{ Arc, Mutex };
+use wasm_link::{ Binding, CallContext, Engine, Function, FunctionKind, Interface, Linker, ReturnKind, Val };
+use wasm_link::cardinality::ExactlyOne ;
+use wasmtime::Config;
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { burn_fuel: "burn-fuel" };
+}
+
+#[test]
+fn closure_receives_plugin_id_and_dispatch_id() {
+
+	let mut config = Config::new();
+	config.consume_fuel( true );
+	let engine = Engine::new( &config ).expect( "failed to create engine" );
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let seen = Arc::new( Mutex::new( None ));
+
+	let recorded = Arc::clone( &seen );
+	let plugin_instance = plugins.burn_fuel.plugin
+		.with_call_fuel_limiter( move | _store, call | {
+			assert_eq!( call.interface_path(), "test:fuel/root" );
+			assert_eq!( call.function_name(), "burn" );
+			assert_eq!( call.function().return_kind(), ReturnKind::AssumeNoResources );
+			assert_eq!( call.plugin_id::<u32>(), None );
+			*recorded.lock().unwrap() = Some(( call.plugin_id::<String>().cloned(), call.dispatch_id() ));
+			100_000
+		})
+		.instantiate( &engine, &linker )
+		.expect( "failed to instantiate plugin" );
+
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, Interface::new(
+			HashMap::from([( "burn".into(), Function::new( FunctionKind::Freestanding, ReturnKind::AssumeNoResources ))]),
+			HashSet::new(),
+		))]),
+		ExactlyOne( "tenant-a".to_string(), plugin_instance ),
+	);
+
+	let context = CallContext::new();
+	match binding.dispatch_with( &context, "root", "burn", &[] ) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+		other => panic!( "Expected Ok( U32( 42 )), got: {:#?}", other ),
+	}
+	assert_eq!( *seen.lock().unwrap(), Some(( Some( "tenant-a".to_string() ), context.dispatch_id() )));
+}
@@ -0,0 +1,46 @@
+use std::collections::{ HashMap, HashSet };
+use wasm_link::{ Binding, Engine, Function, FunctionKind, Interface, Linker, ReturnKind };
+use wasm_link::cardinality::ExactlyOne ;
+use wasm_link::limits::FuelProfile ;
+use wasmtime::Config;
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { burn_fuel: "burn-fuel" };
+}
+
+#[test]
+fn profile_records_fuel_per_plugin_and_function() {
+	let mut config = Config::new();
+	config.consume_fuel( true );
+	let engine = Engine::new( &config ).expect( "failed to create engine" );
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let profile = FuelProfile::new();
+
+	let plugin_instance = plugins.burn_fuel.plugin
+		.with_fuel_limiter(| _store, _interface, _function, _metadata | 100_000 )
+		.with_dispatch_observer( profile.observer( "burner" ))
+		.instantiate( &engine, &linker )
+		.expect( "failed to instantiate plugin" );
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, Interface::new(
+			HashMap::from([( "burn".into(), Function::new( FunctionKind::Freestanding, ReturnKind::AssumeNoResources ))]),
+			HashSet::new(),
+		))]),
+		ExactlyOne( "_".to_string(), plugin_instance ),
+	);
+
+	( 0..3 ).for_each(| _ | { let _ = binding.dispatch( "root", "burn", &[] ); });
+	let stats = profile.stats( "burner", "test:fuel/root", "burn" ).expect( "calls were not recorded" );
+	assert_eq!( stats.calls(), 3 );
+	assert!( stats.max() > 0 && stats.mean() <= stats.max(), "{stats:?}" );
+
+	let table = profile.to_string();
+	let mut lines = table.lines();
+	assert!( lines.next().is_some_and(| header | header.starts_with( "plugin" ) && header.ends_with( "max" )), "{table}" );
+	assert!( lines.next().is_some_and(| row | row.starts_with( "burner" ) && row.contains( "test:fuel/root#burn" )), "{table}" );
+	assert_eq!( lines.next(), None );
+}
@@ -0,0 +1,106 @@
+use std::collections::{ HashMap, HashSet };
+use wasm_link::{ Binding, Engine, Function, FunctionKind, Interface, InterfaceBuilder, Linker, Plugin, ReturnKind, Val };
+use wasm_link::cardinality::ExactlyOne ;
+use wasmtime::Config;
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { burn_fuel: "burn-fuel" };
+}
+
+fn dispatch_with_interface(
+	config: &Config,
+	customise_plugin: impl FnOnce( Plugin<crate::fixture_linking::TestContext> ) -> Plugin<crate::fixture_linking::TestContext>,
+	customise_interface: impl FnOnce( Interface ) -> Interface,
+) -> Result<ExactlyOne<String, Result<Val, wasm_link::DispatchError>>, wasm_link::DispatchError> {
+	let engine = Engine::new( config ).expect( "failed to create engine" );
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+
+	let plugin_instance = customise_plugin( plugins.burn_fuel.plugin )
+		.instantiate( &engine, &linker )
+		.expect( "failed to instantiate plugin" );
+
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, customise_interface( Interface::new(
+			HashMap::from([( "burn".into(), Function::new( FunctionKind::Freestanding, ReturnKind::AssumeNoResources ))]),
+			HashSet::new(),
+		)))]),
+		ExactlyOne( "_".to_string(), plugin_instance ),
+	);
+
+	binding.dispatch( "root", "burn", &[] )
+}
+
+fn fuel_config() -> Config {
+	let mut config = Config::new();
+	config.consume_fuel( true );
+	config
+}
+
+fn epoch_config() -> Config {
+	let mut config = Config::new();
+	config.epoch_interruption( true );
+	config
+}
+
+#[test]
+fn interface_default_fuel_applies_without_limiter() {
+	match dispatch_with_interface( &fuel_config(), | plugin | plugin, | interface | interface.with_default_fuel( 1 )) {
+		Ok( ExactlyOne( _, Err( wasm_link::DispatchError::RuntimeException( _ )))) => {}
+		other => panic!( "Expected RuntimeException from fuel exhaustion, got: {:#?}", other ),
+	}
+	match dispatch_with_interface( &fuel_config(), | plugin | plugin, | interface | interface.with_default_fuel( 100_000 )) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+		other => panic!( "Expected Ok( U32( 42 )), got: {:#?}", other ),
+	}
+}
+
+#[test]
+fn fuel_limiter_overrides_interface_default() {
+	let result = dispatch_with_interface(
+		&fuel_config(),
+		| plugin | plugin.with_call_fuel_limiter(| _store, call | {
+			assert_eq!( call.function().default_fuel(), Some( 1 ));
+			100_000
+		}),
+		| interface | interface.with_default_fuel( 1 ),
+	);
+	match result {
+		Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+		other => panic!( "Expected Ok( U32( 42 )), got: {:#?}", other ),
+	}
+}
+
+#[test]
+fn interface_default_epoch_deadline_applies_without_limiter() {
+	match dispatch_with_interface( &epoch_config(), | plugin | plugin, | interface | interface.with_default_epoch_deadline( 0 )) {
+		Ok( ExactlyOne( _, Err( wasm_link::DispatchError::RuntimeException( _ )))) => {}
+		other => panic!( "Expected RuntimeException from epoch exhaustion, got: {:#?}", other ),
+	}
+	match dispatch_with_interface(
+		&epoch_config(),
+		| plugin | plugin.with_epoch_limiter(| _store, _interface, _function, _metadata | 1_000_000 ),
+		| interface | interface.with_default_epoch_deadline( 0 ),
+	) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+		other => panic!( "Expected Ok( U32( 42 )), got: {:#?}", other ),
+	}
+}
+
+#[test]
+fn interface_default_fuel_applies_to_functions_declared_later() {
+	let result = dispatch_with_interface(
+		&fuel_config(),
+		| plugin | plugin,
+		| _ | InterfaceBuilder::from( Interface::new( HashMap::new(), HashSet::new() ).with_default_fuel( 1 ))
+			.function( "burn", ReturnKind::AssumeNoResources )
+			.build(),
+	);
+	match result {
+		Ok( ExactlyOne( _, Err( wasm_link::DispatchError::RuntimeException( _ )))) => {}
+		other => panic!( "Expected RuntimeException from fuel exhaustion, got: {:#?}", other ),
+	}
+}
@@ -0,0 +1,59 @@
+use std::collections::{ HashMap, HashSet };
+use std::sync::{ Arc, Mutex };
+use wasm_link::{ Binding, CallInfo, DispatchReport, Engine, Function, FunctionKind, Interface, Linker, ReturnKind };
+use wasm_link::cardinality::ExactlyOne ;
+use wasm_link::limits::{ Limiter, Limits };
+use wasmtime::Config;
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { burn_fuel: "burn-fuel" };
+}
+
+/// Gives each call the fuel the previous one consumed plus 1, recording what it saw.
+struct Tight {
+	next: u64,
+	seen: Arc<Mutex<Vec<( u64, u64 )>>>,
+}
+
+impl Limiter for Tight {
+	fn limit( &mut self, _call: &CallInfo<'_> ) -> Limits { Limits::new().with_fuel( self.next ) }
+	fn observe( &mut self, report: &DispatchReport<'_> ) {
+		let ( fuel, consumed ) = ( report.fuel().unwrap(), report.fuel_consumed().unwrap() );
+		self.seen.lock().unwrap().push(( fuel, consumed ));
+		self.next = consumed + 1 ;
+	}
+}
+
+#[test]
+fn limiter_sets_fuel_and_observes_consumption() {
+	let mut config = Config::new();
+	config.consume_fuel( true );
+	let engine = Engine::new( &config ).expect( "failed to create engine" );
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let seen = Arc::new( Mutex::new( Vec::new() ));
+
+	let plugin_instance = plugins.burn_fuel.plugin
+		.with_fuel_limiter(| _store, _interface, _function, _metadata | 1 )
+		.with_limiter( Tight { next: 100_000, seen: Arc::clone( &seen ) })
+		.instantiate( &engine, &linker )
+		.expect( "failed to instantiate plugin" );
+
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, Interface::new(
+			HashMap::from([( "burn".into(), Function::new( FunctionKind::Freestanding, ReturnKind::AssumeNoResources ))]),
+			HashSet::new(),
+		))]),
+		ExactlyOne( "_".to_string(), plugin_instance ),
+	);
+
+	assert!( binding.dispatch( "root", "burn", &[] ).is_ok() );
+	assert!( binding.dispatch( "root", "burn", &[] ).is_ok() );
+	let seen = seen.lock().unwrap();
+	let consumed = seen[0].1 ;
+	assert!( consumed > 0 );
+	assert_eq!( *seen, [( 100_000, consumed ), ( consumed + 1, consumed )]);
+}
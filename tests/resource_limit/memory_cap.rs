@@ -0,0 +1,89 @@
+use std::collections::{ HashMap, HashSet };
+use wasm_link::{ Binding, Engine, Function, FunctionKind, Interface, Linker, PluginContext, ResourceTable, ReturnKind, Val };
+use wasm_link::cardinality::ExactlyOne ;
+use wasm_link::limits::MemoryCap ;
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { grow_memory: "grow-memory" };
+}
+
+fn dispatch_grow_memory( cap: MemoryCap ) -> Result<ExactlyOne<String, Result<Val, wasm_link::DispatchError>>, wasm_link::DispatchError> {
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+
+	let plugin_instance = plugins.grow_memory.plugin
+		.with_memory_cap( cap )
+		.instantiate( &engine, &linker )
+		.expect( "failed to instantiate plugin" );
+
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, Interface::new(
+			HashMap::from([( "grow-memory".into(), Function::new( FunctionKind::Freestanding, ReturnKind::AssumeNoResources ))]),
+			HashSet::new(),
+		))]),
+		ExactlyOne( "_".to_string(), plugin_instance ),
+	);
+
+	binding.dispatch( "root", "grow-memory", &[] )
+}
+
+#[test]
+fn memory_cap_denies_memory_growth() {
+	match dispatch_grow_memory( MemoryCap::new( 65536 )) {
+		Ok( ExactlyOne( _, Ok( Val::S32( -1 )))) => {}
+		other => panic!( "Expected Ok( S32( -1 )) from denied memory growth, got: {:#?}", other ),
+	}
+}
+
+#[test]
+fn memory_cap_can_trap_instead() {
+	match dispatch_grow_memory( MemoryCap::new( 65536 ).trap_on_failure( true )) {
+		Ok( ExactlyOne( _, Err( wasm_link::DispatchError::RuntimeException( _ )))) => {}
+		other => panic!( "Expected RuntimeException from trapping memory cap, got: {:#?}", other ),
+	}
+}
+
+#[test]
+fn memory_cap_allows_growth_within_the_cap() {
+	match dispatch_grow_memory( MemoryCap::from( 2 * 65536 )) {
+		Ok( ExactlyOne( _, Ok( Val::S32( 1 )))) => {}
+		other => panic!( "Expected Ok( S32( 1 )) from memory growth within cap, got: {:#?}", other ),
+	}
+}
+
+#[test]
+fn memory_cap_survives_replacing_the_resource_table() {
+	let mut config = wasmtime::Config::new();
+	config.epoch_interruption( true );
+	let engine = Engine::new( &config ).expect( "failed to create engine" );
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+
+	let plugin_instance = plugins.grow_memory.plugin
+		.with_memory_cap( 2 * 65536 )
+		.with_epoch_limiter(| store, _, _, _ | {
+			*store.data_mut().resource_table() = ResourceTable::new();
+			1_000
+		})
+		.instantiate( &engine, &linker )
+		.expect( "failed to instantiate plugin" );
+
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, Interface::new(
+			HashMap::from([( "grow-memory".into(), Function::new( FunctionKind::Freestanding, ReturnKind::AssumeNoResources ))]),
+			HashSet::new(),
+		))]),
+		ExactlyOne( "_".to_string(), plugin_instance ),
+	);
+
+	match binding.dispatch( "root", "grow-memory", &[] ) {
+		Ok( ExactlyOne( _, Ok( Val::S32( 1 )))) => {}
+		other => panic!( "Expected Ok( S32( 1 )) from memory growth within cap, got: {:#?}", other ),
+	}
+}
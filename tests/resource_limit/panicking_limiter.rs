@@ -0,0 +1,52 @@
+use std::collections::{ HashMap, HashSet };
+use std::sync::Arc ;
+use std::sync::atomic::{ AtomicUsize, Ordering };
+use wasm_link::{ Binding, DispatchError, Engine, Function, FunctionKind, Interface, Linker, PanicPolicy, ReturnKind };
+use wasm_link::cardinality::ExactlyOne ;
+use wasmtime::Config ;
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { burn_fuel: "burn-fuel" };
+}
+
+#[test]
+fn a_panicking_limiter_keeps_limiting_later_calls() {
+
+	let mut config = Config::new();
+	config.consume_fuel( true );
+	let engine = Engine::new( &config ).expect( "failed to create engine" );
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+
+	// Plenty of fuel is left in the store, so only the limiter can exhaust the second call
+	let calls = Arc::new( AtomicUsize::new( 0 ));
+	let plugin_instance = plugins.burn_fuel.plugin
+		.with_initial_fuel( 1_000_000 )
+		.with_fuel_limiter( move | _store, _interface, _function, _metadata | {
+			assert!( calls.fetch_add( 1, Ordering::Relaxed ) > 0, "limiter bug" );
+			1
+		})
+		.with_panic_policy( PanicPolicy::Continue )
+		.instantiate( &engine, &linker )
+		.expect( "failed to instantiate plugin" );
+
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, Interface::new(
+			HashMap::from([( "burn".into(), Function::new( FunctionKind::Freestanding, ReturnKind::AssumeNoResources ))]),
+			HashSet::new(),
+		))]),
+		ExactlyOne( "_".to_string(), plugin_instance ),
+	);
+
+	match binding.dispatch( "root", "burn", &[] ) {
+		Ok( ExactlyOne( _, Err( DispatchError::HostPanic( message )))) => assert_eq!( message, "limiter bug" ),
+		other => panic!( "Expected HostPanic on first dispatch, got: {:#?}", other ),
+	}
+	match binding.dispatch( "root", "burn", &[] ) {
+		Ok( ExactlyOne( _, Err( DispatchError::RuntimeException( _ )))) => {}
+		other => panic!( "Expected the limiter to exhaust the second dispatch, got: {:#?}", other ),
+	}
+}
@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+use wasm_link::{ Binding, DispatchError, Engine, Linker, Val };
+use wasm_link::cardinality::ExactlyOne ;
+use wasm_link::limits::PayloadLimits ;
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { lists: "lists" };
+}
+
+fn dispatch( function: &str, data: &[Val] ) -> Result<Val, DispatchError> {
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+
+	let plugin_instance = plugins.lists.plugin
+		.with_payload_limits( PayloadLimits::new().with_max_list_len( 3 ))
+		.instantiate( &engine, &linker )
+		.expect( "Failed to instantiate plugin" );
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		ExactlyOne( "_".to_string(), plugin_instance ),
+	);
+
+	match binding.dispatch( "root", function, data ) {
+		Ok( ExactlyOne( _, result )) => result,
+		Err( err ) => panic!( "Dispatch failed: {err:#?}" ),
+	}
+}
+
+#[test]
+fn arguments_within_the_limits_are_passed() {
+	match dispatch( "count", &[ Val::List( vec![ Val::U32( 1 ), Val::U32( 2 ) ])]) {
+		Ok( Val::U32( 2 )) => {}
+		value => panic!( "Expected Ok( U32( 2 )), found: {value:#?}" ),
+	}
+}
+
+#[test]
+fn oversized_arguments_are_rejected_before_the_call() {
+	match dispatch( "count", &[ Val::List( vec![ Val::U32( 0 ); 5 ])]) {
+		Err( DispatchError::PayloadTooLarge( message )) => assert_eq!( message, "list of 5 elements exceeds the cap of 3" ),
+		value => panic!( "Expected PayloadTooLarge, found: {value:#?}" ),
+	}
+}
+
+#[test]
+fn oversized_results_are_rejected() {
+	match dispatch( "get-list", &[] ) {
+		Err( DispatchError::PayloadTooLarge( message )) => assert_eq!( message, "list of 4 elements exceeds the cap of 3" ),
+		value => panic!( "Expected PayloadTooLarge, found: {value:#?}" ),
+	}
+}
@@ -0,0 +1,40 @@
+(component
+	(core module $m
+		(memory (export "memory") 1)
+		(global $heap (mut i32) (i32.const 1024))
+		(func $realloc (export "realloc") (param i32 i32 i32 i32) (result i32)
+			(local $ptr i32)
+			global.get $heap
+			local.set $ptr
+			global.get $heap
+			local.get 3
+			i32.add
+			global.set $heap
+			local.get $ptr
+		)
+		(func $count (export "count") (param i32 i32) (result i32)
+			local.get 1
+		)
+		(func $get_list (export "get-list") (result i32)
+			i32.const 0
+			i32.const 16
+			i32.store
+			i32.const 4
+			i32.const 4
+			i32.store
+			i32.const 0
+		)
+	)
+	(core instance $i (instantiate $m))
+	(func $count (export "count") (param "items" (list u32)) (result u32)
+		(canon lift (core func $i "count") (memory $i "memory") (realloc (func $i "realloc")))
+	)
+	(func $get_list (export "get-list") (result (list u32))
+		(canon lift (core func $i "get-list") (memory $i "memory"))
+	)
+	(instance $inst
+		(export "count" (func $count))
+		(export "get-list" (func $get_list))
+	)
+	(export "test:payload/root" (instance $inst))
+)
@@ -0,0 +1,124 @@
+use std::collections::{ HashMap, HashSet };
+use wasm_link::{ Binding, CallContext, Engine, Function, FunctionKind, Interface, Linker, ReturnKind, Val };
+use wasm_link::cardinality::ExactlyOne ;
+use wasmtime::Config ;
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { burn_fuel: "burn-fuel" };
+}
+
+#[test]
+fn bound_plugins_expose_remaining_fuel_and_epoch_deadline_between_calls() {
+
+	let mut config = Config::new();
+	config.consume_fuel( true ).epoch_interruption( true );
+	let engine = Engine::new( &config ).expect( "failed to create engine" );
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+
+	let plugin_instance = plugins.burn_fuel.plugin
+		.with_initial_fuel( 1_000 )
+		.with_fuel_limiter(| _, _, _, _ | 100_000 )
+		.with_epoch_limiter(| _, _, _, _ | 7 )
+		.instantiate( &engine, &linker )
+		.expect( "failed to instantiate plugin" );
+
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, Interface::new(
+			HashMap::from([( "burn".into(), Function::new( FunctionKind::Freestanding, ReturnKind::AssumeNoResources ))]),
+			HashSet::new(),
+		))]),
+		ExactlyOne( "burner".to_string(), plugin_instance ),
+	);
+	let id = "burner".to_string();
+
+	assert_eq!( binding.current_epoch_deadline( &id ), None );
+	assert!( binding.remaining_fuel( &id ).is_some_and(| fuel | fuel <= 1_000 ));
+	assert!( binding.component_info( &id ).is_some_and(| info | info.exports().contains( &"test:fuel/root".to_string() )));
+
+	match binding.dispatch( "root", "burn", &[] ) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+		other => panic!( "Expected Ok( U32( 42 )), got: {:#?}", other ),
+	}
+	assert_eq!( binding.current_epoch_deadline( &id ), Some( 7 ));
+	assert!( binding.remaining_fuel( &id ).is_some_and(| fuel | fuel > 0 && fuel < 100_000 ));
+}
+
+#[test]
+fn a_call_without_a_deadline_clears_the_reported_epoch_deadline() {
+
+	let mut config = Config::new();
+	config.epoch_interruption( true );
+	let engine = Engine::new( &config ).expect( "failed to create engine" );
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+
+	let plugin_instance = plugins.burn_fuel.plugin
+		.instantiate( &engine, &linker )
+		.expect( "failed to instantiate plugin" );
+
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, Interface::new(
+			HashMap::from([( "burn".into(), Function::new( FunctionKind::Freestanding, ReturnKind::AssumeNoResources ))]),
+			HashSet::new(),
+		))]),
+		ExactlyOne( "burner".to_string(), plugin_instance ),
+	);
+	let id = "burner".to_string();
+
+	match binding.dispatch_with( &CallContext::new().with_epoch_deadline( 7 ), "root", "burn", &[] ) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+		other => panic!( "Expected Ok( U32( 42 )), got: {:#?}", other ),
+	}
+	assert_eq!( binding.current_epoch_deadline( &id ), Some( 7 ));
+
+	match binding.dispatch( "root", "burn", &[] ) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+		other => panic!( "Expected Ok( U32( 42 )), got: {:#?}", other ),
+	}
+	assert_eq!( binding.current_epoch_deadline( &id ), None );
+}
+
+#[test]
+fn bound_async_plugins_expose_remaining_fuel_and_epoch_deadline_between_calls() {
+	futures::executor::block_on( async {
+		let mut config = Config::new();
+		config.consume_fuel( true ).epoch_interruption( true );
+		let engine = Engine::new( &config ).expect( "failed to create engine" );
+		let linker = Linker::new( &engine );
+		let executor = futures::executor::ThreadPool::new()
+			.expect( "Failed to create async executor" );
+		let plugins = fixtures::plugins( &engine );
+		let bindings = fixtures::bindings();
+
+		let plugin_instance = plugins.burn_fuel.plugin
+			.with_fuel_limiter(| _, _, _, _ | 100_000 )
+			.with_epoch_limiter(| _, _, _, _ | 7 )
+			.instantiate_async( &engine, &linker, executor )
+			.await
+			.expect( "failed to instantiate plugin" );
+
+		let binding = Binding::new(
+			bindings.root.package,
+			HashMap::from([( bindings.root.name, Interface::new(
+				HashMap::from([( "burn".into(), Function::new( FunctionKind::Freestanding, ReturnKind::AssumeNoResources ))]),
+				HashSet::new(),
+			))]),
+			ExactlyOne( "burner".to_string(), plugin_instance ),
+		);
+		let id = "burner".to_string();
+
+		assert_eq!( binding.current_epoch_deadline( &id ).await, None );
+		match binding.dispatch_async( "root", "burn", &[] ).await {
+			Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+			other => panic!( "Expected Ok( U32( 42 )), got: {:#?}", other ),
+		}
+		assert_eq!( binding.current_epoch_deadline( &id ).await, Some( 7 ));
+		assert!( binding.remaining_fuel( &id ).await.is_some_and(| fuel | fuel > 0 && fuel < 100_000 ));
+	});
+}
@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+use std::sync::{ Arc, Mutex };
+use std::time::{ Duration, Instant };
+use wasm_link::{ Binding, CallContext, DispatchError, Engine, Linker, Val };
+use wasm_link::cardinality::ExactlyOne ;
+use wasm_link::limits::Watchdog ;
+use wasmtime::Config;
+
+fixtures! {
+	bindings = { root: "root" };
+	plugins  = { spin: "spin" };
+}
+
+#[test]
+fn watchdog_interrupts_calls_past_the_ceiling() {
+	let mut config = Config::new();
+	config.epoch_interruption( true );
+	let engine = Engine::new( &config ).expect( "failed to create engine" );
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let watchdog = Watchdog::start( &engine, Duration::from_millis( 1 ), Duration::from_millis( 20 ));
+
+	let plugin_instance = plugins.spin.plugin
+		.with_watchdog( &watchdog )
+		.instantiate( &engine, &linker )
+		.expect( "failed to instantiate plugin" );
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		ExactlyOne( "_".to_string(), plugin_instance ),
+	);
+
+	match binding.dispatch( "root", "quick", &[] ) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+		other => panic!( "Expected Ok( U32( 42 )), got: {:#?}", other ),
+	}
+	match binding.dispatch( "root", "spin", &[] ) {
+		Ok( ExactlyOne( _, Err( DispatchError::RuntimeException( _ )))) => {}
+		other => panic!( "Expected RuntimeException from the watchdog, got: {:#?}", other ),
+	}
+}
+
+#[test]
+fn watchdog_interrupts_calls_past_the_dispatch_deadline() {
+	let mut config = Config::new();
+	config.epoch_interruption( true );
+	let engine = Engine::new( &config ).expect( "failed to create engine" );
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let watchdog = Watchdog::start( &engine, Duration::from_millis( 1 ), Duration::from_secs( 45 ));
+
+	let plugin_instance = plugins.spin.plugin
+		.with_watchdog( &watchdog )
+		.instantiate( &engine, &linker )
+		.expect( "failed to instantiate plugin" );
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		ExactlyOne( "_".to_string(), plugin_instance ),
+	);
+
+	let started = Instant::now();
+	let context = CallContext::new().with_deadline( started + Duration::from_millis( 20 ));
+	match binding.dispatch_with( &context, "root", "spin", &[] ) {
+		Ok( ExactlyOne( _, Err( DispatchError::RuntimeException( _ )))) => {}
+		other => panic!( "Expected RuntimeException past the deadline, got: {:#?}", other ),
+	}
+	assert!( started.elapsed() < Duration::from_secs( 30 ), "the call ran until the watchdog's ceiling" );
+}
+
+#[test]
+fn calls_starting_past_the_dispatch_deadline_are_refused() {
+	let engine = Engine::default();
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let plugin_instance = plugins.spin.plugin
+		.instantiate( &engine, &linker )
+		.expect( "failed to instantiate plugin" );
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		ExactlyOne( "_".to_string(), plugin_instance ),
+	);
+
+	let context = CallContext::new().with_deadline( Instant::now() );
+	match binding.dispatch_with( &context, "root", "quick", &[] ) {
+		Ok( ExactlyOne( _, Err( DispatchError::RuntimeException( err )))) => {
+			assert_eq!( err.downcast_ref::<wasmtime::Trap>(), Some( &wasmtime::Trap::Interrupt ));
+		}
+		other => panic!( "Expected an interrupt, got: {:#?}", other ),
+	}
+	match binding.dispatch( "root", "quick", &[] ) {
+		Ok( ExactlyOne( _, Ok( Val::U32( 42 )))) => {}
+		other => panic!( "Expected Ok( U32( 42 )), got: {:#?}", other ),
+	}
+}
+
+#[test]
+fn watchdog_reports_uncontended_long_holds() {
+	let mut config = Config::new();
+	config.epoch_interruption( true );
+	let engine = Engine::new( &config ).expect( "failed to create engine" );
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let watchdog = Watchdog::start( &engine, Duration::from_millis( 1 ), Duration::from_millis( 200 ));
+	let holds = Arc::new( Mutex::new( Vec::new() ));
+
+	let reported = Arc::clone( &holds );
+	let plugin_instance = plugins.spin.plugin
+		.with_watchdog( &watchdog )
+		.with_long_hold_observer( Duration::from_millis( 20 ), move | hold | reported.lock().unwrap().push( *hold ))
+		.instantiate( &engine, &linker )
+		.expect( "failed to instantiate plugin" );
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		ExactlyOne( "_".to_string(), plugin_instance ),
+	);
+
+	let ( dispatch_id, result ) = binding.dispatch_traced( "root", "spin", &[] );
+	match result {
+		Ok( ExactlyOne( _, Err( DispatchError::RuntimeException( _ )))) => {}
+		other => panic!( "Expected RuntimeException from the watchdog, got: {:#?}", other ),
+	}
+	let holds = holds.lock().unwrap();
+	assert_eq!( holds.len(), 1 );
+	assert_eq!( holds[0].dispatch_id(), dispatch_id );
+	assert!( holds[0].held_for() >= Duration::from_millis( 20 ));
+}
+
+#[test]
+fn long_hold_interrupt_ends_calls_at_the_threshold() {
+	let mut config = Config::new();
+	config.epoch_interruption( true );
+	let engine = Engine::new( &config ).expect( "failed to create engine" );
+	let linker = Linker::new( &engine );
+	let plugins = fixtures::plugins( &engine );
+	let bindings = fixtures::bindings();
+	let watchdog = Watchdog::start( &engine, Duration::from_millis( 1 ), Duration::from_secs( 45 ));
+
+	let plugin_instance = plugins.spin.plugin
+		.with_watchdog( &watchdog )
+		.with_long_hold_observer( Duration::from_millis( 20 ), | _ | {})
+		.with_long_hold_interrupt()
+		.instantiate( &engine, &linker )
+		.expect( "failed to instantiate plugin" );
+	let binding = Binding::new(
+		bindings.root.package,
+		HashMap::from([( bindings.root.name, bindings.root.spec )]),
+		ExactlyOne( "_".to_string(), plugin_instance ),
+	);
+
+	let started = Instant::now();
+	match binding.dispatch( "root", "spin", &[] ) {
+		Ok( ExactlyOne( _, Err( DispatchError::RuntimeException( _ )))) => {}
+		other => panic!( "Expected RuntimeException at the long hold threshold, got: {:#?}", other ),
+	}
+	assert!( started.elapsed() < Duration::from_secs( 30 ), "the call ran until the watchdog's ceiling" );
+}
+
+#[test]
+fn ceiling_is_rounded_down_to_whole_ticks() {
+	let engine = Engine::default();
+	assert_eq!( Watchdog::start( &engine, Duration::from_millis( 10 ), Duration::from_millis( 25 )).ceiling_ticks(), 2 );
+	assert_eq!( Watchdog::start( &engine, Duration::from_millis( 10 ), Duration::ZERO ).ceiling_ticks(), 1 );
+}
@@ -0,0 +1,19 @@
+(component
+	(core module $m
+		(func $spin (export "spin") (result i32)
+			(loop $loop (br $loop))
+			(i32.const 0)
+		)
+		(func $quick (export "quick") (result i32)
+			(i32.const 42)
+		)
+	)
+	(core instance $i (instantiate $m))
+	(func $spin (export "spin") (result u32) (canon lift (core func $i "spin")))
+	(func $quick (export "quick") (result u32) (canon lift (core func $i "quick")))
+	(instance $inst
+		(export "spin" (func $spin))
+		(export "quick" (func $quick))
+	)
+	(export "test:watchdog/root" (instance $inst))
+)
@@ -6,8 +6,16 @@ include!( "test_utils/assert_no_warnings.rs" );
 
 	mod fuel_exhaustion ;
 	mod fuel_limiter_closure_args ;
+	mod fuel_limiter_call_info ;
+	mod dispatch_report ;
+	mod call_context_limits ;
+	mod limiter_trait ;
+	mod panicking_limiter ;
+	mod store_inspection ;
+	mod fuel_profile ;
 	mod fuel_limiter_per_call_reset ;
 	mod fuel_limiter_without_limiter ;
+	mod interface_default_limits ;
 	mod initial_fuel_complex_global ;
 	mod initial_fuel_explicit_start ;
 	mod initial_fuel_lifetime_budget ;
@@ -16,11 +24,16 @@ include!( "test_utils/assert_no_warnings.rs" );
 	mod initial_fuel_table_initializer ;
 
 	mod epoch_exhaustion ;
+	mod epoch_behavior ;
 	mod epoch_limiter_closure_args ;
+	mod epoch_limiter_call_info ;
 	mod epoch_limiter_per_call_reset ;
 	mod epoch_limiter_without_limiter ;
+	mod watchdog ;
 
 	mod memory_exhaustion ;
 	mod memory_limiter_without_limiter ;
+	mod memory_cap ;
+	mod payload_limits ;
 
 }
@@ -6,4 +6,5 @@ include!( "test_utils/assert_no_warnings.rs" );
 	mod single_plugin ;
 	mod dependant_plugins ;
 	mod dependant_plugins_async ;
+	mod forwarded_resource ;
 }
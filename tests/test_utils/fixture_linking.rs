@@ -88,12 +88,22 @@ mod fixture_linking {
 	#[derive( Debug )]
 	pub struct TestContext {
 		pub resource_table: wasm_link::ResourceTable,
+		pub call_context: Option<wasm_link::CallContext>,
 	}
 
 	impl wasm_link::PluginContext for TestContext {
 		fn resource_table( &mut self ) -> &mut wasm_link::ResourceTable {
 			&mut self.resource_table
 		}
+		fn set_call_context( &mut self, context: wasm_link::CallContext ) {
+			self.call_context = Some( context );
+		}
+		fn call_context( &self ) -> Option<&wasm_link::CallContext> {
+			self.call_context.as_ref()
+		}
+		fn clear_call_context( &mut self ) {
+			self.call_context = None ;
+		}
 	}
 
 	/// Parsed binding data from fixtures.
@@ -144,7 +154,7 @@ mod fixture_linking {
 		Ok( PluginData {
 			plugin: Plugin::new(
 				component,
-				TestContext { resource_table: wasm_link::ResourceTable::new() },
+				TestContext { resource_table: wasm_link::ResourceTable::new(), call_context: None },
 			),
 		})
 
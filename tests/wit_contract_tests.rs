@@ -3,6 +3,7 @@ use std::collections::HashSet ;
 use wasm_link::{
 	Component, DispatchError, Engine, ResourceCreationError, ResourceReceiveError, Val,
 };
+use wasm_link::protocol ;
 use wasmtime::Store ;
 use wit_component::{ ComponentEncoder, StringEncoding, dummy_module, embed_component_metadata };
 use wit_parser::{ ManglingAndAbi, Resolve, TypeDefKind, WorldItem };
@@ -24,7 +25,7 @@ fn dispatch_errors_cover_the_provided_wit_contract() -> Result<(), Box<dyn std::
 	let instance = linker.instantiate( &mut store, &component )?;
 	let validate = instance.get_func( &mut store, "validate" ).ok_or( "missing validate export" )?;
 
-	let values = dispatch_error_values();
+	let values = dispatch_errors().into_iter().map( Val::from ).collect::<Vec<_>>();
 	let variant_names = values.iter().map( variant_name ).collect::<Result<HashSet<_>, _>>()?;
 	assert_eq!( variant_names.len(), values.len(), "dispatch errors must have unique WIT variants" );
 	assert_eq!( values.len(), contract_case_count, "every WIT dispatch-error case must be produced" );
@@ -42,6 +43,30 @@ fn dispatch_errors_cover_the_provided_wit_contract() -> Result<(), Box<dyn std::
 	Ok(())
 }
 
+#[test]
+fn dispatch_error_codes_match_the_wit_variants() -> Result<(), Box<dyn std::error::Error>> {
+	for error in dispatch_errors() {
+		let code = error.code();
+		assert_eq!( variant_name( &Val::from( error ))?, code );
+	}
+	Ok(())
+}
+
+#[test]
+fn protocol_constants_match_the_wit_contract() -> Result<(), Box<dyn std::error::Error>> {
+	let mut resolve = Resolve::new();
+	let package = resolve.push_str( "wasm-link.wit", protocol::WIT )?;
+	assert_eq!( resolve.packages[package].name.to_string(), protocol::PACKAGE );
+	let interface = resolve.packages[package].interfaces.get( protocol::ERRORS_INTERFACE ).ok_or( "missing errors interface" )?;
+	let ty = resolve.interfaces[*interface].types.get( protocol::DISPATCH_ERROR ).ok_or( "missing dispatch-error type" )?;
+	let TypeDefKind::Variant( variant ) = &resolve.types[*ty].kind else { return Err( "dispatch-error is not a variant".into() )};
+	let cases = variant.cases.iter().map(| case | case.name.as_str() ).collect::<Vec<_>>();
+	assert_eq!( cases, protocol::DISPATCH_ERROR_CASES );
+	let codes = dispatch_errors().iter().map( DispatchError::code ).collect::<HashSet<_>>();
+	assert_eq!( codes, protocol::DISPATCH_ERROR_CASES.into_iter().collect() );
+	Ok(())
+}
+
 fn load_contract() -> Result<(Resolve, wit_parser::WorldId), Box<dyn std::error::Error>> {
 	let mut resolve = Resolve::new();
 	let _ = resolve.push_path( "wit" )?;
@@ -73,19 +98,23 @@ fn variant_case_count(
 	}
 }
 
-fn dispatch_error_values() -> Vec<Val> {
+fn dispatch_errors() -> Vec<DispatchError> {
 	vec![
-		DispatchError::LockRejected.into(),
-		DispatchError::InvalidInterfacePath( "package/interface".to_string() ).into(),
-		DispatchError::InvalidFunction( "function".to_string() ).into(),
-		DispatchError::MissingResponse.into(),
-		DispatchError::RuntimeException( wasmtime::Error::msg( "trap" )).into(),
-		DispatchError::InvalidArgumentList.into(),
-		DispatchError::UnsupportedType( "future".to_string() ).into(),
-		DispatchError::ExecutorUnavailable.into(),
-		DispatchError::ResourceCreationError( ResourceCreationError::ResourceTableFull ).into(),
-		DispatchError::ResourceCreationError( ResourceCreationError::ResourceHandleConversionFailed ).into(),
-		DispatchError::ResourceReceiveError( ResourceReceiveError::InvalidHandle ).into(),
+		DispatchError::LockRejected,
+		DispatchError::PluginDisabled,
+		DispatchError::InvalidInterfacePath( "package/interface".to_string() ),
+		DispatchError::InvalidFunction( "function".to_string() ),
+		DispatchError::MissingResponse,
+		DispatchError::RuntimeException( wasmtime::Error::msg( "trap" )),
+		DispatchError::InvalidArgumentList,
+		DispatchError::UnsupportedType( "future".to_string() ),
+		DispatchError::ExecutorUnavailable,
+		DispatchError::PayloadTooLarge( "list of 2 elements exceeds the cap of 1".to_string() ),
+		DispatchError::AllImplementationsFailed,
+		DispatchError::HostPanic( "observer bug".to_string() ),
+		DispatchError::ResourceCreationError( ResourceCreationError::ResourceTableFull ),
+		DispatchError::ResourceCreationError( ResourceCreationError::ResourceHandleConversionFailed ),
+		DispatchError::ResourceReceiveError( ResourceReceiveError::InvalidHandle ),
 	]
 }
 